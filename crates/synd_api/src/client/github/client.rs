@@ -38,13 +38,17 @@ impl GithubClient {
     }
 
     #[tracing::instrument(name = "github::authenticate", skip_all)]
-    pub async fn authenticate(&self, access_token: &str) -> anyhow::Result<String> {
+    /// Return the node id and the email of the authenticated user
+    pub async fn authenticate(
+        &self,
+        access_token: &str,
+    ) -> anyhow::Result<query::authenticate::AuthenticateViewer> {
         let variables = query::authenticate::Variables {};
         let request = query::Authenticate::build_query(variables);
         let response: query::authenticate::ResponseData =
             self.request(access_token, &request).await?;
 
-        Ok(response.viewer.email)
+        Ok(response.viewer)
     }
 
     async fn request<Body, ResponseData>(
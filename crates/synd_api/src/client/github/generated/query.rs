@@ -4,7 +4,7 @@ pub mod authenticate {
     #![allow(dead_code)]
     use std::result::Result;
     pub const OPERATION_NAME: &str = "Authenticate";
-    pub const QUERY: &str = "query Authenticate {\n  viewer {\n    email,\n  }\n}\n";
+    pub const QUERY: &str = "query Authenticate {\n  viewer {\n    id,\n    email,\n  }\n}\n";
     use super::*;
     use serde::{Deserialize, Serialize};
     #[allow(dead_code)]
@@ -23,6 +23,7 @@ pub mod authenticate {
     }
     #[derive(Deserialize, Debug)]
     pub struct AuthenticateViewer {
+        pub id: ID,
         pub email: String,
     }
 }
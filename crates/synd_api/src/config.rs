@@ -22,6 +22,12 @@ pub mod serve {
     pub const DEFAULT_REQUEST_TIMEOUT: &str = "30s";
     pub const DEFAULT_REQUEST_BODY_LIMIT_BYTES: usize = 1024 * 2;
     pub const DEFAULT_REQUEST_CONCURRENCY_LIMIT: usize = 100;
+    pub const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 300;
+    pub const DEFAULT_EXPENSIVE_RATE_LIMIT_PER_MINUTE: u32 = 30;
+    pub const DEFAULT_GRAPHQL_DEPTH_LIMIT: usize = 10;
+    pub const DEFAULT_GRAPHQL_COMPLEXITY_LIMIT: usize = 20_000;
+    pub const DEFAULT_PERSISTED_QUERY_CACHE_SIZE: usize = 256;
+    pub const DEFAULT_TLS_RELOAD_INTERVAL: &str = "30s";
 
     pub const HEALTH_CHECK_PATH: &str = "/health";
 }
@@ -32,6 +38,11 @@ pub mod metrics {
     pub const MONITOR_INTERVAL: Duration = Duration::from_secs(60);
 }
 
+pub mod fetch {
+    pub const DEFAULT_TIMEOUT: &str = "10s";
+    pub const DEFAULT_CONNECT_TIMEOUT: &str = "10s";
+}
+
 pub mod cache {
     pub const DEFAULT_FEED_CACHE_SIZE_MB: u64 = 100;
     pub const DEFAULT_FEED_CACHE_TTL: &str = "180min";
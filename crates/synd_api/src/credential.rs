@@ -0,0 +1,164 @@
+use std::fmt;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use ring::{
+    aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use synd_feed::types::{FeedCredential, FeedUrl};
+use thiserror::Error;
+
+/// Feed credential encrypted to be stored in the repository
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedCredential {
+    /// Base64 encoded nonce
+    nonce: String,
+    /// Base64 encoded ciphertext with the authentication tag
+    ciphertext: String,
+}
+
+impl fmt::Debug for EncryptedCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedCredential")
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CredentialError {
+    #[error("credential key must be base64 encoded 32 bytes")]
+    InvalidKey,
+    #[error("failed to encrypt credential")]
+    Encrypt,
+    #[error("failed to decrypt credential")]
+    Decrypt,
+    #[error("failed to encode credential: {0}")]
+    Encode(#[from] serde_json::Error),
+}
+
+/// Encrypt and decrypt feed credentials with AES-256-GCM
+/// The owner and the url of the subscription are authenticated
+/// so that the encrypted credential can not be used for other subscriptions
+pub struct CredentialCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl fmt::Debug for CredentialCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialCipher").finish_non_exhaustive()
+    }
+}
+
+impl CredentialCipher {
+    /// Construct the cipher from the base64 encoded 256 bits key
+    pub fn from_base64(key: &str) -> Result<Self, CredentialError> {
+        let key = STANDARD
+            .decode(key.trim())
+            .map_err(|_| CredentialError::InvalidKey)?;
+        let key = UnboundKey::new(&AES_256_GCM, &key).map_err(|_| CredentialError::InvalidKey)?;
+
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    pub fn encrypt(
+        &self,
+        user_id: &str,
+        url: &FeedUrl,
+        credential: &FeedCredential,
+    ) -> Result<EncryptedCredential, CredentialError> {
+        let mut nonce = [0_u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| CredentialError::Encrypt)?;
+
+        let mut in_out = serde_json::to_vec(credential)?;
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Self::aad(user_id, url),
+                &mut in_out,
+            )
+            .map_err(|_| CredentialError::Encrypt)?;
+
+        Ok(EncryptedCredential {
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(in_out),
+        })
+    }
+
+    pub fn decrypt(
+        &self,
+        user_id: &str,
+        url: &FeedUrl,
+        encrypted: &EncryptedCredential,
+    ) -> Result<FeedCredential, CredentialError> {
+        let nonce = STANDARD
+            .decode(&encrypted.nonce)
+            .ok()
+            .and_then(|nonce| Nonce::try_assume_unique_for_key(&nonce).ok())
+            .ok_or(CredentialError::Decrypt)?;
+        let mut in_out = STANDARD
+            .decode(&encrypted.ciphertext)
+            .map_err(|_| CredentialError::Decrypt)?;
+
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Self::aad(user_id, url), &mut in_out)
+            .map_err(|_| CredentialError::Decrypt)?;
+
+        Ok(serde_json::from_slice(plaintext)?)
+    }
+
+    fn aad(user_id: &str, url: &FeedUrl) -> Aad<Vec<u8>> {
+        Aad::from(format!("{user_id}\n{url}").into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> CredentialCipher {
+        CredentialCipher::from_base64(&STANDARD.encode([7_u8; 32])).unwrap()
+    }
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let cipher = cipher();
+        let url = FeedUrl::parse("https://private.example.com/feed.xml").unwrap();
+        let credential = FeedCredential::Basic {
+            username: "user".into(),
+            password: Some("secret".into()),
+        };
+
+        let encrypted = cipher.encrypt("user1", &url, &credential).unwrap();
+        assert!(!encrypted.ciphertext.contains("secret"));
+        assert_ne!(
+            encrypted,
+            cipher.encrypt("user1", &url, &credential).unwrap()
+        );
+        assert_eq!(
+            cipher.decrypt("user1", &url, &encrypted).unwrap(),
+            credential
+        );
+
+        // Encrypted credential is bound to the user and the url
+        assert!(cipher.decrypt("user2", &url, &encrypted).is_err());
+        let other = FeedUrl::parse("https://other.example.com/feed.xml").unwrap();
+        assert!(cipher.decrypt("user1", &other, &encrypted).is_err());
+
+        let another_key = CredentialCipher::from_base64(&STANDARD.encode([8_u8; 32])).unwrap();
+        assert!(another_key.decrypt("user1", &url, &encrypted).is_err());
+    }
+
+    #[test]
+    fn invalid_key() {
+        assert!(CredentialCipher::from_base64("not base64").is_err());
+        assert!(CredentialCipher::from_base64(&STANDARD.encode([0_u8; 16])).is_err());
+    }
+}
@@ -1,36 +1,50 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context;
 use axum_server::tls_rustls::RustlsConfig;
+use reqwest::{
+    Certificate,
+    header::{HeaderMap, HeaderName, HeaderValue},
+};
+use synd_auth::{jwt::oidc::JwtService as OidcJwtService, oidc::ProviderMetadata};
 use synd_feed::feed::{
     cache::{CacheConfig, CacheLayer},
-    service::FeedService,
+    service::{AddressPolicy, FeedService, FetchConfig},
 };
 use tokio_util::sync::CancellationToken;
 
 use crate::{
-    cli::{self, CacheOptions, KvsdOptions, TlsOptions},
+    cli::{
+        self, AuthorizationOptions, CacheOptions, FetchOptions, KvsdOptions, OidcOptions,
+        TlsOptions,
+    },
     config,
     monitor::Monitors,
+    principal::Role,
     repository::kvsd::KvsdClient,
-    serve::{ServeOptions, auth::Authenticator},
+    serve::{ServeOptions, auth::Authenticator, tls::TlsReloader},
     usecase::{MakeUsecase, Runtime, authorize::Authorizer},
 };
 
 pub struct Dependency {
     pub authenticator: Authenticator,
     pub runtime: Runtime,
-    pub tls_config: RustlsConfig,
+    /// None when serving plain http
+    pub tls_config: Option<RustlsConfig>,
     pub serve_options: ServeOptions,
     pub monitors: Monitors,
 }
 
 impl Dependency {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         kvsd: KvsdOptions,
         tls: TlsOptions,
         serve_options: cli::ServeOptions,
         cache: CacheOptions,
+        fetch: FetchOptions,
+        oidc: OidcOptions,
+        authorization: AuthorizationOptions,
         ct: CancellationToken,
     ) -> anyhow::Result<Self> {
         let kvsd = {
@@ -47,16 +61,24 @@ impl Dependency {
                 kvsd_password,
                 Duration::from_secs(10),
             )
-            .await?
+            .await
+            .map(Arc::new)?
         };
 
+        let credential_cipher = fetch.fetch_credential_key.clone();
+
         let cache_feed_service = {
             let CacheOptions {
                 feed_cache_size_mb,
                 feed_cache_ttl,
                 feed_cache_refresh_interval,
             } = cache;
-            let feed_service = FeedService::new(config::USER_AGENT, 10 * 1024 * 1024);
+            let feed_service = FeedService::from_config(
+                config::USER_AGENT,
+                10 * 1024 * 1024,
+                fetch_config(fetch).await?,
+            )
+            .context("build feed service")?;
             let cache_feed_service = CacheLayer::with(
                 feed_service,
                 CacheConfig::default()
@@ -67,25 +89,74 @@ impl Dependency {
                 .periodic_refresher()
                 .with_emit_metrics(true);
 
-            tokio::spawn(periodic_refresher.run(feed_cache_refresh_interval, ct));
+            tokio::spawn(periodic_refresher.run(feed_cache_refresh_interval, ct.clone()));
 
-            cache_feed_service
+            Arc::new(cache_feed_service)
         };
 
         let make_usecase = MakeUsecase {
-            subscription_repo: Arc::new(kvsd),
-            fetch_feed: Arc::new(cache_feed_service),
+            subscription_repo: kvsd.clone(),
+            user_repo: kvsd.clone(),
+            fetch_feed: cache_feed_service.clone(),
+            feed_cache: cache_feed_service,
+            credential_cipher,
         };
 
-        let authenticator = Authenticator::new()?;
+        let authenticator = {
+            let authenticator = Authenticator::new(kvsd.clone(), kvsd)?.with_roles(
+                authorization
+                    .read_only_accounts
+                    .into_iter()
+                    .map(|account| (account, Role::ReadOnly))
+                    .chain(
+                        authorization
+                            .admin_accounts
+                            .into_iter()
+                            .map(|account| (account, Role::Admin)),
+                    ),
+            );
+            match oidc {
+                OidcOptions {
+                    oidc_issuer: Some(issuer),
+                    oidc_client_id: Some(client_id),
+                } => {
+                    let metadata = ProviderMetadata::discover(&issuer)
+                        .await
+                        .with_context(|| format!("oidc discovery: {issuer}"))?;
+                    authenticator.with_oidc_jwt(OidcJwtService::from_metadata(client_id, &metadata))
+                }
+                _ => authenticator,
+            }
+        };
 
         let authorizer = Authorizer::new();
 
         let runtime = Runtime::new(make_usecase, authorizer);
 
-        let tls_config = RustlsConfig::from_pem_file(&tls.certificate, &tls.private_key)
-            .await
-            .with_context(|| format!("tls options: {tls:?}"))?;
+        let tls_config = match tls {
+            TlsOptions { no_tls: true, .. } => None,
+            TlsOptions {
+                certificate: Some(certificate),
+                private_key: Some(private_key),
+                reload_interval,
+                ..
+            } => {
+                let tls_config = RustlsConfig::from_pem_file(&certificate, &private_key)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "tls options: certificate: {} private_key: {}",
+                            certificate.display(),
+                            private_key.display()
+                        )
+                    })?;
+                let reloader = TlsReloader::new(tls_config.clone(), certificate, private_key);
+                tokio::spawn(reloader.run(reload_interval, ct));
+
+                Some(tls_config)
+            }
+            _ => anyhow::bail!("tls certificate and private key are required unless no-tls"),
+        };
 
         let monitors = Monitors::new();
 
@@ -98,3 +169,93 @@ impl Dependency {
         })
     }
 }
+
+async fn fetch_config(
+    FetchOptions {
+        fetch_timeout,
+        fetch_connect_timeout,
+        fetch_proxy,
+        fetch_root_ca,
+        fetch_host_headers,
+        fetch_allowed_hosts,
+        ..
+    }: FetchOptions,
+) -> anyhow::Result<FetchConfig> {
+    // Feed urls are given by users, so private addresses are not reachable by default
+    let address_policy = fetch_allowed_hosts
+        .iter()
+        .fold(AddressPolicy::new(), |policy, allowed| {
+            policy.with_allowed(allowed)
+        });
+    let mut config = FetchConfig::default()
+        .with_timeout(fetch_timeout)
+        .with_connect_timeout(fetch_connect_timeout)
+        .with_address_policy(address_policy);
+
+    if let Some(proxy) = fetch_proxy {
+        config = config.with_proxy(proxy);
+    }
+
+    for path in fetch_root_ca {
+        let pem = tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("read root ca: {}", path.display()))?;
+        for certificate in Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("parse root ca: {}", path.display()))?
+        {
+            config = config.with_root_certificate(certificate);
+        }
+    }
+
+    if let Some(path) = fetch_host_headers {
+        let json = tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("read host headers: {}", path.display()))?;
+        for (host, headers) in parse_host_headers(&json)
+            .with_context(|| format!("parse host headers: {}", path.display()))?
+        {
+            config = config.with_host_headers(host, headers);
+        }
+    }
+
+    Ok(config)
+}
+
+fn parse_host_headers(json: &[u8]) -> anyhow::Result<HashMap<String, HeaderMap>> {
+    serde_json::from_slice::<HashMap<String, HashMap<String, String>>>(json)?
+        .into_iter()
+        .map(|(host, headers)| {
+            let headers = headers
+                .into_iter()
+                .map(|(name, value)| {
+                    let name = HeaderName::try_from(name.as_str())
+                        .with_context(|| format!("invalid header name: {name}"))?;
+                    // Do not include the value which might be a secret in the error
+                    let value = HeaderValue::try_from(value)
+                        .with_context(|| format!("invalid header value of {name}"))?;
+                    Ok((name, value))
+                })
+                .collect::<anyhow::Result<HeaderMap>>()?;
+            Ok((host, headers))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_headers() {
+        let headers = parse_host_headers(
+            br#"{"feeds.example.com": {"Authorization": "Bearer xxx", "cookie": "a=b"}}"#,
+        )
+        .unwrap();
+        let headers = &headers["feeds.example.com"];
+        assert_eq!(headers["authorization"], "Bearer xxx");
+        assert_eq!(headers["cookie"], "a=b");
+
+        assert!(parse_host_headers(br#"{"feeds.example.com": {"in valid": "x"}}"#).is_err());
+        assert!(parse_host_headers(br#"["feeds.example.com"]"#).is_err());
+    }
+}
@@ -2,10 +2,13 @@ mod query;
 pub(crate) use query::Query;
 
 mod mutation;
-use async_graphql::{EmptySubscription, Schema, SchemaBuilder};
+use async_graphql::{
+    EmptySubscription, Schema, SchemaBuilder,
+    extensions::apollo_persisted_queries::{ApolloPersistedQueries, LruCacheStorage},
+};
 pub(crate) use mutation::Mutation;
 
-use crate::{gql::mutation::ResponseCode, principal::Principal, usecase};
+use crate::{gql::mutation::ResponseCode, principal::Principal, serve::GraphQLOptions, usecase};
 
 pub(crate) mod object;
 pub(crate) mod scalar;
@@ -30,6 +33,7 @@ pub(crate) mod handler {
         Extension(Context {
             schema,
             gql_monitor,
+            ..
         }): Extension<Context>,
         Extension(principal): Extension<Principal>,
         req: GraphQLRequest,
@@ -43,16 +47,29 @@ pub(crate) mod handler {
 }
 
 #[must_use]
-pub(crate) fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    let schema = Schema::build(Query, Mutation, EmptySubscription);
+pub(crate) fn schema_builder(
+    options: &GraphQLOptions,
+) -> SchemaBuilder<Query, Mutation, EmptySubscription> {
+    let GraphQLOptions {
+        depth_limit,
+        complexity_limit,
+        persisted_query_cache_size,
+    } = *options;
+
+    let schema = Schema::build(Query, Mutation, EmptySubscription).extension(
+        ApolloPersistedQueries::new(LruCacheStorage::new(persisted_query_cache_size.max(1))),
+    );
 
     if cfg!(not(feature = "introspection")) {
         schema
             .disable_introspection()
-            .limit_depth(10)
-            .limit_complexity(60)
+            .limit_depth(depth_limit)
+            .limit_complexity(complexity_limit)
     } else {
-        schema.limit_depth(20).limit_complexity(300)
+        // Introspection query requires deeper nesting
+        schema
+            .limit_depth(depth_limit.max(20))
+            .limit_complexity(complexity_limit.max(300))
     }
     // disabled
     // schema.extension(Tracing)
@@ -93,7 +110,7 @@ impl async_graphql::ErrorExtensions for usecase::FetchSubscribedFeedsError {
 
 macro_rules! run_usecase {
     ($usecase:ty, $cx:expr, $input:expr,$err_handle:expr) => {{
-        let runtime = $cx.data_unchecked::<crate::usecase::Runtime>();
+        let runtime = $cx.data_unchecked::<std::sync::Arc<crate::usecase::Runtime>>();
         let err_handle = $err_handle;
 
         match runtime.run::<$usecase, _, _>($cx, $input).await {
@@ -108,12 +125,70 @@ pub(super) use run_usecase;
 
 #[cfg(test)]
 mod tests {
-    use async_graphql::ErrorExtensions;
+    use std::sync::{Arc, Mutex};
+
+    use async_graphql::{
+        ErrorExtensions, ServerError, ValidationResult,
+        extensions::{Extension, ExtensionContext, ExtensionFactory, NextValidation},
+    };
 
     use crate::usecase::authorize::Unauthorized;
 
     use super::*;
 
+    /// Record the complexity of the query and stop before execution
+    #[derive(Clone, Default)]
+    struct Complexity(Arc<Mutex<Option<usize>>>);
+
+    impl ExtensionFactory for Complexity {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(self.clone())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for Complexity {
+        async fn validation(
+            &self,
+            cx: &ExtensionContext<'_>,
+            next: NextValidation<'_>,
+        ) -> Result<ValidationResult, Vec<ServerError>> {
+            let result = next.run(cx).await?;
+            *self.0.lock().unwrap() = Some(result.complexity);
+            Err(vec![ServerError::new("stop", None)])
+        }
+    }
+
+    async fn complexity(query: &str) -> Option<usize> {
+        let complexity = Complexity::default();
+        let options = GraphQLOptions {
+            depth_limit: crate::config::serve::DEFAULT_GRAPHQL_DEPTH_LIMIT,
+            complexity_limit: crate::config::serve::DEFAULT_GRAPHQL_COMPLEXITY_LIMIT,
+            persisted_query_cache_size: 1,
+        };
+        schema_builder(&options)
+            .extension(complexity.clone())
+            .finish()
+            .execute(query)
+            .await;
+        complexity.0.lock().unwrap().take()
+    }
+
+    #[tokio::test]
+    async fn query_complexity() {
+        // Equivalent to the queries synd_term sends
+        let feeds = "query { subscription { feeds(first: 50) { nodes { id type title url updated websiteUrl description generator requirement category entries(first: 10) { nodes { title published updated summaryText } } links { nodes { href rel mediaType title } } authors { nodes } } pageInfo { hasNextPage endCursor } errors { url errorMessage } } } }";
+        let entries = "query { subscription { entries(first: 200) { nodes { id title published updated summaryText websiteUrl enclosures { url mimeType durationSeconds } feed { title url requirement category } } pageInfo { hasNextPage endCursor } } } }";
+        let entry = r#"query { subscription { entry(feedUrl: "https://example.com/feed.xml", id: "1") { contentHtml } } }"#;
+        // Fan out by nesting connections
+        let fan_out = "query { subscription { feeds(first: 100) { nodes { entries(first: 100) { nodes { title summaryText } } } } } }";
+
+        assert!(complexity(feeds).await.is_some());
+        assert!(complexity(entries).await.is_some());
+        assert!(complexity(entry).await.is_some());
+        assert_eq!(complexity(fan_out).await, None);
+    }
+
     #[test]
     fn usecase_error_impl_gql_error() {
         insta::assert_yaml_snapshot!(
@@ -0,0 +1,193 @@
+use std::convert::Infallible;
+
+use async_graphql::{Context, InputObject, Object, Union};
+use synd_feed::types::{Annotated, FeedUrl};
+
+use crate::{
+    gql::{mutation::ResponseStatus, object, run_usecase},
+    usecase::{self, EvictCachedFeed, RefreshCachedFeed, RefreshCachedFeedError},
+};
+
+pub(crate) struct AdminMutation;
+
+#[Object]
+impl AdminMutation {
+    /// Fetch the feed bypassing the cache and replace the cached one
+    async fn refresh_feed(
+        &self,
+        cx: &Context<'_>,
+        input: RefreshFeedInput,
+    ) -> async_graphql::Result<RefreshFeedResponse> {
+        run_usecase!(
+            RefreshCachedFeed,
+            cx,
+            input,
+            |err: RefreshCachedFeedError| { Ok(err.into()) }
+        )
+    }
+
+    /// Evict the feed from the cache
+    /// If given feed is not cached, this mutation will succeed
+    async fn evict_feed(
+        &self,
+        cx: &Context<'_>,
+        input: EvictFeedInput,
+    ) -> async_graphql::Result<EvictFeedResponse> {
+        run_usecase!(EvictCachedFeed, cx, input, |err: Infallible| match err {})
+    }
+}
+
+#[derive(InputObject)]
+pub(crate) struct RefreshFeedInput {
+    /// Feed url to refresh
+    pub url: FeedUrl,
+}
+
+impl From<RefreshFeedInput> for usecase::RefreshCachedFeedInput {
+    fn from(value: RefreshFeedInput) -> Self {
+        usecase::RefreshCachedFeedInput { url: value.url }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Union)]
+pub(crate) enum RefreshFeedResponse {
+    Success(RefreshFeedSuccess),
+    Error(RefreshFeedError),
+}
+
+pub(crate) struct RefreshFeedSuccess {
+    pub status: ResponseStatus,
+    /// Refreshed feed
+    pub feed: object::Feed,
+}
+
+#[Object]
+impl RefreshFeedSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    pub async fn feed(&self) -> &object::Feed {
+        &self.feed
+    }
+}
+
+pub(crate) struct RefreshFeedError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl RefreshFeedError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<usecase::Output<usecase::RefreshCachedFeedOutput>> for RefreshFeedResponse {
+    fn from(output: usecase::Output<usecase::RefreshCachedFeedOutput>) -> Self {
+        RefreshFeedResponse::Success(RefreshFeedSuccess {
+            status: ResponseStatus::ok(),
+            feed: object::Feed::from(Annotated {
+                feed: output.output.feed,
+                requirement: None,
+                category: None,
+            }),
+        })
+    }
+}
+
+impl From<RefreshCachedFeedError> for RefreshFeedResponse {
+    fn from(err: RefreshCachedFeedError) -> Self {
+        match err {
+            RefreshCachedFeedError::FetchFeed(fetch_err) => {
+                let (status, message) = ResponseStatus::from_fetch_feed_error(fetch_err);
+                RefreshFeedResponse::Error(RefreshFeedError { status, message })
+            }
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub(crate) struct EvictFeedInput {
+    /// Feed url to evict
+    pub url: FeedUrl,
+}
+
+impl From<EvictFeedInput> for usecase::EvictCachedFeedInput {
+    fn from(value: EvictFeedInput) -> Self {
+        usecase::EvictCachedFeedInput { url: value.url }
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum EvictFeedResponse {
+    Success(EvictFeedSuccess),
+    Error(EvictFeedError),
+}
+
+pub(crate) struct EvictFeedSuccess {
+    pub status: ResponseStatus,
+    pub evicted: bool,
+}
+
+#[Object]
+impl EvictFeedSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Whether the feed was cached
+    pub async fn evicted(&self) -> bool {
+        self.evicted
+    }
+}
+
+pub(crate) struct EvictFeedError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl EvictFeedError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<usecase::Output<usecase::EvictCachedFeedOutput>> for EvictFeedResponse {
+    fn from(output: usecase::Output<usecase::EvictCachedFeedOutput>) -> Self {
+        EvictFeedResponse::Success(EvictFeedSuccess {
+            status: ResponseStatus::ok(),
+            evicted: output.output.evicted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use synd_feed::feed::service::FetchFeedError;
+
+    use super::*;
+
+    #[test]
+    fn refresh_internal_error() {
+        let r = RefreshFeedResponse::from(RefreshCachedFeedError::FetchFeed(
+            FetchFeedError::Other(anyhow::anyhow!("error")),
+        ));
+        assert!(
+            matches!(r, RefreshFeedResponse::Error(RefreshFeedError { status, ..}) if status == ResponseStatus::internal())
+        );
+    }
+}
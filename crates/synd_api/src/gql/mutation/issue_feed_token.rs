@@ -0,0 +1,34 @@
+use async_graphql::Object;
+
+use crate::{gql::mutation::ResponseStatus, rest, usecase};
+
+pub(crate) struct IssueFeedTokenSuccess {
+    pub status: ResponseStatus,
+    pub token: String,
+}
+
+#[Object]
+impl IssueFeedTokenSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Secret token to read the aggregated feed
+    pub async fn token(&self) -> &str {
+        self.token.as_str()
+    }
+
+    /// Path of the aggregated feed
+    pub async fn path(&self) -> String {
+        rest::feed_path(&self.token)
+    }
+}
+
+impl From<usecase::Output<usecase::IssueFeedTokenOutput>> for IssueFeedTokenSuccess {
+    fn from(output: usecase::Output<usecase::IssueFeedTokenOutput>) -> Self {
+        IssueFeedTokenSuccess {
+            status: ResponseStatus::ok(),
+            token: output.output.token,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use async_graphql::{InputObject, Object, Union};
+
+use crate::{
+    gql::{mutation::ResponseStatus, object::LinkedIdentity},
+    usecase::{self, LinkAccountError as UsecaseLinkAccountError},
+};
+
+#[derive(InputObject)]
+pub(crate) struct LinkAccountInput {
+    /// Credential of the provider to link in the same format as the authorization header.
+    /// ex. `google {id_token}`
+    pub credential: String,
+}
+
+#[derive(Union)]
+pub(crate) enum LinkAccountResponse {
+    Success(LinkAccountSuccess),
+    Error(LinkAccountError),
+}
+
+pub(crate) struct LinkAccountSuccess {
+    pub status: ResponseStatus,
+    pub identities: Vec<LinkedIdentity>,
+}
+
+#[Object]
+impl LinkAccountSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Identities linked to the user
+    pub async fn identities(&self) -> &[LinkedIdentity] {
+        self.identities.as_slice()
+    }
+}
+
+pub(crate) struct LinkAccountError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl LinkAccountError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl LinkAccountResponse {
+    pub(super) fn invalid_credential() -> Self {
+        LinkAccountResponse::Error(LinkAccountError {
+            status: ResponseStatus::invalid_credential(),
+            message: "invalid credential".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::LinkAccountOutput>> for LinkAccountResponse {
+    fn from(output: usecase::Output<usecase::LinkAccountOutput>) -> Self {
+        LinkAccountResponse::Success(LinkAccountSuccess {
+            status: ResponseStatus::ok(),
+            identities: output
+                .output
+                .user
+                .identities
+                .into_iter()
+                .map(LinkedIdentity::from)
+                .collect(),
+        })
+    }
+}
+
+impl From<UsecaseLinkAccountError> for LinkAccountResponse {
+    fn from(err: UsecaseLinkAccountError) -> Self {
+        let status = match err {
+            UsecaseLinkAccountError::AlreadyLinked { .. } => ResponseStatus::already_linked(),
+        };
+        LinkAccountResponse::Error(LinkAccountError {
+            status,
+            message: format!("{err}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn already_linked() {
+        let r = LinkAccountResponse::from(UsecaseLinkAccountError::AlreadyLinked {
+            provider: "google".into(),
+        });
+        assert!(
+            matches!(r, LinkAccountResponse::Error(LinkAccountError { status, ..}) if status == ResponseStatus::already_linked())
+        );
+    }
+}
@@ -1,12 +1,23 @@
+use std::convert::Infallible;
+
 use async_graphql::{Context, Enum, Interface, Object, SimpleObject};
+use synd_feed::feed::service::FetchFeedError;
 
 use crate::{
     gql::run_usecase,
-    usecase::{SubscribeFeed, SubscribeFeedError, UnsubscribeFeed},
+    serve::auth::Authenticator,
+    usecase::{
+        self, IssueFeedToken, IssueFeedTokenInput, LinkAccount, LinkAccountError, SubscribeFeed,
+        SubscribeFeedError, UnsubscribeFeed, UpdateFeeds, UpdateFeedsError,
+    },
 };
 
+pub mod admin;
+pub mod issue_feed_token;
+pub mod link_account;
 pub mod subscribe_feed;
 pub mod unsubscribe_feed;
+pub mod update_feeds;
 
 #[derive(Enum, PartialEq, Eq, Clone, Copy, Debug)]
 pub(crate) enum ResponseCode {
@@ -18,6 +29,10 @@ pub(crate) enum ResponseCode {
     InvalidFeedUrl,
     /// The feed server returned a status other than 200
     FeedUnavailable,
+    /// Given credential could not be verified or accepted
+    InvalidCredential,
+    /// Given identity is already linked to another user
+    AlreadyLinked,
     /// Something went wrong
     InternalError,
 }
@@ -46,11 +61,36 @@ impl ResponseStatus {
         }
     }
 
+    fn invalid_credential() -> Self {
+        Self {
+            code: ResponseCode::InvalidCredential,
+        }
+    }
+
+    fn already_linked() -> Self {
+        Self {
+            code: ResponseCode::AlreadyLinked,
+        }
+    }
+
     fn internal() -> Self {
         Self {
             code: ResponseCode::InternalError,
         }
     }
+
+    /// Map the error on fetching the feed to the status and the message
+    fn from_fetch_feed_error(err: FetchFeedError) -> (Self, String) {
+        match err {
+            FetchFeedError::InvalidFeed(kind) => (Self::invalid_feed_url(), format!("{kind}")),
+            FetchFeedError::Blocked(blocked) => (Self::invalid_feed_url(), format!("{blocked}")),
+            FetchFeedError::Fetch(request_err) => (
+                Self::feed_unavailable(),
+                format!("feed unavailable: {request_err}"),
+            ),
+            fetch_err => (Self::internal(), format!("{fetch_err}")),
+        }
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -59,6 +99,11 @@ impl ResponseStatus {
 enum MutationResponse {
     SubscribeFeed(subscribe_feed::SubscribeFeedSuccess),
     UnsubscribeFeed(unsubscribe_feed::UnsubscribeFeedSuccess),
+    UpdateFeeds(update_feeds::UpdateFeedsSuccess),
+    LinkAccount(link_account::LinkAccountSuccess),
+    IssueFeedToken(issue_feed_token::IssueFeedTokenSuccess),
+    RefreshFeed(admin::RefreshFeedSuccess),
+    EvictFeed(admin::EvictFeedSuccess),
 }
 
 #[derive(Interface)]
@@ -69,6 +114,10 @@ enum MutationResponse {
 enum ErrorResponse {
     SubscribeFeed(subscribe_feed::SubscribeFeedError),
     UnsubscribeFeed(unsubscribe_feed::UnsubscribeFeedError),
+    UpdateFeeds(update_feeds::UpdateFeedsError),
+    LinkAccount(link_account::LinkAccountError),
+    RefreshFeed(admin::RefreshFeedError),
+    EvictFeed(admin::EvictFeedError),
 }
 
 pub(crate) struct Mutation;
@@ -97,4 +146,53 @@ impl Mutation {
             err.into()
         ))
     }
+
+    /// Update the requirement and category of the subscribed feeds
+    /// Unlike `subscribeFeed`, the feeds are not fetched
+    async fn update_feeds(
+        &self,
+        cx: &Context<'_>,
+        input: update_feeds::UpdateFeedsInput,
+    ) -> async_graphql::Result<update_feeds::UpdateFeedsResponse> {
+        run_usecase!(UpdateFeeds, cx, input, |err: UpdateFeedsError| Ok(
+            err.into()
+        ))
+    }
+
+    /// Link the identity of another provider to the current user
+    /// After linking, signing in with either provider resolves to the same user
+    async fn link_account(
+        &self,
+        cx: &Context<'_>,
+        input: link_account::LinkAccountInput,
+    ) -> async_graphql::Result<link_account::LinkAccountResponse> {
+        let authenticator = cx.data_unchecked::<Authenticator>();
+        let Ok(identity) = authenticator.verify_identity(&input.credential).await else {
+            return Ok(link_account::LinkAccountResponse::invalid_credential());
+        };
+        let input = usecase::LinkAccountInput { identity };
+
+        run_usecase!(LinkAccount, cx, input, |err: LinkAccountError| Ok(
+            err.into()
+        ))
+    }
+
+    /// Issue the secret token to read the aggregated feed
+    /// The previously issued token is revoked
+    async fn issue_feed_token(
+        &self,
+        cx: &Context<'_>,
+    ) -> async_graphql::Result<issue_feed_token::IssueFeedTokenSuccess> {
+        run_usecase!(
+            IssueFeedToken,
+            cx,
+            IssueFeedTokenInput {},
+            |err: Infallible| { match err {} }
+        )
+    }
+
+    /// Operations which require the admin role
+    async fn admin(&self) -> admin::AdminMutation {
+        admin::AdminMutation {}
+    }
 }
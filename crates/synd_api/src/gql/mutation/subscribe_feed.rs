@@ -1,8 +1,7 @@
-use async_graphql::{InputObject, Object, Union};
-use synd_feed::{
-    feed::service::FetchFeedError,
-    types::{Category, FeedUrl, Requirement},
-};
+use std::fmt;
+
+use async_graphql::{InputObject, Object, OneofObject, Union};
+use synd_feed::types::{Category, FeedCredential, FeedUrl, Requirement};
 
 use crate::{
     gql::{
@@ -20,6 +19,47 @@ pub(crate) struct SubscribeFeedInput {
     pub requirement: Option<Requirement>,
     /// Feed category
     pub category: Option<Category<'static>>,
+    /// Credential to fetch the private feed
+    pub credential: Option<FeedCredentialInput>,
+}
+
+/// Credential to fetch the feed which requires authentication
+#[derive(OneofObject)]
+pub(crate) enum FeedCredentialInput {
+    /// Http basic authentication
+    Basic(BasicCredentialInput),
+    /// Bearer token
+    Bearer(String),
+}
+
+#[derive(InputObject)]
+pub(crate) struct BasicCredentialInput {
+    pub username: String,
+    pub password: Option<String>,
+}
+
+// Avoid leaking the secret in logs
+impl fmt::Debug for FeedCredentialInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeedCredentialInput::Basic(basic) => f
+                .debug_struct("Basic")
+                .field("username", &basic.username)
+                .finish_non_exhaustive(),
+            FeedCredentialInput::Bearer(_) => f.debug_struct("Bearer").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl From<FeedCredentialInput> for FeedCredential {
+    fn from(value: FeedCredentialInput) -> Self {
+        match value {
+            FeedCredentialInput::Basic(BasicCredentialInput { username, password }) => {
+                FeedCredential::Basic { username, password }
+            }
+            FeedCredentialInput::Bearer(token) => FeedCredential::Bearer { token },
+        }
+    }
 }
 
 impl From<SubscribeFeedInput> for usecase::SubscribeFeedInput {
@@ -28,6 +68,7 @@ impl From<SubscribeFeedInput> for usecase::SubscribeFeedInput {
             url: value.url,
             requirement: value.requirement,
             category: value.category,
+            credential: value.credential.map(FeedCredential::from),
         }
     }
 }
@@ -91,19 +132,14 @@ impl From<UsecaseSubscribeFeedError> for SubscribeFeedResponse {
 impl From<UsecaseSubscribeFeedError> for SubscribeFeedError {
     fn from(err: UsecaseSubscribeFeedError) -> Self {
         match err {
-            UsecaseSubscribeFeedError::FetchFeed(fetch_err) => match fetch_err {
-                FetchFeedError::InvalidFeed(kind) => Self {
-                    status: ResponseStatus::invalid_feed_url(),
-                    message: format!("{kind}"),
-                },
-                FetchFeedError::Fetch(request_err) => Self {
-                    status: ResponseStatus::feed_unavailable(),
-                    message: format!("feed unavailable: {request_err}"),
-                },
-                fetch_err => Self {
-                    status: ResponseStatus::internal(),
-                    message: format!("{fetch_err}"),
-                },
+            UsecaseSubscribeFeedError::FetchFeed(fetch_err) => {
+                let (status, message) = ResponseStatus::from_fetch_feed_error(fetch_err);
+                Self { status, message }
+            }
+            err @ (UsecaseSubscribeFeedError::CredentialNotEnabled
+            | UsecaseSubscribeFeedError::Credential(_)) => Self {
+                status: ResponseStatus::invalid_credential(),
+                message: format!("{err}"),
             },
         }
     }
@@ -111,6 +147,8 @@ impl From<UsecaseSubscribeFeedError> for SubscribeFeedError {
 
 #[cfg(test)]
 mod tests {
+    use synd_feed::feed::service::FetchFeedError;
+
     use super::*;
 
     #[test]
@@ -121,4 +159,11 @@ mod tests {
 
         assert_eq!(r.status, ResponseStatus::internal());
     }
+
+    #[test]
+    fn credential_error() {
+        let r = SubscribeFeedError::from(UsecaseSubscribeFeedError::CredentialNotEnabled);
+
+        assert_eq!(r.status, ResponseStatus::invalid_credential());
+    }
 }
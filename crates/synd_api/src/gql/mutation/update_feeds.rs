@@ -0,0 +1,106 @@
+use async_graphql::{InputObject, Object, Union};
+use synd_feed::types::{Category, FeedUrl, Requirement};
+
+use crate::{gql::mutation::ResponseStatus, usecase};
+
+#[derive(InputObject, Debug)]
+pub(crate) struct UpdateFeedsInput {
+    /// Feeds to update
+    pub feeds: Vec<UpdateFeedInput>,
+}
+
+#[derive(InputObject, Debug)]
+pub(crate) struct UpdateFeedInput {
+    /// Subscribed feed url to update
+    pub url: FeedUrl,
+    /// Requirement level for feed
+    pub requirement: Option<Requirement>,
+    /// Feed category
+    pub category: Option<Category<'static>>,
+}
+
+impl From<UpdateFeedsInput> for usecase::UpdateFeedsInput {
+    fn from(value: UpdateFeedsInput) -> Self {
+        usecase::UpdateFeedsInput {
+            feeds: value
+                .feeds
+                .into_iter()
+                .map(|feed| usecase::UpdateFeedInput {
+                    url: feed.url,
+                    requirement: feed.requirement,
+                    category: feed.category,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum UpdateFeedsResponse {
+    Success(UpdateFeedsSuccess),
+    Error(UpdateFeedsError),
+}
+
+pub(crate) struct UpdateFeedsSuccess {
+    pub status: ResponseStatus,
+}
+
+#[Object]
+impl UpdateFeedsSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+}
+
+pub(crate) struct UpdateFeedsError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl UpdateFeedsError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<usecase::Output<usecase::UpdateFeedsOutput>> for UpdateFeedsResponse {
+    fn from(_output: usecase::Output<usecase::UpdateFeedsOutput>) -> Self {
+        UpdateFeedsResponse::Success(UpdateFeedsSuccess {
+            status: ResponseStatus::ok(),
+        })
+    }
+}
+
+impl From<usecase::UpdateFeedsError> for UpdateFeedsResponse {
+    fn from(err: usecase::UpdateFeedsError) -> Self {
+        match err {
+            err @ usecase::UpdateFeedsError::NotSubscribed(_) => {
+                UpdateFeedsResponse::Error(UpdateFeedsError {
+                    status: ResponseStatus::invalid_feed_url(),
+                    message: format!("{err}"),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_subscribed() {
+        let r = UpdateFeedsResponse::from(usecase::UpdateFeedsError::NotSubscribed(
+            FeedUrl::parse("https://example.com/feed.xml").unwrap(),
+        ));
+        assert!(
+            matches!(r, UpdateFeedsResponse::Error(UpdateFeedsError { status, ..}) if status == ResponseStatus::invalid_feed_url())
+        );
+    }
+}
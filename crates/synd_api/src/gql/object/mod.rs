@@ -7,7 +7,10 @@ use async_graphql::{
 use feed_rs::model as feedrs;
 use synd_feed::types::{self, Annotated, Category, FeedType, FeedUrl, Requirement};
 
-use crate::gql::scalar;
+use crate::{
+    gql::scalar,
+    principal::{self, IdentityProvider},
+};
 
 use self::id::FeedIdV1;
 
@@ -34,17 +37,83 @@ impl From<feedrs::Link> for Link {
     }
 }
 
+/// Media file attached to the entry
+#[derive(SimpleObject)]
+pub(crate) struct Enclosure {
+    /// Url of the media
+    pub url: String,
+    /// Mime type of the media
+    pub mime_type: Option<String>,
+    /// Size of the media in bytes
+    pub length: Option<u64>,
+    /// Duration of the media in seconds
+    pub duration_seconds: Option<u64>,
+}
+
+impl From<types::Enclosure> for Enclosure {
+    fn from(value: types::Enclosure) -> Self {
+        Self {
+            url: value.url,
+            mime_type: value.mime_type,
+            length: value.length,
+            duration_seconds: value.duration.map(|duration| duration.as_secs()),
+        }
+    }
+}
+
+/// Podcast metadata of the entry
+#[derive(SimpleObject)]
+pub(crate) struct PodcastEpisode {
+    /// Episode title
+    pub title: Option<String>,
+    /// Episode description
+    pub description: Option<String>,
+    /// Episode authors
+    pub authors: Vec<String>,
+    /// Duration of the episode in seconds
+    pub duration_seconds: Option<u64>,
+}
+
+impl From<types::Episode> for PodcastEpisode {
+    fn from(value: types::Episode) -> Self {
+        Self {
+            title: value.title,
+            description: value.description,
+            authors: value.authors,
+            duration_seconds: value.duration.map(|duration| duration.as_secs()),
+        }
+    }
+}
+
+#[allow(clippy::struct_field_names)]
 pub(crate) struct Entry<'a> {
     meta: Cow<'a, Annotated<types::FeedMeta>>,
     entry: types::Entry,
+    // feeds of the duplicates collapsed into this entry
+    duplicates: Vec<Annotated<types::FeedMeta>>,
 }
 
 #[Object]
 impl Entry<'_> {
+    /// Entry id which is unique within the feed
+    async fn id(&self) -> ID {
+        ID(self.entry.id().to_string())
+    }
+
     /// Feed of this entry
     async fn feed(&self) -> FeedMeta {
         self.meta.clone().into()
     }
+
+    /// Feeds which published this entry.
+    /// The first one is the feed of this entry, followed by the feeds of the collapsed duplicates
+    async fn sources(&self) -> Vec<FeedMeta> {
+        std::iter::once(Cow::Borrowed(self.meta.as_ref()))
+            .chain(self.duplicates.iter().map(Cow::Borrowed))
+            .map(FeedMeta::from)
+            .collect()
+    }
+
     /// Entry title
     async fn title(&self) -> Option<&str> {
         self.entry.title()
@@ -65,15 +134,74 @@ impl Entry<'_> {
         self.entry.summary().or(self.entry.content())
     }
 
+    /// Entry summary sanitized to be safely embedded in html. If there is no summary, return the content
+    async fn summary_html(&self) -> Option<String> {
+        self.entry
+            .summary_html()
+            .or_else(|| self.entry.content_html())
+    }
+
+    /// Entry summary rendered as plain text with links as footnotes. If there is no summary, return the content
+    /// Lines are wrapped at given width, otherwise only explicit line breaks are kept
+    async fn summary_text(&self, width: Option<i32>) -> Option<String> {
+        let width = text_width(width);
+        self.entry
+            .summary_text(width)
+            .or_else(|| self.entry.content_text(width))
+    }
+
+    /// Entry content sanitized to be safely embedded in html
+    async fn content_html(&self) -> Option<String> {
+        self.entry.content_html()
+    }
+
+    /// Entry content rendered as plain text with links as footnotes
+    /// Lines are wrapped at given width, otherwise only explicit line breaks are kept
+    async fn content_text(&self, width: Option<i32>) -> Option<String> {
+        self.entry.content_text(text_width(width))
+    }
+
     /// Link to websiteurl at which this entry is published
     async fn website_url(&self) -> Option<&str> {
         self.entry.website_url(self.meta.feed.r#type())
     }
+
+    /// Media files attached to the entry
+    async fn enclosures(&self) -> Vec<Enclosure> {
+        self.entry
+            .enclosures()
+            .into_iter()
+            .map(Enclosure::from)
+            .collect()
+    }
+
+    /// Urls of the entry thumbnail images
+    async fn thumbnails(&self) -> Vec<&str> {
+        self.entry.thumbnails().collect()
+    }
+
+    /// Podcast metadata if the entry is an episode
+    async fn episode(&self) -> Option<PodcastEpisode> {
+        self.entry.episode().map(PodcastEpisode::from)
+    }
+}
+
+fn text_width(width: Option<i32>) -> Option<usize> {
+    width.and_then(|width| usize::try_from(width).ok())
 }
 
 impl<'a> Entry<'a> {
     pub fn new(meta: Cow<'a, Annotated<types::FeedMeta>>, entry: types::Entry) -> Self {
-        Self { meta, entry }
+        Self {
+            meta,
+            entry,
+            duplicates: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_duplicates(self, duplicates: Vec<Annotated<types::FeedMeta>>) -> Self {
+        Self { duplicates, ..self }
     }
 }
 
@@ -108,6 +236,7 @@ impl Feed {
 
     /// Feed entries
     #[allow(clippy::cast_sign_loss)]
+    #[graphql(complexity = "first.unwrap_or(5).max(0) as usize * child_complexity")]
     async fn entries(
         &self,
         #[graphql(default = 5)] first: Option<i32>,
@@ -184,6 +313,16 @@ impl Feed {
         self.0.feed.meta().generator()
     }
 
+    /// Url of the feed image such as podcast artwork
+    async fn image_url(&self) -> Option<&str> {
+        self.0.feed.meta().image_url()
+    }
+
+    /// Whether the podcast is marked as explicit
+    async fn explicit(&self) -> bool {
+        self.0.feed.meta().is_explicit()
+    }
+
     /// Requirement level for feed
     async fn requirement(&self) -> Option<Requirement> {
         self.0.requirement
@@ -247,3 +386,24 @@ impl<'a> From<Cow<'a, Annotated<types::FeedMeta>>> for FeedMeta<'a> {
         Self(value)
     }
 }
+
+/// Identity linked to the user
+#[derive(SimpleObject)]
+pub(crate) struct LinkedIdentity {
+    /// Provider which verified the identity
+    pub provider: IdentityProvider,
+    /// Identifier of the account issued by the provider
+    pub subject: String,
+    /// Email of the identity
+    pub email: String,
+}
+
+impl From<principal::Identity> for LinkedIdentity {
+    fn from(identity: principal::Identity) -> Self {
+        Self {
+            provider: identity.provider,
+            subject: identity.subject,
+            email: identity.email,
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, convert::Infallible};
 
 use async_graphql::{
-    Context, Object, Result, SimpleObject,
+    Context, ID, Object, Result, SimpleObject,
     connection::{Connection, Edge},
 };
 use synd_feed::types::FeedUrl;
@@ -12,9 +12,10 @@ use crate::{
         run_usecase,
     },
     usecase::{
-        FetchEntries, FetchEntriesError, FetchEntriesInput, FetchEntriesOutput,
-        FetchSubscribedFeeds, FetchSubscribedFeedsError, FetchSubscribedFeedsInput,
-        FetchSubscribedFeedsOutput, Output,
+        CountUsers, CountUsersInput, CountUsersOutput, EntryFilter, FetchEntries,
+        FetchEntriesError, FetchEntriesInput, FetchEntriesOutput, FetchSubscribedFeeds,
+        FetchSubscribedFeedsError, FetchSubscribedFeedsInput, FetchSubscribedFeedsOutput,
+        InspectFeedCache, InspectFeedCacheInput, InspectFeedCacheOutput, Output,
     },
 };
 
@@ -34,6 +35,7 @@ struct Subscription;
 #[Object]
 impl Subscription {
     /// Return Subscribed feeds
+    #[graphql(complexity = "first.unwrap_or(10).clamp(0, 100) as usize * child_complexity")]
     async fn feeds(
         &self,
         cx: &Context<'_>,
@@ -87,11 +89,15 @@ impl Subscription {
     }
 
     /// Return subscribed latest entries order by published time.
+    /// If deduplicate is true, the entries of the same article published in several feeds
+    /// are collapsed into the latest one which lists all of the feeds as sources
+    #[graphql(complexity = "first.unwrap_or(20).clamp(0, 200) as usize * child_complexity")]
     async fn entries<'cx>(
         &self,
         cx: &Context<'_>,
         after: Option<String>,
         #[graphql(default = 20)] first: Option<i32>,
+        #[graphql(default = false)] deduplicate: bool,
     ) -> Result<Connection<id::EntryId, Entry<'cx>>> {
         #[allow(clippy::cast_sign_loss)]
         let first = first.unwrap_or(20).min(200) as usize;
@@ -99,9 +105,16 @@ impl Subscription {
         let input = FetchEntriesInput {
             after: after.map(Into::into),
             first: first + 1,
+            filter: EntryFilter::default(),
+            deduplicate,
         };
         let Output {
-            output: FetchEntriesOutput { entries, feeds },
+            output:
+                FetchEntriesOutput {
+                    entries,
+                    feeds,
+                    mut duplicates,
+                },
         } = run_usecase!(FetchEntries, cx, input, |err: FetchEntriesError| Err(
             async_graphql::ErrorExtensions::extend(&err)
         ))?;
@@ -117,8 +130,14 @@ impl Subscription {
                     .get(&feed_url)
                     .expect("FeedMeta not found. this is a bug")
                     .clone();
+                let duplicated_in = duplicates
+                    .remove(&entry.id())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|feed_url| feeds.get(&feed_url).cloned())
+                    .collect();
                 let cursor = entry.id().into();
-                let node = Entry::new(Cow::Owned(meta), entry);
+                let node = Entry::new(Cow::Owned(meta), entry).with_duplicates(duplicated_in);
                 Edge::new(cursor, node)
             });
 
@@ -126,6 +145,118 @@ impl Subscription {
 
         Ok(connection)
     }
+
+    /// Return the entry of the subscribed feed.
+    /// Intended to get the fields which are too large to get with the entries like the content
+    async fn entry<'cx>(
+        &self,
+        cx: &Context<'_>,
+        feed_url: FeedUrl,
+        id: ID,
+    ) -> Result<Option<Entry<'cx>>> {
+        let input = FetchEntriesInput {
+            after: None,
+            first: usize::MAX,
+            filter: EntryFilter {
+                feed_url: Some(feed_url),
+                ..Default::default()
+            },
+            deduplicate: false,
+        };
+        let Output {
+            output: FetchEntriesOutput { entries, feeds, .. },
+        } = run_usecase!(FetchEntries, cx, input, |err: FetchEntriesError| Err(
+            async_graphql::ErrorExtensions::extend(&err)
+        ))?;
+
+        let entry = entries
+            .into_iter()
+            .find(|(entry, _)| entry.id_ref().to_string() == id.as_str())
+            .map(|(entry, feed_url)| {
+                let meta = feeds
+                    .get(&feed_url)
+                    .expect("FeedMeta not found. this is a bug")
+                    .clone();
+                Entry::new(Cow::Owned(meta), entry)
+            });
+
+        Ok(entry)
+    }
+}
+
+#[derive(SimpleObject)]
+struct FeedCacheStats {
+    /// Number of cached feeds
+    entry_count: u64,
+    /// Approximate total size of cached feeds in bytes
+    weighted_size: u64,
+    hit_count: u64,
+    miss_count: u64,
+    /// Ratio of cache hits to lookups since the server started
+    hit_ratio: f64,
+}
+
+#[derive(SimpleObject)]
+struct CachedFeed {
+    url: FeedUrl,
+    /// Elapsed seconds since the feed was fetched
+    age_seconds: u64,
+}
+
+#[derive(SimpleObject)]
+struct FeedCache {
+    stats: FeedCacheStats,
+    feeds: Vec<CachedFeed>,
+}
+
+struct Admin;
+
+/// Operations which require the admin role
+#[Object]
+impl Admin {
+    /// Return feed cache statistics and cached feeds
+    async fn feed_cache(&self, cx: &Context<'_>) -> Result<FeedCache> {
+        let Output {
+            output: InspectFeedCacheOutput { stats, entries },
+        } = run_usecase!(
+            InspectFeedCache,
+            cx,
+            InspectFeedCacheInput {},
+            |err: Infallible| match err {}
+        )?;
+
+        Ok(FeedCache {
+            stats: FeedCacheStats {
+                entry_count: stats.entry_count,
+                weighted_size: stats.weighted_size,
+                hit_count: stats.hit_count,
+                miss_count: stats.miss_count,
+                hit_ratio: stats.hit_ratio(),
+            },
+            feeds: entries
+                .into_iter()
+                .map(|entry| CachedFeed {
+                    url: entry.url,
+                    age_seconds: entry.age.as_secs(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Return the number of users
+    /// Users who have not signed in since the user records were introduced are not included
+    async fn user_count(&self, cx: &Context<'_>) -> Result<u64> {
+        let Output {
+            output: CountUsersOutput { count },
+        } = run_usecase!(
+            CountUsers,
+            cx,
+            CountUsersInput {},
+            |err: Infallible| match err {}
+        )?;
+
+        Ok(count)
+    }
 }
 
 pub(crate) struct Query;
@@ -135,4 +266,8 @@ impl Query {
     async fn subscription(&self) -> Subscription {
         Subscription {}
     }
+
+    async fn admin(&self) -> Admin {
+        Admin {}
+    }
 }
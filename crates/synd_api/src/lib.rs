@@ -5,11 +5,13 @@
 pub mod cli;
 pub mod client;
 pub mod config;
+pub mod credential;
 pub mod dependency;
 pub(crate) mod gql;
 pub mod monitor;
 pub(crate) mod principal;
 pub mod repository;
+pub(crate) mod rest;
 pub mod serve;
 pub mod shutdown;
 pub mod usecase;
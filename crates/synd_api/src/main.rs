@@ -1,6 +1,7 @@
 use std::env;
 
 use fdlimit::Outcome;
+use reqwest::Url;
 use synd_o11y::{
     opentelemetry::OpenTelemetryGuard, tracing_subscriber::initializer::TracingInitializer,
 };
@@ -43,6 +44,9 @@ async fn run(
         tls,
         o11y,
         cache,
+        fetch,
+        oidc,
+        authorization,
         dry_run,
     }: Args,
     shutdown: Shutdown,
@@ -52,6 +56,9 @@ async fn run(
         tls,
         serve,
         cache.clone(),
+        fetch.clone(),
+        oidc.clone(),
+        authorization,
         shutdown.cancellation_token(),
     )
     .await?;
@@ -59,6 +66,9 @@ async fn run(
     info!(
         version = config::app::VERSION,
         otlp_endpoint=?o11y.otlp_endpoint,
+        oidc_issuer=?oidc.oidc_issuer.as_ref().map(Url::as_str),
+        fetch_proxy=?fetch.fetch_proxy.as_ref().map(Url::host_str),
+        feed_credential_enabled=fetch.fetch_credential_key.is_some(),
         request_timeout=?dep.serve_options.timeout,
         request_body_limit_bytes=dep.serve_options.body_limit_bytes,
         concurrency_limit=?dep.serve_options.concurrency_limit,
@@ -1,8 +1,13 @@
 use std::{
     collections::hash_map::DefaultHasher,
+    fmt,
     hash::{Hash, Hasher},
+    str::FromStr,
 };
 
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Debug)]
 pub enum Principal {
     User(User),
@@ -15,40 +20,252 @@ impl Principal {
             Principal::User(User { id, .. }) => Some(id.as_str()),
         }
     }
+
+    pub fn role(&self) -> Role {
+        match self {
+            Principal::User(User { role, .. }) => *role,
+        }
+    }
+
+    pub fn permits(&self, permission: Permission) -> bool {
+        self.role().permits(permission)
+    }
+}
+
+/// Role of the principal which determines the permitted operations
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Operator of synd-api
+    Admin,
+    /// Regular user
+    #[default]
+    Member,
+    /// Can only read, for example an api token scoped to read
+    ReadOnly,
+}
+
+impl Role {
+    pub fn permits(self, permission: Permission) -> bool {
+        match self {
+            Role::Admin => true,
+            Role::Member => matches!(permission, Permission::Read | Permission::Write),
+            Role::ReadOnly => matches!(permission, Permission::Read),
+        }
+    }
+}
+
+/// Permission required by an operation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    Read,
+    Write,
+    Admin,
+}
+
+impl Permission {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Permission::Read => "read",
+            Permission::Write => "write",
+            Permission::Admin => "admin",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct User {
     id: String,
-    #[allow(dead_code)]
     email: String,
+    role: Role,
 }
 
 impl User {
-    pub fn from_email(email: impl Into<String>) -> Self {
-        let mut s = DefaultHasher::new();
-        let email = email.into();
+    pub fn new(id: impl Into<String>, email: impl Into<String>) -> Self {
+        User {
+            id: id.into(),
+            email: email.into(),
+            role: Role::default(),
+        }
+    }
 
-        email.hash(&mut s);
-        let id = s.finish();
-        let id = format!("{id:016x}");
+    #[must_use]
+    pub fn with_role(self, role: Role) -> Self {
+        Self { role, ..self }
+    }
 
-        User { id, email }
+    /// Generate a random id for a new user
+    pub fn generate_id() -> String {
+        format!("{:032x}", rand::rng().random::<u128>())
+    }
+
+    /// Id derived from the email which identified users before user records were persisted.
+    /// It is only looked up to migrate their subscriptions, never assigned to new users.
+    pub fn legacy_id(email: &str) -> String {
+        let mut s = DefaultHasher::new();
+        email.hash(&mut s);
+        format!("{:016x}", s.finish())
     }
 
     pub fn id(&self) -> &str {
         self.id.as_str()
     }
+
+    pub fn email(&self) -> &str {
+        self.email.as_str()
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+}
+
+/// Authentication provider which verified the identity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, async_graphql::Enum)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentityProvider {
+    Github,
+    Google,
+    Oidc,
+}
+
+impl IdentityProvider {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IdentityProvider::Github => "github",
+            IdentityProvider::Google => "google",
+            IdentityProvider::Oidc => "oidc",
+        }
+    }
+}
+
+impl fmt::Display for IdentityProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IdentityProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "github" => Ok(IdentityProvider::Github),
+            "google" => Ok(IdentityProvider::Google),
+            "oidc" => Ok(IdentityProvider::Oidc),
+            _ => Err(format!("unknown identity provider `{s}`")),
+        }
+    }
+}
+
+/// Identity verified by an authentication provider
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identity {
+    pub provider: IdentityProvider,
+    /// Stable identifier of the account issued by the provider.
+    /// `sub` claim for google and oidc, node id for github
+    pub subject: String,
+    pub email: String,
+}
+
+impl Identity {
+    /// Whether both identify the same account of the provider regardless of the email
+    pub fn is_same_account(&self, other: &Identity) -> bool {
+        self.provider == other.provider && self.subject == other.subject
+    }
+
+    pub fn account(&self) -> Account {
+        Account {
+            provider: self.provider,
+            subject: self.subject.clone(),
+        }
+    }
+}
+
+/// Account of the provider in the form of `{provider}:{subject}`. ex. `github:MDQ6VXNlcjE=`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Account {
+    pub provider: IdentityProvider,
+    pub subject: String,
+}
+
+impl FromStr for Account {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (provider, subject) = s
+            .split_once(':')
+            .ok_or_else(|| format!("`{s}` is not in the form of `{{provider}}:{{subject}}`"))?;
+        if subject.is_empty() {
+            return Err(format!("subject of `{s}` is empty"));
+        }
+        Ok(Account {
+            provider: provider.parse()?,
+            subject: subject.to_owned(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::User;
+    use super::{Account, IdentityProvider, Permission, Principal, Role, User};
+
+    #[test]
+    fn user_id() {
+        assert_eq!(User::legacy_id("foo@ymgyt.io"), "585779d8c9b2e06d");
+
+        let id = User::generate_id();
+        assert_eq!(id.len(), 32);
+        assert_ne!(id, User::generate_id());
+    }
+
+    #[test]
+    fn role_permits() {
+        let permissions = [Permission::Read, Permission::Write, Permission::Admin];
+        let permitted = |role: Role| {
+            permissions
+                .into_iter()
+                .filter(|p| Principal::User(User::new("id", "email").with_role(role)).permits(*p))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(permitted(Role::Admin), permissions);
+        assert_eq!(
+            permitted(Role::Member),
+            [Permission::Read, Permission::Write]
+        );
+        assert_eq!(permitted(Role::ReadOnly), [Permission::Read]);
+        assert_eq!(User::new("id", "email").role, Role::Member);
+    }
+
+    #[test]
+    fn identity_provider_serde() {
+        for provider in [
+            IdentityProvider::Github,
+            IdentityProvider::Google,
+            IdentityProvider::Oidc,
+        ] {
+            let json = serde_json::to_string(&provider).unwrap();
+            assert_eq!(json, format!("\"{provider}\""));
+            assert_eq!(
+                serde_json::from_str::<IdentityProvider>(&json).unwrap(),
+                provider
+            );
+            assert_eq!(provider.as_str().parse::<IdentityProvider>(), Ok(provider));
+        }
+    }
 
     #[test]
-    fn user_from_email() {
-        let u = User::from_email("foo@ymgyt.io");
-        assert_eq!(u.id().len(), 16);
-        assert_eq!(u.id(), "585779d8c9b2e06d");
+    fn parse_account() {
+        assert_eq!(
+            "oidc:https://id.example.com/users/1".parse::<Account>(),
+            Ok(Account {
+                provider: IdentityProvider::Oidc,
+                subject: "https://id.example.com/users/1".into(),
+            })
+        );
+        assert!("github".parse::<Account>().is_err());
+        assert!("github:".parse::<Account>().is_err());
+        assert!("gitlab:1".parse::<Account>().is_err());
     }
 }
@@ -11,10 +11,15 @@ use thiserror::Error;
 use tokio::sync::Mutex;
 use tokio::{net::TcpStream, sync::MutexGuard};
 
-use crate::repository::{
-    self, RepositoryError, SubscriptionRepository,
-    subscription::RepositoryResult,
-    types::{FeedAnnotations, SubscribedFeeds},
+use crate::{
+    principal::Identity,
+    repository::{
+        self, RepositoryError, SubscriptionRepository, UserRepository,
+        subscription::RepositoryResult,
+        types::{
+            FeedAnnotations, FeedTokenLink, IdentityLink, SubscribedFeeds, UserRecord, UserStats,
+        },
+    },
 };
 
 #[derive(Error, Debug)]
@@ -103,6 +108,31 @@ impl KvsdClient {
         Key::new(key).expect("Invalid key")
     }
 
+    fn user_key(user_id: &str) -> Key {
+        let key = format!("{prefix}/user/{user_id}", prefix = Self::key_prefix());
+        Key::new(key).expect("Invalid key")
+    }
+
+    fn identity_key(identity: &Identity) -> Key {
+        let key = format!(
+            "{prefix}/identity/{provider}/{subject}",
+            prefix = Self::key_prefix(),
+            provider = identity.provider,
+            subject = identity.subject,
+        );
+        Key::new(key).expect("Invalid key")
+    }
+
+    fn feed_token_key(token: &str) -> Key {
+        let key = format!("{prefix}/feed_token/{token}", prefix = Self::key_prefix());
+        Key::new(key).expect("Invalid key")
+    }
+
+    fn user_stats_key() -> Key {
+        let key = format!("{prefix}/stats/user", prefix = Self::key_prefix());
+        Key::new(key).expect("Invalid key")
+    }
+
     fn key_prefix() -> &'static str {
         "/synd_api/v1"
     }
@@ -121,6 +151,7 @@ impl SubscriptionRepository for KvsdClient {
         let annotations = FeedAnnotations {
             requirement: feed.requirement,
             category: feed.category,
+            credential: feed.credential,
         };
 
         let feeds = if let Some(mut feeds) =
@@ -187,3 +218,100 @@ impl SubscriptionRepository for KvsdClient {
         Ok(feeds)
     }
 }
+
+#[async_trait]
+impl UserRepository for KvsdClient {
+    #[tracing::instrument(name = "repo::fetch_user", skip_all)]
+    async fn fetch_user(&self, user_id: &str) -> RepositoryResult<Option<UserRecord>> {
+        let key = Self::user_key(user_id);
+
+        let mut client = self.client.lock().await;
+        Self::get::<UserRecord>(&mut client, key).await
+    }
+
+    #[tracing::instrument(name = "repo::fetch_user_by_identity", skip_all)]
+    async fn fetch_user_by_identity(
+        &self,
+        identity: &Identity,
+    ) -> RepositoryResult<Option<UserRecord>> {
+        let key = Self::identity_key(identity);
+
+        let mut client = self.client.lock().await;
+        let Some(IdentityLink { user_id }) = Self::get::<IdentityLink>(&mut client, key).await?
+        else {
+            return Ok(None);
+        };
+        Self::get::<UserRecord>(&mut client, Self::user_key(&user_id)).await
+    }
+
+    #[tracing::instrument(name = "repo::fetch_user_by_feed_token", skip_all)]
+    async fn fetch_user_by_feed_token(&self, token: &str) -> RepositoryResult<Option<UserRecord>> {
+        let key = Self::feed_token_key(token);
+
+        let mut client = self.client.lock().await;
+        let Some(FeedTokenLink { user_id }) = Self::get::<FeedTokenLink>(&mut client, key).await?
+        else {
+            return Ok(None);
+        };
+        let user = Self::get::<UserRecord>(&mut client, Self::user_key(&user_id)).await?;
+        // Reissued tokens are revoked
+        Ok(user.filter(|user| user.feed_token.as_deref() == Some(token)))
+    }
+
+    #[tracing::instrument(name = "repo::put_user", skip_all)]
+    async fn put_user(&self, user: UserRecord) -> RepositoryResult<()> {
+        let mut client = self.client.lock().await;
+        for identity in &user.identities {
+            Self::set(
+                &mut client,
+                Self::identity_key(identity),
+                IdentityLink {
+                    user_id: user.id.clone(),
+                },
+            )
+            .await?;
+        }
+
+        if let Some(token) = user.feed_token.as_deref() {
+            Self::set(
+                &mut client,
+                Self::feed_token_key(token),
+                FeedTokenLink {
+                    user_id: user.id.clone(),
+                },
+            )
+            .await?;
+        }
+
+        let key = Self::user_key(&user.id);
+        // kvsd does not support scanning keys, so maintain the number of users on write
+        let counted = Self::get::<UserRecord>(&mut client, key.clone())
+            .await?
+            .is_some_and(|stored| stored.counted);
+        Self::set(
+            &mut client,
+            key,
+            UserRecord {
+                counted: true,
+                ..user
+            },
+        )
+        .await?;
+
+        if !counted {
+            let mut stats = Self::get::<UserStats>(&mut client, Self::user_stats_key())
+                .await?
+                .unwrap_or_default();
+            stats.count += 1;
+            Self::set(&mut client, Self::user_stats_key(), stats).await?;
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::count_users", skip_all)]
+    async fn count_users(&self) -> RepositoryResult<u64> {
+        let mut client = self.client.lock().await;
+        let stats = Self::get::<UserStats>(&mut client, Self::user_stats_key()).await?;
+        Ok(stats.unwrap_or_default().count)
+    }
+}
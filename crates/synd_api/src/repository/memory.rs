@@ -0,0 +1,131 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+
+use crate::{
+    principal::Identity,
+    repository::{
+        SubscriptionRepository, UserRepository,
+        subscription::RepositoryResult,
+        types::{FeedAnnotations, FeedSubscription, SubscribedFeeds, UserRecord},
+    },
+};
+
+/// In memory repository for tests
+#[derive(Default)]
+pub(crate) struct MemoryRepository {
+    users: Mutex<HashMap<String, UserRecord>>,
+    count: Mutex<u64>,
+    subscriptions: Mutex<HashMap<String, Vec<FeedSubscription>>>,
+}
+
+impl MemoryRepository {
+    /// Store given records as is like the ones persisted by the older versions
+    pub(crate) fn with_users(users: impl IntoIterator<Item = UserRecord>) -> Self {
+        Self {
+            users: Mutex::new(
+                users
+                    .into_iter()
+                    .map(|user| (user.id.clone(), user))
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn users(&self) -> Vec<UserRecord> {
+        self.users.lock().unwrap().values().cloned().collect()
+    }
+}
+
+#[async_trait]
+impl UserRepository for MemoryRepository {
+    async fn fetch_user(&self, user_id: &str) -> RepositoryResult<Option<UserRecord>> {
+        Ok(self.users.lock().unwrap().get(user_id).cloned())
+    }
+
+    async fn fetch_user_by_identity(
+        &self,
+        identity: &Identity,
+    ) -> RepositoryResult<Option<UserRecord>> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .find(|user| user.is_linked(identity))
+            .cloned())
+    }
+
+    async fn fetch_user_by_feed_token(&self, token: &str) -> RepositoryResult<Option<UserRecord>> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .values()
+            .find(|user| user.feed_token.as_deref() == Some(token))
+            .cloned())
+    }
+
+    async fn put_user(&self, user: UserRecord) -> RepositoryResult<()> {
+        let stored = self.users.lock().unwrap().insert(
+            user.id.clone(),
+            UserRecord {
+                counted: true,
+                ..user
+            },
+        );
+        if !stored.is_some_and(|stored| stored.counted) {
+            *self.count.lock().unwrap() += 1;
+        }
+        Ok(())
+    }
+
+    async fn count_users(&self) -> RepositoryResult<u64> {
+        Ok(*self.count.lock().unwrap())
+    }
+}
+
+#[async_trait]
+impl SubscriptionRepository for MemoryRepository {
+    async fn put_feed_subscription(&self, feed: FeedSubscription) -> RepositoryResult<()> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let feeds = subscriptions.entry(feed.user_id.clone()).or_default();
+        feeds.retain(|subscribed| subscribed.url != feed.url);
+        feeds.push(feed);
+        Ok(())
+    }
+
+    async fn delete_feed_subscription(&self, feed: FeedSubscription) -> RepositoryResult<()> {
+        if let Some(feeds) = self.subscriptions.lock().unwrap().get_mut(&feed.user_id) {
+            feeds.retain(|subscribed| subscribed.url != feed.url);
+        }
+        Ok(())
+    }
+
+    async fn fetch_subscribed_feeds(&self, user_id: &str) -> RepositoryResult<SubscribedFeeds> {
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let feeds = subscriptions
+            .get(user_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        Ok(SubscribedFeeds {
+            urls: feeds.iter().map(|feed| feed.url.clone()).collect(),
+            annotations: Some(
+                feeds
+                    .iter()
+                    .map(|feed| {
+                        (
+                            feed.url.clone(),
+                            FeedAnnotations {
+                                requirement: feed.requirement,
+                                category: feed.category.clone(),
+                                credential: feed.credential.clone(),
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+        })
+    }
+}
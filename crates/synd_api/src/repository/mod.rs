@@ -2,9 +2,15 @@ mod subscription;
 use ::kvsd::KvsdError;
 pub use subscription::SubscriptionRepository;
 
+mod user;
+pub use user::UserRepository;
+
 pub mod kvsd;
 pub mod types;
 
+#[cfg(test)]
+pub(crate) mod memory;
+
 #[derive(thiserror::Error, Debug)]
 pub enum RepositoryError {
     #[error("internal error: {0}")]
@@ -4,7 +4,11 @@ use kvsd::Value;
 use serde::{Deserialize, Serialize};
 use synd_feed::types::{Category, FeedUrl, Requirement};
 
-use crate::repository::RepositoryError;
+use crate::{
+    credential::EncryptedCredential,
+    principal::{Identity, Role},
+    repository::RepositoryError,
+};
 
 #[derive(Debug, Clone)]
 pub struct Feed {
@@ -18,6 +22,7 @@ pub struct FeedSubscription {
     pub url: FeedUrl,
     pub requirement: Option<Requirement>,
     pub category: Option<Category<'static>>,
+    pub credential: Option<EncryptedCredential>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -30,6 +35,9 @@ pub struct SubscribedFeeds {
 pub struct FeedAnnotations {
     pub requirement: Option<Requirement>,
     pub category: Option<Category<'static>>,
+    /// Credential to fetch the private feed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential: Option<EncryptedCredential>,
 }
 
 impl TryFrom<Value> for SubscribedFeeds {
@@ -48,3 +56,116 @@ impl TryFrom<SubscribedFeeds> for Value {
         Ok(Value::new(value).unwrap())
     }
 }
+
+/// Persisted user with the identities linked to it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserRecord {
+    pub id: String,
+    pub email: String,
+    pub identities: Vec<Identity>,
+    /// Records persisted before roles were introduced are members
+    #[serde(default)]
+    pub role: Role,
+    /// Secret token to read the aggregated feed of the user
+    #[serde(default)]
+    pub feed_token: Option<String>,
+    /// Whether the user is included in `UserStats`. set by the repository.
+    /// Records persisted before the stats were introduced are counted on the next sign in
+    #[serde(default)]
+    pub counted: bool,
+}
+
+impl UserRecord {
+    pub fn is_linked(&self, identity: &Identity) -> bool {
+        self.identities
+            .iter()
+            .any(|linked| linked.is_same_account(identity))
+    }
+}
+
+impl TryFrom<Value> for UserRecord {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<UserRecord> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: UserRecord) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+/// Index from an identity to the user it is linked to
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdentityLink {
+    pub user_id: String,
+}
+
+impl TryFrom<Value> for IdentityLink {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<IdentityLink> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: IdentityLink) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+/// Index from a feed token to the user who issued it
+/// Once the token is reissued, the index remains but no longer matches the user record
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FeedTokenLink {
+    pub user_id: String,
+}
+
+impl TryFrom<Value> for FeedTokenLink {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<FeedTokenLink> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: FeedTokenLink) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+/// Aggregated counts of persisted users
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct UserStats {
+    pub count: u64,
+}
+
+impl TryFrom<Value> for UserStats {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<UserStats> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: UserStats) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
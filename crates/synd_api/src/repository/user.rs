@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::{
+    principal::Identity,
+    repository::{subscription::RepositoryResult, types::UserRecord},
+};
+
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn fetch_user(&self, user_id: &str) -> RepositoryResult<Option<UserRecord>>;
+
+    async fn fetch_user_by_identity(
+        &self,
+        identity: &Identity,
+    ) -> RepositoryResult<Option<UserRecord>>;
+
+    /// Return the user who issued given feed token
+    async fn fetch_user_by_feed_token(&self, token: &str) -> RepositoryResult<Option<UserRecord>>;
+
+    /// Persist the user and link its identities and feed token to it
+    async fn put_user(&self, user: UserRecord) -> RepositoryResult<()>;
+
+    /// Return the number of persisted users
+    async fn count_users(&self) -> RepositoryResult<u64>;
+}
@@ -0,0 +1,572 @@
+//! Aggregated feed of the subscribed entries for feed readers
+//! which can not authenticate with the authorization header
+use axum::{
+    Extension,
+    extract::{OriginalUri, Path, Query},
+    http::{HeaderMap, HeaderValue, StatusCode, header, uri::Scheme},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use synd_feed::types::{self, Category, FeedUrl, Requirement};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    rest::{ApiError, ErrorCode, ErrorResponse},
+    serve::{Context, auth::Authenticator},
+    usecase::{EntryFilter, FetchEntries, FetchEntriesInput, FetchEntriesOutput, Output},
+};
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 200;
+
+/// Format of the aggregated feed
+#[derive(Deserialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Format {
+    #[default]
+    Atom,
+    Rss,
+    /// [JSON Feed 1.1](https://www.jsonfeed.org/version/1.1/)
+    Json,
+}
+
+impl Format {
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Atom => "application/atom+xml; charset=utf-8",
+            Format::Rss => "application/rss+xml; charset=utf-8",
+            Format::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub(super) struct FeedParams {
+    /// Format of the document. defaults to atom
+    format: Option<Format>,
+    /// Only include entries of the feeds whose requirement satisfies this requirement.
+    /// Feeds without requirement are treated as `should`
+    #[param(example = "should")]
+    requirement: Option<String>,
+    /// Comma separated categories. only include entries of the feeds belonging to them
+    #[param(example = "rust,linux")]
+    category: Option<String>,
+    /// Number of entries to include. max 200
+    limit: Option<usize>,
+}
+
+impl FeedParams {
+    fn filter(&self) -> Result<EntryFilter, ApiError> {
+        let requirement = self
+            .requirement
+            .as_deref()
+            .map(str::parse::<Requirement>)
+            .transpose()
+            .map_err(ApiError::invalid_input)?;
+        let categories = self
+            .category
+            .as_deref()
+            .into_iter()
+            .flat_map(|categories| categories.split(','))
+            .filter(|category| !category.trim().is_empty())
+            .map(|category| {
+                Category::new(category.to_owned())
+                    .map_err(|err| ApiError::invalid_input(format!("category: {err}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(EntryFilter {
+            requirement,
+            categories,
+            feed_url: None,
+        })
+    }
+}
+
+/// Aggregated feed of the subscribed entries
+/// The token is issued by the `issueFeedToken` graphql mutation
+#[utoipa::path(
+    get,
+    path = "/api/v1/feed/{token}",
+    params(
+        ("token" = String, Path, description = "Secret token issued for the aggregated feed"),
+        FeedParams,
+    ),
+    responses(
+        (status = 200, description = "Atom, RSS 2.0 or JSON Feed document", content(
+            (String = "application/atom+xml"),
+            (String = "application/rss+xml"),
+            (String = "application/feed+json"),
+        )),
+        (status = 400, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+        (status = 429, description = "Rate limit of the token is exceeded"),
+    ),
+    security(()),
+)]
+pub(super) async fn aggregated_feed(
+    Extension(cx): Extension<Context>,
+    Extension(authenticator): Extension<Authenticator>,
+    Extension(scheme): Extension<Scheme>,
+    Path(token): Path<String>,
+    Query(params): Query<FeedParams>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let Ok(principal) = authenticator.authenticate_feed_token(&token).await else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::NotFound,
+            "feed not found",
+        ));
+    };
+    let filter = params.filter()?;
+    let format = params.format.unwrap_or_default();
+
+    let Output {
+        output: FetchEntriesOutput { entries, feeds, .. },
+    } = cx
+        .runtime
+        .run::<FetchEntries, _, _>(
+            &principal,
+            FetchEntriesInput {
+                after: None,
+                first: params.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT),
+                filter,
+                deduplicate: false,
+            },
+        )
+        .await
+        .map_err(|err| ApiError::from_usecase(err, |err| ApiError::internal(format!("{err}"))))?;
+
+    let entries = entries
+        .into_iter()
+        .map(|(entry, feed_url)| {
+            let feed = feeds.get(&feed_url);
+            let feed_type = feed.map_or(types::FeedType::Atom, |feed| feed.feed.r#type());
+            AggregatedEntry {
+                id: entry.id().to_string(),
+                title: entry.title().map(ToOwned::to_owned),
+                url: entry.website_url(feed_type).map(ToOwned::to_owned),
+                // Publishers html is sanitized not to run scripts in feed readers
+                summary: entry.summary_html(),
+                summary_text: entry.summary_text(None),
+                content: entry.content_html(),
+                enclosures: entry.enclosures(),
+                published: entry.published(),
+                updated: entry.updated(),
+                feed_title: feed.and_then(|feed| feed.feed.title().map(ToOwned::to_owned)),
+                category: feed.and_then(|feed| feed.category.clone()),
+                feed_url,
+            }
+        })
+        .collect();
+
+    // Feed readers identify the feed by the url, so use the url requested
+    let self_url = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(|host| format!("{scheme}://{host}{uri}"));
+
+    let aggregated = Aggregated::new(self_url, entries);
+    let body = match format {
+        Format::Atom => aggregated.into_atom(),
+        Format::Rss => aggregated.into_rss(),
+        Format::Json => aggregated.into_json_feed(),
+    }
+    .map_err(|err| ApiError::internal(format!("failed to render feed: {err}")))?;
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(format.content_type()),
+        )],
+        body,
+    )
+        .into_response())
+}
+
+struct AggregatedEntry {
+    id: String,
+    title: Option<String>,
+    url: Option<String>,
+    summary: Option<String>,
+    summary_text: Option<String>,
+    content: Option<String>,
+    enclosures: Vec<types::Enclosure>,
+    published: Option<types::Time>,
+    updated: Option<types::Time>,
+    feed_title: Option<String>,
+    feed_url: FeedUrl,
+    category: Option<Category<'static>>,
+}
+
+impl AggregatedEntry {
+    fn updated(&self) -> Option<types::Time> {
+        self.updated.or(self.published)
+    }
+}
+
+struct Aggregated {
+    self_url: Option<String>,
+    updated: types::Time,
+    entries: Vec<AggregatedEntry>,
+}
+
+impl Aggregated {
+    const TITLE: &'static str = "syndicationd";
+    const DESCRIPTION: &'static str = "Entries of the feeds subscribed in syndicationd";
+    const ID: &'static str = "urn:syndicationd:aggregated";
+
+    fn new(self_url: Option<String>, entries: Vec<AggregatedEntry>) -> Self {
+        let updated = entries
+            .iter()
+            .filter_map(AggregatedEntry::updated)
+            .max()
+            .unwrap_or_else(Utc::now);
+        Self {
+            self_url,
+            updated,
+            entries,
+        }
+    }
+
+    fn into_atom(self) -> anyhow::Result<Vec<u8>> {
+        use atom_syndication as atom;
+
+        let fixed = |time: types::Time| DateTime::<FixedOffset>::from(time);
+        let feed_updated = fixed(self.updated);
+
+        let entries = self
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let updated = entry.updated().map_or(feed_updated, fixed);
+                atom::Entry {
+                    title: atom::Text::plain(entry.title.clone().unwrap_or_default()),
+                    updated,
+                    published: entry.published.map(fixed),
+                    links: entry
+                        .url
+                        .iter()
+                        .map(|url| atom::Link {
+                            href: url.clone(),
+                            rel: "alternate".into(),
+                            ..Default::default()
+                        })
+                        .chain(entry.enclosures.iter().map(|enclosure| atom::Link {
+                            href: enclosure.url.clone(),
+                            rel: "enclosure".into(),
+                            mime_type: enclosure.mime_type.clone(),
+                            length: enclosure.length.map(|length| length.to_string()),
+                            ..Default::default()
+                        }))
+                        .collect(),
+                    summary: entry.summary.map(atom::Text::html),
+                    content: entry.content.map(|content| atom::Content {
+                        value: Some(content),
+                        content_type: Some("html".into()),
+                        ..Default::default()
+                    }),
+                    categories: entry
+                        .category
+                        .iter()
+                        .map(|category| atom::Category {
+                            term: category.to_string(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    source: Some(atom::Source {
+                        title: atom::Text::plain(entry.feed_title.unwrap_or_default()),
+                        id: entry.feed_url.to_string(),
+                        updated,
+                        links: vec![atom::Link {
+                            href: entry.feed_url.to_string(),
+                            rel: "self".into(),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }),
+                    id: entry.id,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let feed = atom::Feed {
+            title: atom::Text::plain(Self::TITLE),
+            subtitle: Some(atom::Text::plain(Self::DESCRIPTION)),
+            id: self.self_url.clone().unwrap_or_else(|| Self::ID.to_owned()),
+            updated: feed_updated,
+            links: self
+                .self_url
+                .into_iter()
+                .map(|href| atom::Link {
+                    href,
+                    rel: "self".into(),
+                    ..Default::default()
+                })
+                .collect(),
+            entries,
+            ..Default::default()
+        };
+
+        Ok(feed.write_to(Vec::new())?)
+    }
+
+    fn into_rss(self) -> anyhow::Result<Vec<u8>> {
+        let items = self
+            .entries
+            .into_iter()
+            .map(|entry| rss::Item {
+                title: entry.title,
+                link: entry.url,
+                description: entry.summary.or(entry.content),
+                // Rss allows only one enclosure per item
+                enclosure: entry.enclosures.first().map(|enclosure| rss::Enclosure {
+                    url: enclosure.url.clone(),
+                    length: enclosure.length.unwrap_or_default().to_string(),
+                    mime_type: enclosure.mime_type.clone().unwrap_or_default(),
+                }),
+                pub_date: entry
+                    .published
+                    .or(entry.updated)
+                    .map(|time| time.to_rfc2822()),
+                guid: Some(rss::Guid {
+                    value: entry.id,
+                    permalink: false,
+                }),
+                categories: entry
+                    .category
+                    .iter()
+                    .map(|category| rss::Category {
+                        name: category.to_string(),
+                        domain: None,
+                    })
+                    .collect(),
+                source: Some(rss::Source {
+                    url: entry.feed_url.to_string(),
+                    title: entry.feed_title,
+                }),
+                ..Default::default()
+            })
+            .collect();
+
+        let channel = rss::Channel {
+            title: Self::TITLE.to_owned(),
+            link: self.self_url.unwrap_or_default(),
+            description: Self::DESCRIPTION.to_owned(),
+            last_build_date: Some(self.updated.to_rfc2822()),
+            items,
+            ..Default::default()
+        };
+
+        Ok(channel.write_to(Vec::new())?)
+    }
+
+    fn into_json_feed(self) -> anyhow::Result<Vec<u8>> {
+        let items = self
+            .entries
+            .into_iter()
+            .map(|entry| JsonFeedItem {
+                url: entry.url,
+                title: entry.title,
+                content_html: entry.content.or(entry.summary),
+                // JSON Feed summary is plain text
+                summary: entry.summary_text,
+                date_published: entry.published,
+                date_modified: entry.updated,
+                tags: entry
+                    .category
+                    .into_iter()
+                    .map(|category| category.to_string())
+                    .collect(),
+                external_url: Some(entry.feed_url.to_string()),
+                attachments: entry
+                    .enclosures
+                    .into_iter()
+                    .map(|enclosure| JsonFeedAttachment {
+                        url: enclosure.url,
+                        mime_type: enclosure.mime_type,
+                        size_in_bytes: enclosure.length,
+                        duration_in_seconds: enclosure.duration.map(|duration| duration.as_secs()),
+                    })
+                    .collect(),
+                id: entry.id,
+            })
+            .collect();
+
+        Ok(serde_json::to_vec(&JsonFeed {
+            version: JsonFeed::VERSION,
+            title: Self::TITLE,
+            description: Self::DESCRIPTION,
+            feed_url: self.self_url,
+            items,
+        })?)
+    }
+}
+
+#[derive(Serialize)]
+struct JsonFeed {
+    version: &'static str,
+    title: &'static str,
+    description: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feed_url: Option<String>,
+    items: Vec<JsonFeedItem>,
+}
+
+impl JsonFeed {
+    const VERSION: &'static str = "https://jsonfeed.org/version/1.1";
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    /// Url of the feed the entry belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    // JSON Feed requires either content_html or content_text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_published: Option<types::Time>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_modified: Option<types::Time>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<JsonFeedAttachment>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedAttachment {
+    url: String,
+    // JSON Feed requires mime_type, but it is not always provided by the publisher
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_in_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_in_seconds: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn aggregated() -> Aggregated {
+        let time = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        Aggregated::new(
+            Some("https://synd.example.com/api/v1/feed/xxx".into()),
+            vec![AggregatedEntry {
+                id: "entry-1".into(),
+                title: Some("Entry 1".into()),
+                url: Some("https://blog.example.com/1".into()),
+                summary: Some("summary".into()),
+                summary_text: Some("summary".into()),
+                content: Some("<p>content</p>".into()),
+                enclosures: vec![types::Enclosure {
+                    url: "https://blog.example.com/1.mp3".into(),
+                    mime_type: Some("audio/mpeg".into()),
+                    length: Some(1024),
+                    duration: Some(std::time::Duration::from_secs(60)),
+                }],
+                published: Some(time),
+                updated: None,
+                feed_title: Some("Blog".into()),
+                feed_url: FeedUrl::parse("https://blog.example.com/feed.xml").unwrap(),
+                category: Some(Category::new("rust").unwrap()),
+            }],
+        )
+    }
+
+    #[test]
+    fn render_atom() {
+        let body = String::from_utf8(aggregated().into_atom().unwrap()).unwrap();
+        let feed = body.parse::<atom_syndication::Feed>().unwrap();
+
+        assert_eq!(feed.id, "https://synd.example.com/api/v1/feed/xxx");
+        assert_eq!(feed.updated.to_rfc3339(), "2024-06-01T00:00:00+00:00");
+        let entry = &feed.entries[0];
+        assert_eq!(entry.id, "entry-1");
+        assert_eq!(entry.links[0].href, "https://blog.example.com/1");
+        assert_eq!(entry.links[1].rel, "enclosure");
+        assert_eq!(entry.links[1].href, "https://blog.example.com/1.mp3");
+        assert_eq!(entry.links[1].length.as_deref(), Some("1024"));
+        assert_eq!(entry.categories[0].term, "rust");
+        assert_eq!(
+            entry.source.as_ref().unwrap().id,
+            "https://blog.example.com/feed.xml"
+        );
+    }
+
+    #[test]
+    fn render_rss() {
+        let body = aggregated().into_rss().unwrap();
+        let channel = rss::Channel::read_from(body.as_slice()).unwrap();
+
+        let item = &channel.items[0];
+        assert_eq!(item.guid.as_ref().unwrap().value, "entry-1");
+        assert_eq!(item.description.as_deref(), Some("summary"));
+        assert_eq!(
+            item.pub_date.as_deref(),
+            Some("Sat, 1 Jun 2024 00:00:00 +0000")
+        );
+        assert_eq!(item.categories[0].name, "rust");
+        let enclosure = item.enclosure.as_ref().unwrap();
+        assert_eq!(enclosure.url, "https://blog.example.com/1.mp3");
+        assert_eq!(enclosure.mime_type, "audio/mpeg");
+    }
+
+    #[test]
+    fn render_json_feed() {
+        let body = aggregated().into_json_feed().unwrap();
+        let feed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(feed["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(feed["items"][0]["id"], "entry-1");
+        assert_eq!(feed["items"][0]["content_html"], "<p>content</p>");
+        assert_eq!(feed["items"][0]["tags"][0], "rust");
+        assert_eq!(feed["items"][0]["date_published"], "2024-06-01T00:00:00Z");
+        let attachment = &feed["items"][0]["attachments"][0];
+        assert_eq!(attachment["url"], "https://blog.example.com/1.mp3");
+        assert_eq!(attachment["size_in_bytes"], 1024);
+        assert_eq!(attachment["duration_in_seconds"], 60);
+    }
+
+    #[test]
+    fn filter_params() {
+        let params = FeedParams {
+            format: None,
+            requirement: Some("must".into()),
+            category: Some("rust, linux,".into()),
+            limit: None,
+        };
+        let filter = params.filter().unwrap();
+        assert_eq!(filter.requirement, Some(Requirement::Must));
+        assert_eq!(
+            filter.categories,
+            vec![
+                Category::new("rust").unwrap(),
+                Category::new("linux").unwrap()
+            ]
+        );
+
+        let params = FeedParams {
+            requirement: Some("unknown".into()),
+            ..params
+        };
+        assert!(params.filter().is_err());
+    }
+}
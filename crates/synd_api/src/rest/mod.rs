@@ -0,0 +1,248 @@
+//! Module for REST api which provides the same operations as graphql
+//! for clients which can not speak graphql
+use std::time::Duration;
+
+use axum::{
+    Json, Router,
+    http::{Method, StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::Serialize;
+use synd_feed::feed::service::FetchFeedError;
+use utoipa::{
+    Modify, OpenApi, ToSchema,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
+
+use crate::{principal::Principal, usecase};
+
+mod feed;
+mod v1;
+
+pub(crate) const OPENAPI_PATH: &str = "/api/v1/openapi.json";
+pub(crate) const SUBSCRIPTIONS_PATH: &str = "/api/v1/subscriptions";
+pub(crate) const IMPORT_PATH: &str = "/api/v1/import";
+const FEED_PATH: &str = "/api/v1/feed/{token}";
+
+/// Max bytes of the import request body which contains up to the max number of feeds
+pub(crate) const IMPORT_BODY_LIMIT_BYTES: usize = v1::MAX_IMPORT_FEEDS * 1024;
+
+/// Return the max bytes of the request body.
+/// Import is allowed to exceed the default limit so that it can contain the max number of feeds
+pub(crate) fn body_limit(parts: &Parts, default: usize) -> usize {
+    if parts.method == Method::POST && parts.uri.path() == IMPORT_PATH {
+        default.max(IMPORT_BODY_LIMIT_BYTES)
+    } else {
+        default
+    }
+}
+
+/// Return the path of the aggregated feed for given token
+pub(crate) fn feed_path(token: &str) -> String {
+    FEED_PATH.replace("{token}", token)
+}
+
+/// Return the token of the aggregated feed path
+pub(crate) fn feed_token(path: &str) -> Option<&str> {
+    path.strip_prefix(FEED_PATH.trim_end_matches("{token}"))
+        .filter(|token| !token.is_empty() && !token.contains('/'))
+}
+
+/// Return routes which require authentication
+pub(crate) fn router() -> Router {
+    Router::new()
+        .route("/api/v1/feeds", get(v1::list_feeds))
+        .route("/api/v1/entries", get(v1::list_entries))
+        .route(
+            SUBSCRIPTIONS_PATH,
+            post(v1::subscribe_feed).delete(v1::unsubscribe_feed),
+        )
+        .route("/api/v1/export", get(v1::export))
+        .route(IMPORT_PATH, post(v1::import))
+}
+
+/// Return routes which authenticate with the token in the path
+pub(crate) fn feed_router() -> Router {
+    Router::new().route(FEED_PATH, get(feed::aggregated_feed))
+}
+
+pub(crate) async fn openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "synd-api", description = "syndicationd REST api", version = "v1"),
+    paths(
+        v1::list_feeds,
+        v1::list_entries,
+        v1::subscribe_feed,
+        v1::unsubscribe_feed,
+        v1::export,
+        v1::import,
+        feed::aggregated_feed,
+    ),
+    modifiers(&Authorization),
+    security(("authorization" = [])),
+)]
+struct ApiDoc;
+
+struct Authorization;
+
+impl Modify for Authorization {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "authorization",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "authorization",
+                "`{provider} {token}` e.g. `github gho_xxx`",
+            ))),
+        );
+    }
+}
+
+impl usecase::Context for &Principal {
+    fn principal(&self) -> Principal {
+        (*self).clone()
+    }
+}
+
+#[derive(Serialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ErrorCode {
+    /// Principal does not have enough permissions
+    Unauthorized,
+    /// Given url is not valid feed url
+    InvalidFeedUrl,
+    /// The feed server returned a status other than 200
+    FeedUnavailable,
+    /// Request is malformed
+    InvalidInput,
+    /// Requested resource does not exist
+    NotFound,
+    /// Rate limit of the principal is exceeded
+    RateLimited,
+    /// Something went wrong
+    InternalError,
+}
+
+/// Error response body
+#[derive(Serialize, ToSchema, Debug)]
+pub(crate) struct ErrorResponse {
+    code: ErrorCode,
+    message: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct ApiError {
+    status: StatusCode,
+    body: ErrorResponse,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: ErrorResponse {
+                code,
+                message: message.into(),
+            },
+        }
+    }
+
+    fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidInput, message)
+    }
+
+    fn rate_limited(retry_after: Duration) -> Self {
+        Self::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::RateLimited,
+            format!(
+                "rate limit exceeded. retry after {}s",
+                retry_after.as_secs().max(1)
+            ),
+        )
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::InternalError,
+            message,
+        )
+    }
+
+    fn from_usecase<E>(err: usecase::Error<E>, handle: impl FnOnce(E) -> Self) -> Self {
+        match err {
+            usecase::Error::Usecase(err) => handle(err),
+            usecase::Error::Unauthorized(_) => Self::new(
+                StatusCode::FORBIDDEN,
+                ErrorCode::Unauthorized,
+                "unauthorized error",
+            ),
+            usecase::Error::Repository(_) => Self::internal("repository error"),
+        }
+    }
+
+    fn from_fetch_feed_error(err: FetchFeedError) -> Self {
+        match err {
+            FetchFeedError::InvalidFeed(kind) => Self::new(
+                StatusCode::BAD_REQUEST,
+                ErrorCode::InvalidFeedUrl,
+                format!("{kind}"),
+            ),
+            FetchFeedError::Blocked(blocked) => Self::new(
+                StatusCode::BAD_REQUEST,
+                ErrorCode::InvalidFeedUrl,
+                format!("{blocked}"),
+            ),
+            FetchFeedError::Fetch(request_err) => Self::new(
+                StatusCode::BAD_GATEWAY,
+                ErrorCode::FeedUnavailable,
+                format!("feed unavailable: {request_err}"),
+            ),
+            err => Self::internal(format!("{err}")),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn openapi_document() {
+        insta::assert_yaml_snapshot!("openapi", ApiDoc::openapi());
+    }
+
+    #[test]
+    fn parse_feed_token() {
+        assert_eq!(feed_token(&feed_path("abc")), Some("abc"));
+        assert_eq!(feed_token("/api/v1/feed/"), None);
+        assert_eq!(feed_token("/api/v1/feed/abc/x"), None);
+        assert_eq!(feed_token("/api/v1/feeds"), None);
+    }
+
+    #[test]
+    fn error_mapping() {
+        let err = ApiError::from_usecase(
+            usecase::Error::<()>::Unauthorized(usecase::authorize::Unauthorized),
+            |()| unreachable!(),
+        );
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        assert_eq!(err.body.code, ErrorCode::Unauthorized);
+
+        let err = ApiError::from_fetch_feed_error(FetchFeedError::Other(anyhow::anyhow!("error")));
+        assert_eq!(err.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.body.code, ErrorCode::InternalError);
+    }
+}
@@ -0,0 +1,938 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::Query,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use synd_feed::types::{self, Annotated, Category, FeedCredential, FeedUrl, Requirement};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    principal::Principal,
+    rest::{ApiError, ErrorResponse},
+    serve::{Context, layer::rate_limit::ExpensiveBudget},
+    usecase::{
+        EntryFilter, FetchEntries, FetchEntriesInput, FetchEntriesOutput, FetchSubscribedFeeds,
+        FetchSubscribedFeedsInput, FetchSubscribedFeedsOutput, Output, SubscribeFeed,
+        SubscribeFeedError, SubscribeFeedInput, SubscribeFeedOutput, UnsubscribeFeed,
+        UnsubscribeFeedInput,
+    },
+};
+
+/// Cursor based pagination parameters
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub(super) struct Pagination {
+    /// Cursor returned as `end_cursor` of the previous page
+    after: Option<String>,
+    /// Number of items to return
+    first: Option<usize>,
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub(super) struct EntriesParams {
+    /// Cursor returned as `end_cursor` of the previous page
+    after: Option<String>,
+    /// Number of items to return
+    first: Option<usize>,
+    /// Collapse the entries of the same article published in several feeds
+    deduplicate: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct Feed {
+    #[schema(value_type = String)]
+    url: FeedUrl,
+    title: Option<String>,
+    description: Option<String>,
+    website_url: Option<String>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    updated: Option<types::Time>,
+    #[schema(value_type = Option<String>, example = "Must")]
+    requirement: Option<Requirement>,
+    #[schema(value_type = Option<String>, example = "rust")]
+    category: Option<Category<'static>>,
+}
+
+impl From<Annotated<Arc<types::Feed>>> for Feed {
+    fn from(feed: Annotated<Arc<types::Feed>>) -> Self {
+        let meta = feed.feed.meta();
+        Self {
+            url: meta.url().clone(),
+            title: meta.title().map(ToOwned::to_owned),
+            description: meta.description().map(ToOwned::to_owned),
+            website_url: meta.website_url().map(ToOwned::to_owned),
+            updated: meta.updated(),
+            requirement: feed.requirement,
+            category: feed.category,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct FetchFeedError {
+    #[schema(value_type = String)]
+    url: FeedUrl,
+    message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct FeedsResponse {
+    feeds: Vec<Feed>,
+    /// Feeds which could not be fetched
+    errors: Vec<FetchFeedError>,
+    page_info: PageInfo,
+}
+
+/// Media file attached to the entry
+#[derive(Serialize, ToSchema)]
+pub(super) struct Enclosure {
+    url: String,
+    mime_type: Option<String>,
+    /// Size in bytes
+    length: Option<u64>,
+    duration_seconds: Option<u64>,
+}
+
+impl From<types::Enclosure> for Enclosure {
+    fn from(enclosure: types::Enclosure) -> Self {
+        Self {
+            url: enclosure.url,
+            mime_type: enclosure.mime_type,
+            length: enclosure.length,
+            duration_seconds: enclosure.duration.map(|duration| duration.as_secs()),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct Entry {
+    id: String,
+    title: Option<String>,
+    /// Summary written by the publisher as is
+    summary: Option<String>,
+    /// Summary sanitized to be safely embedded in html
+    summary_html: Option<String>,
+    /// Summary rendered as plain text with links as footnotes
+    summary_text: Option<String>,
+    website_url: Option<String>,
+    /// Media files such as podcast audio
+    enclosures: Vec<Enclosure>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    published: Option<types::Time>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    updated: Option<types::Time>,
+    #[schema(value_type = String)]
+    feed_url: FeedUrl,
+    feed_title: Option<String>,
+    /// Feeds of the duplicates collapsed into this entry
+    #[schema(value_type = Vec<String>)]
+    duplicate_feed_urls: Vec<FeedUrl>,
+    #[schema(value_type = Option<String>, example = "Must")]
+    requirement: Option<Requirement>,
+    #[schema(value_type = Option<String>, example = "rust")]
+    category: Option<Category<'static>>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct EntriesResponse {
+    entries: Vec<Entry>,
+    page_info: PageInfo,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct SubscribeFeedRequest {
+    /// Feed url to subscribe
+    #[schema(value_type = String)]
+    url: FeedUrl,
+    #[schema(value_type = Option<String>, example = "Must")]
+    requirement: Option<Requirement>,
+    #[schema(value_type = Option<String>, example = "rust")]
+    category: Option<String>,
+    /// Credential to fetch the private feed. it is stored encrypted and never returned
+    credential: Option<FeedCredentialRequest>,
+}
+
+/// Credential to fetch the feed which requires authentication
+#[derive(Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub(super) enum FeedCredentialRequest {
+    /// Http basic authentication
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    /// Bearer token
+    Bearer { token: String },
+}
+
+impl From<FeedCredentialRequest> for FeedCredential {
+    fn from(value: FeedCredentialRequest) -> Self {
+        match value {
+            FeedCredentialRequest::Basic { username, password } => {
+                FeedCredential::Basic { username, password }
+            }
+            FeedCredentialRequest::Bearer { token } => FeedCredential::Bearer { token },
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct SubscribeFeedResponse {
+    feed: Feed,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct UnsubscribeFeedParams {
+    /// Feed url to unsubscribe
+    #[param(value_type = String)]
+    url: FeedUrl,
+}
+
+/// Subscribed feed in the format of `synd export`
+#[derive(Serialize, Deserialize, ToSchema)]
+pub(super) struct ExportedFeed {
+    title: Option<String>,
+    #[schema(value_type = String)]
+    url: FeedUrl,
+    #[schema(value_type = Option<String>, example = "Must")]
+    requirement: Option<Requirement>,
+    #[schema(value_type = Option<String>, example = "rust")]
+    category: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub(super) struct Export {
+    feeds: Vec<ExportedFeed>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct ImportError {
+    #[schema(value_type = String)]
+    url: FeedUrl,
+    #[schema(inline)]
+    error: ErrorResponse,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct ImportResponse {
+    /// Number of subscribed feeds
+    imported: usize,
+    errors: Vec<ImportError>,
+}
+
+fn subscribe_feed_error(err: SubscribeFeedError) -> ApiError {
+    match err {
+        SubscribeFeedError::FetchFeed(err) => ApiError::from_fetch_feed_error(err),
+        err @ (SubscribeFeedError::CredentialNotEnabled | SubscribeFeedError::Credential(_)) => {
+            ApiError::invalid_input(format!("{err}"))
+        }
+    }
+}
+
+fn parse_category(category: Option<String>) -> Result<Option<Category<'static>>, ApiError> {
+    category
+        .map(|c| {
+            Category::new(c).map_err(|err| ApiError::invalid_input(format!("category: {err}")))
+        })
+        .transpose()
+}
+
+/// List subscribed feeds
+#[utoipa::path(
+    get,
+    path = "/api/v1/feeds",
+    params(Pagination),
+    responses(
+        (status = 200, body = FeedsResponse),
+        (status = 403, body = ErrorResponse),
+    ),
+)]
+pub(super) async fn list_feeds(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    Query(Pagination { after, first }): Query<Pagination>,
+) -> Result<Json<FeedsResponse>, ApiError> {
+    let first = first.unwrap_or(20).min(100);
+    let Output {
+        output: FetchSubscribedFeedsOutput { mut feeds },
+    } = cx
+        .runtime
+        .run::<FetchSubscribedFeeds, _, _>(
+            &principal,
+            FetchSubscribedFeedsInput {
+                after,
+                first: first + 1,
+            },
+        )
+        .await
+        .map_err(|err| ApiError::from_usecase(err, |err| ApiError::internal(format!("{err}"))))?;
+
+    let has_next_page = feeds.len() > first;
+    feeds.truncate(first);
+
+    let end_cursor = feeds.last().map(|feed| match feed {
+        Ok(feed) => feed.feed.meta().url().to_string(),
+        Err((url, _)) => url.to_string(),
+    });
+    let (feeds, errors): (Vec<_>, Vec<_>) = feeds.into_iter().partition(Result::is_ok);
+
+    Ok(Json(FeedsResponse {
+        feeds: feeds
+            .into_iter()
+            .filter_map(Result::ok)
+            .map(Feed::from)
+            .collect(),
+        errors: errors
+            .into_iter()
+            .filter_map(Result::err)
+            .map(|(url, err)| FetchFeedError {
+                url,
+                message: err.to_string(),
+            })
+            .collect(),
+        page_info: PageInfo {
+            has_next_page,
+            end_cursor,
+        },
+    }))
+}
+
+/// List subscribed latest entries order by published time
+#[utoipa::path(
+    get,
+    path = "/api/v1/entries",
+    params(EntriesParams),
+    responses(
+        (status = 200, body = EntriesResponse),
+        (status = 403, body = ErrorResponse),
+    ),
+)]
+pub(super) async fn list_entries(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    Query(EntriesParams {
+        after,
+        first,
+        deduplicate,
+    }): Query<EntriesParams>,
+) -> Result<Json<EntriesResponse>, ApiError> {
+    let first = first.unwrap_or(20).min(200);
+    let Output {
+        output:
+            FetchEntriesOutput {
+                entries,
+                feeds,
+                mut duplicates,
+            },
+    } = cx
+        .runtime
+        .run::<FetchEntries, _, _>(
+            &principal,
+            FetchEntriesInput {
+                after: after.map(Into::into),
+                first: first + 1,
+                filter: EntryFilter::default(),
+                deduplicate: deduplicate.unwrap_or(false),
+            },
+        )
+        .await
+        .map_err(|err| ApiError::from_usecase(err, |err| ApiError::internal(format!("{err}"))))?;
+
+    let has_next_page = entries.len() > first;
+    let entries = entries
+        .into_iter()
+        .take(first)
+        .map(|(entry, feed_url)| {
+            let feed = feeds.get(&feed_url);
+            let feed_type = feed.map_or(types::FeedType::Atom, |feed| feed.feed.r#type());
+            Entry {
+                id: entry.id().to_string(),
+                title: entry.title().map(ToOwned::to_owned),
+                summary: entry.summary().map(ToOwned::to_owned),
+                summary_html: entry.summary_html(),
+                summary_text: entry.summary_text(None),
+                website_url: entry.website_url(feed_type).map(ToOwned::to_owned),
+                enclosures: entry
+                    .enclosures()
+                    .into_iter()
+                    .map(Enclosure::from)
+                    .collect(),
+                published: entry.published(),
+                updated: entry.updated(),
+                feed_title: feed.and_then(|feed| feed.feed.title().map(ToOwned::to_owned)),
+                duplicate_feed_urls: duplicates.remove(&entry.id()).unwrap_or_default(),
+                requirement: feed.and_then(|feed| feed.requirement),
+                category: feed.and_then(|feed| feed.category.clone()),
+                feed_url,
+            }
+        })
+        .collect::<Vec<_>>();
+    let end_cursor = entries.last().map(|entry| entry.id.clone());
+
+    Ok(Json(EntriesResponse {
+        entries,
+        page_info: PageInfo {
+            has_next_page,
+            end_cursor,
+        },
+    }))
+}
+
+/// Subscribe feed
+#[utoipa::path(
+    post,
+    path = "/api/v1/subscriptions",
+    request_body = SubscribeFeedRequest,
+    responses(
+        (status = 201, body = SubscribeFeedResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 502, body = ErrorResponse),
+    ),
+)]
+pub(super) async fn subscribe_feed(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    Json(SubscribeFeedRequest {
+        url,
+        requirement,
+        category,
+        credential,
+    }): Json<SubscribeFeedRequest>,
+) -> Result<Response, ApiError> {
+    let input = SubscribeFeedInput {
+        url,
+        requirement,
+        category: parse_category(category)?,
+        credential: credential.map(FeedCredential::from),
+    };
+    let Output {
+        output: SubscribeFeedOutput { feed },
+    } = cx
+        .runtime
+        .run::<SubscribeFeed, _, _>(&principal, input)
+        .await
+        .map_err(|err| ApiError::from_usecase(err, subscribe_feed_error))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SubscribeFeedResponse {
+            feed: Feed::from(feed),
+        }),
+    )
+        .into_response())
+}
+
+/// Unsubscribe feed
+/// If given feed is not subscribed, this operation will succeed
+#[utoipa::path(
+    delete,
+    path = "/api/v1/subscriptions",
+    params(UnsubscribeFeedParams),
+    responses(
+        (status = 204),
+        (status = 403, body = ErrorResponse),
+    ),
+)]
+pub(super) async fn unsubscribe_feed(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    Query(UnsubscribeFeedParams { url }): Query<UnsubscribeFeedParams>,
+) -> Result<StatusCode, ApiError> {
+    cx.runtime
+        .run::<UnsubscribeFeed, _, _>(&principal, UnsubscribeFeedInput { url })
+        .await
+        .map_err(|err| ApiError::from_usecase(err, |err| ApiError::internal(format!("{err}"))))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Export subscribed feeds
+#[utoipa::path(
+    get,
+    path = "/api/v1/export",
+    responses(
+        (status = 200, body = Export),
+        (status = 403, body = ErrorResponse),
+    ),
+)]
+pub(super) async fn export(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+) -> Result<Json<Export>, ApiError> {
+    const PAGE_SIZE: usize = 100;
+
+    let mut exported = Vec::new();
+    let mut after = None;
+    loop {
+        let Output {
+            output: FetchSubscribedFeedsOutput { mut feeds },
+        } = cx
+            .runtime
+            .run::<FetchSubscribedFeeds, _, _>(
+                &principal,
+                FetchSubscribedFeedsInput {
+                    after: after.take(),
+                    first: PAGE_SIZE + 1,
+                },
+            )
+            .await
+            .map_err(|err| {
+                ApiError::from_usecase(err, |err| ApiError::internal(format!("{err}")))
+            })?;
+
+        let has_next_page = feeds.len() > PAGE_SIZE;
+        feeds.truncate(PAGE_SIZE);
+
+        exported.extend(feeds.into_iter().map(|feed| match feed {
+            Ok(feed) => ExportedFeed {
+                title: feed.feed.meta().title().map(ToOwned::to_owned),
+                url: feed.feed.meta().url().clone(),
+                requirement: feed.requirement,
+                category: feed.category.map(|c| c.into_inner().into_owned()),
+            },
+            // Export the url so that the subscription is not lost
+            Err((url, _)) => ExportedFeed {
+                title: None,
+                url,
+                requirement: None,
+                category: None,
+            },
+        }));
+
+        if !has_next_page {
+            break;
+        }
+        after = exported.last().map(|feed| feed.url.to_string());
+    }
+
+    Ok(Json(Export { feeds: exported }))
+}
+
+/// Max number of feeds imported by a request
+pub(super) const MAX_IMPORT_FEEDS: usize = 100;
+
+/// Subscribe feeds in the format of export
+///
+/// Each feed consumes the expensive rate limit. Feeds exceeding the limit are reported as `rate_limited` errors.
+/// At most 100 feeds can be imported at once.
+#[utoipa::path(
+    post,
+    path = "/api/v1/import",
+    request_body = Export,
+    responses(
+        (status = 200, body = ImportResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    ),
+)]
+pub(super) async fn import(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    Extension(budget): Extension<ExpensiveBudget>,
+    Json(Export { feeds }): Json<Export>,
+) -> Result<Json<ImportResponse>, ApiError> {
+    if feeds.len() > MAX_IMPORT_FEEDS {
+        return Err(ApiError::invalid_input(format!(
+            "at most {MAX_IMPORT_FEEDS} feeds can be imported at once"
+        )));
+    }
+
+    let mut imported = 0;
+    let mut errors = Vec::new();
+
+    let mut feeds = feeds.into_iter();
+    while let Some(ExportedFeed {
+        url,
+        requirement,
+        category,
+        ..
+    }) = feeds.next()
+    {
+        let category = match parse_category(category) {
+            Ok(category) => category,
+            Err(err) => {
+                errors.push(ImportError {
+                    url,
+                    error: err.body,
+                });
+                continue;
+            }
+        };
+        if let Err(retry_after) = budget.acquire().await {
+            // Report the rest so that the client can import them later
+            errors.extend(
+                std::iter::once(url)
+                    .chain(feeds.map(|feed| feed.url))
+                    .map(|url| ImportError {
+                        url,
+                        error: ApiError::rate_limited(retry_after).body,
+                    }),
+            );
+            break;
+        }
+        let input = SubscribeFeedInput {
+            url: url.clone(),
+            requirement,
+            category,
+            // Exported feeds never contain credentials
+            credential: None,
+        };
+        match cx
+            .runtime
+            .run::<SubscribeFeed, _, _>(&principal, input)
+            .await
+        {
+            Ok(_) => imported += 1,
+            Err(err) => {
+                let err = ApiError::from_usecase(err, subscribe_feed_error);
+                // Abort as subsequent feeds are also not permitted
+                if err.status == StatusCode::FORBIDDEN {
+                    return Err(err);
+                }
+                errors.push(ImportError {
+                    url,
+                    error: err.body,
+                });
+            }
+        }
+    }
+
+    Ok(Json(ImportResponse { imported, errors }))
+}
+
+#[cfg(test)]
+mod tests {
+    use synd_feed::feed::{
+        cache::CacheLayer,
+        service::{FetchFeed, FetchFeedError, FetchFeedResult},
+    };
+    use tokio_metrics::TaskMonitor;
+
+    use crate::{
+        config, gql,
+        principal::User,
+        repository::{SubscriptionRepository, memory::MemoryRepository, types::FeedSubscription},
+        serve::{
+            GraphQLOptions,
+            layer::rate_limit::{Quota, RateLimitLayer},
+        },
+        usecase::{MakeUsecase, Runtime, authorize::Authorizer},
+    };
+
+    use std::fmt::Write as _;
+
+    use crate::rest::ErrorCode;
+
+    use super::*;
+
+    /// Serve feeds which have an entry per day. urls containing `unavailable` fail
+    #[derive(Clone)]
+    struct StubFeeds;
+
+    #[async_trait::async_trait]
+    impl FetchFeed for StubFeeds {
+        async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<types::Feed> {
+            if url.as_str().contains("unavailable") {
+                return Err(FetchFeedError::XmlFormat("unavailable".into()));
+            }
+            let entries = (1..=3).fold(String::new(), |mut entries, day| {
+                write!(
+                    entries,
+                    "<entry><id>{url}{day}</id><title>{day}</title><published>2024-01-0{day}T00:00:00Z</published><updated>2024-01-0{day}T00:00:00Z</updated></entry>"
+                )
+                .unwrap();
+                entries
+            });
+            let xml = format!(
+                r#"<?xml version="1.0" encoding="utf-8"?><feed xmlns="http://www.w3.org/2005/Atom"><id>{url}</id><title>{url}</title><updated>2024-01-03T00:00:00Z</updated>{entries}</feed>"#
+            );
+            let feed = feed_rs::parser::parse(xml.as_bytes()).unwrap();
+            Ok(types::Feed::from((url, feed)))
+        }
+
+        async fn fetch_private_feed(
+            &self,
+            url: FeedUrl,
+            _credential: &FeedCredential,
+        ) -> FetchFeedResult<types::Feed> {
+            self.fetch_feed(url).await
+        }
+    }
+
+    fn context(repo: &Arc<MemoryRepository>) -> Context {
+        let cache = Arc::new(CacheLayer::new(StubFeeds));
+        let make = MakeUsecase {
+            subscription_repo: repo.clone(),
+            user_repo: repo.clone(),
+            fetch_feed: cache.clone(),
+            feed_cache: cache,
+            credential_cipher: None,
+        };
+        let options = GraphQLOptions {
+            depth_limit: config::serve::DEFAULT_GRAPHQL_DEPTH_LIMIT,
+            complexity_limit: config::serve::DEFAULT_GRAPHQL_COMPLEXITY_LIMIT,
+            persisted_query_cache_size: 1,
+        };
+        Context {
+            gql_monitor: TaskMonitor::new(),
+            schema: gql::schema_builder(&options).finish(),
+            runtime: Arc::new(Runtime::new(make, Authorizer::new())),
+        }
+    }
+
+    fn principal() -> Principal {
+        Principal::User(User::new("user-a", "a@example.com"))
+    }
+
+    fn url(path: &str) -> FeedUrl {
+        FeedUrl::parse(&format!("https://example.com/{path}")).unwrap()
+    }
+
+    fn budget(per_minute: u32) -> ExpensiveBudget {
+        let quota = Quota { per_minute };
+        ExpensiveBudget::new(RateLimitLayer::new(quota, quota, 1024), "user-a")
+    }
+
+    fn exported(path: &str, category: Option<&str>) -> ExportedFeed {
+        ExportedFeed {
+            title: None,
+            url: url(path),
+            requirement: None,
+            category: category.map(ToOwned::to_owned),
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribe_then_list_feeds() {
+        let repo = Arc::new(MemoryRepository::default());
+        let cx = context(&repo);
+
+        for path in ["a", "b", "c"] {
+            let response = subscribe_feed(
+                Extension(cx.clone()),
+                Extension(principal()),
+                Json(SubscribeFeedRequest {
+                    url: url(path),
+                    requirement: Some(Requirement::Must),
+                    category: Some("rust".into()),
+                    credential: None,
+                }),
+            )
+            .await
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+        }
+
+        let err = subscribe_feed(
+            Extension(cx.clone()),
+            Extension(principal()),
+            Json(SubscribeFeedRequest {
+                url: url("a"),
+                requirement: None,
+                category: Some(String::new()),
+                credential: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let list = |after: Option<String>| {
+            list_feeds(
+                Extension(cx.clone()),
+                Extension(principal()),
+                Query(Pagination {
+                    after,
+                    first: Some(2),
+                }),
+            )
+        };
+
+        let Json(first) = list(None).await.unwrap();
+        assert_eq!(
+            first
+                .feeds
+                .iter()
+                .map(|f| f.url.clone())
+                .collect::<Vec<_>>(),
+            vec![url("a"), url("b")]
+        );
+        assert_eq!(first.feeds[0].requirement, Some(Requirement::Must));
+        assert!(first.page_info.has_next_page);
+        assert_eq!(first.page_info.end_cursor, Some(url("b").to_string()));
+
+        let Json(second) = list(first.page_info.end_cursor).await.unwrap();
+        assert_eq!(
+            second
+                .feeds
+                .iter()
+                .map(|f| f.url.clone())
+                .collect::<Vec<_>>(),
+            vec![url("c")]
+        );
+        assert!(!second.page_info.has_next_page);
+    }
+
+    #[tokio::test]
+    async fn list_feeds_with_errors() {
+        let repo = Arc::new(MemoryRepository::default());
+        let cx = context(&repo);
+        for path in ["a", "unavailable"] {
+            repo.put_feed_subscription(FeedSubscription {
+                user_id: "user-a".into(),
+                url: url(path),
+                requirement: None,
+                category: None,
+                credential: None,
+            })
+            .await
+            .unwrap();
+        }
+
+        let Json(response) = list_feeds(
+            Extension(cx),
+            Extension(principal()),
+            Query(Pagination {
+                after: None,
+                first: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.feeds.len(), 1);
+        assert_eq!(response.errors[0].url, url("unavailable"));
+        // Failed feeds are also a cursor
+        assert_eq!(
+            response.page_info.end_cursor,
+            Some(url("unavailable").to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn list_entries_with_cursor() {
+        let repo = Arc::new(MemoryRepository::default());
+        let cx = context(&repo);
+        repo.put_feed_subscription(FeedSubscription {
+            user_id: "user-a".into(),
+            url: url("a"),
+            requirement: None,
+            category: None,
+            credential: None,
+        })
+        .await
+        .unwrap();
+
+        let list = |after: Option<String>| {
+            list_entries(
+                Extension(cx.clone()),
+                Extension(principal()),
+                Query(EntriesParams {
+                    after,
+                    first: Some(2),
+                    deduplicate: None,
+                }),
+            )
+        };
+
+        let Json(first) = list(None).await.unwrap();
+        // Latest first
+        assert_eq!(
+            first
+                .entries
+                .iter()
+                .map(|e| e.title.clone().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["3", "2"]
+        );
+        assert!(first.page_info.has_next_page);
+        assert_eq!(
+            first.page_info.end_cursor.as_ref(),
+            Some(&first.entries[1].id)
+        );
+
+        let Json(second) = list(first.page_info.end_cursor).await.unwrap();
+        assert_eq!(second.entries.len(), 1);
+        assert_eq!(second.entries[0].title.as_deref(), Some("1"));
+        assert!(!second.page_info.has_next_page);
+    }
+
+    #[tokio::test]
+    async fn import_charge_per_feed() {
+        let repo = Arc::new(MemoryRepository::default());
+        let cx = context(&repo);
+
+        let Json(response) = import(
+            Extension(cx.clone()),
+            Extension(principal()),
+            Extension(budget(2)),
+            Json(Export {
+                feeds: vec![
+                    exported("a", Some("rust")),
+                    // Invalid input does not consume the budget
+                    exported("b", Some("")),
+                    exported("c", None),
+                    exported("d", None),
+                    exported("e", None),
+                ],
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.imported, 2);
+        assert_eq!(
+            response
+                .errors
+                .iter()
+                .map(|e| (e.url.clone(), e.error.code))
+                .collect::<Vec<_>>(),
+            vec![
+                (url("b"), ErrorCode::InvalidInput),
+                (url("d"), ErrorCode::RateLimited),
+                (url("e"), ErrorCode::RateLimited),
+            ]
+        );
+        assert_eq!(
+            repo.fetch_subscribed_feeds("user-a").await.unwrap().urls,
+            vec![url("a"), url("c")]
+        );
+    }
+
+    #[tokio::test]
+    async fn import_too_many_feeds() {
+        let repo = Arc::new(MemoryRepository::default());
+        let feeds = (0..=MAX_IMPORT_FEEDS)
+            .map(|i| exported(&i.to_string(), None))
+            .collect();
+
+        let err = import(
+            Extension(context(&repo)),
+            Extension(principal()),
+            Extension(budget(1000)),
+            Json(Export { feeds }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(
+            repo.fetch_subscribed_feeds("user-a")
+                .await
+                .unwrap()
+                .urls
+                .is_empty()
+        );
+    }
+}
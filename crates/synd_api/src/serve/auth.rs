@@ -1,13 +1,14 @@
-use std::time::Duration;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use futures_util::future::BoxFuture;
 use moka::future::Cache;
-use synd_auth::jwt::google::JwtService as GoogleJwtService;
+use synd_auth::jwt::{google::JwtService as GoogleJwtService, oidc::JwtService as OidcJwtService};
 use tracing::warn;
 
 use crate::{
     client::github::GithubClient,
-    principal::{Principal, User},
+    principal::{Account, Identity, IdentityProvider, Principal, Role, User},
+    repository::{RepositoryError, SubscriptionRepository, UserRepository, types::UserRecord},
     serve::layer::authenticate::Authenticate,
 };
 
@@ -15,11 +16,18 @@ use crate::{
 pub struct Authenticator {
     github: GithubClient,
     google: GoogleJwtService,
+    oidc: Option<OidcJwtService>,
+    users: Arc<dyn UserRepository>,
+    subscriptions: Arc<dyn SubscriptionRepository>,
+    roles: Arc<HashMap<Account, Role>>,
     cache: Cache<String, Principal>,
 }
 
 impl Authenticator {
-    pub fn new() -> anyhow::Result<Self> {
+    pub fn new(
+        users: Arc<dyn UserRepository>,
+        subscriptions: Arc<dyn SubscriptionRepository>,
+    ) -> anyhow::Result<Self> {
         let cache = Cache::builder()
             .max_capacity(1024 * 1024)
             .time_to_live(Duration::from_secs(60 * 60))
@@ -28,6 +36,10 @@ impl Authenticator {
         Ok(Self {
             github: GithubClient::new()?,
             google: GoogleJwtService::default(),
+            oidc: None,
+            users,
+            subscriptions,
+            roles: Arc::default(),
             cache,
         })
     }
@@ -42,6 +54,24 @@ impl Authenticator {
         Self { google, ..self }
     }
 
+    #[must_use]
+    pub fn with_oidc_jwt(self, oidc: OidcJwtService) -> Self {
+        Self {
+            oidc: Some(oidc),
+            ..self
+        }
+    }
+
+    /// Users linked to given accounts are granted the role instead of the persisted one.
+    /// If an account is given more than once, the last role is used
+    #[must_use]
+    pub fn with_roles(self, roles: impl IntoIterator<Item = (Account, Role)>) -> Self {
+        Self {
+            roles: Arc::new(roles.into_iter().collect()),
+            ..self
+        }
+    }
+
     /// Authenticate from given token
     #[tracing::instrument(skip_all)]
     pub async fn authenticate<S>(&self, token: S) -> Result<Principal, ()>
@@ -49,22 +79,55 @@ impl Authenticator {
         S: AsRef<str>,
     {
         let token = token.as_ref();
+        if let Some(principal) = self.cache.get(token).await {
+            tracing::debug!("Principal cache hit");
+            return Ok(principal);
+        }
+
+        let identity = self.verify_identity(token).await?;
+        match self.resolve_user(identity).await {
+            Ok(user) => {
+                let principal = Principal::User(user);
+
+                self.cache.insert(token.to_owned(), principal.clone()).await;
+
+                Ok(principal)
+            }
+            Err(err) => {
+                warn!("Failed to resolve user: {err}");
+                Err(())
+            }
+        }
+    }
+
+    /// Authenticate from the token issued for the aggregated feed
+    /// The principal is only allowed to read
+    #[tracing::instrument(skip_all)]
+    pub async fn authenticate_feed_token(&self, token: &str) -> Result<Principal, ()> {
+        // Not cached so that reissuing the token revokes it immediately
+        match self.users.fetch_user_by_feed_token(token).await {
+            Ok(Some(record)) => Ok(Principal::User(
+                User::new(record.id, record.email).with_role(Role::ReadOnly),
+            )),
+            Ok(None) => Err(()),
+            Err(err) => {
+                warn!("Failed to resolve user by feed token: {err}");
+                Err(())
+            }
+        }
+    }
+
+    /// Verify given token with the authentication provider
+    pub async fn verify_identity(&self, token: &str) -> Result<Identity, ()> {
         let mut split = token.splitn(2, ' ');
         match (split.next(), split.next()) {
             (Some("github"), Some(access_token)) => {
-                if let Some(principal) = self.cache.get(token).await {
-                    tracing::debug!("Principal cache hit");
-                    return Ok(principal);
-                }
-
                 match self.github.authenticate(access_token).await {
-                    Ok(email) => {
-                        let principal = Principal::User(User::from_email(email));
-
-                        self.cache.insert(token.to_owned(), principal.clone()).await;
-
-                        Ok(principal)
-                    }
+                    Ok(viewer) => Ok(Identity {
+                        provider: IdentityProvider::Github,
+                        subject: viewer.id,
+                        email: viewer.email,
+                    }),
                     Err(err) => {
                         warn!("Failed to authenticate github: {err}");
                         Err(())
@@ -72,24 +135,17 @@ impl Authenticator {
                 }
             }
             (Some("google"), Some(id_token)) => {
-                if let Some(principal) = self.cache.get(id_token).await {
-                    tracing::debug!("Principal cache hit");
-                    return Ok(principal);
-                }
-
                 match self.google.decode_id_token(id_token).await {
                     Ok(claims) => {
                         if !claims.email_verified {
                             warn!("Google jwt claims email is not verified");
                             return Err(());
                         }
-                        let principal = Principal::User(User::from_email(claims.email));
-
-                        self.cache
-                            .insert(id_token.to_owned(), principal.clone())
-                            .await;
-
-                        Ok(principal)
+                        Ok(Identity {
+                            provider: IdentityProvider::Google,
+                            subject: claims.sub,
+                            email: claims.email,
+                        })
                     }
                     Err(err) => {
                         // If a lot of intentional invalid id tokens are sent
@@ -101,9 +157,101 @@ impl Authenticator {
                     }
                 }
             }
+            (Some("oidc"), Some(id_token)) => {
+                let Some(oidc) = self.oidc.as_ref() else {
+                    warn!("Oidc authentication is not enabled");
+                    return Err(());
+                };
+
+                match oidc.decode_id_token(id_token).await {
+                    Ok(claims) => {
+                        let Some(email) = claims.verified_email().map(str::to_owned) else {
+                            warn!("Oidc jwt claims does not contain verified email");
+                            return Err(());
+                        };
+                        Ok(Identity {
+                            provider: IdentityProvider::Oidc,
+                            subject: claims.sub,
+                            email,
+                        })
+                    }
+                    Err(err) => {
+                        warn!("Failed to authenticate oidc: {err}");
+                        Err(())
+                    }
+                }
+            }
             _ => Err(()),
         }
     }
+
+    /// Resolve the user linked to given identity, creating it on first sign in
+    async fn resolve_user(&self, identity: Identity) -> Result<User, RepositoryError> {
+        let record = if let Some(record) = self.users.fetch_user_by_identity(&identity).await? {
+            // Backfill the user stats
+            if !record.counted {
+                self.users.put_user(record.clone()).await?;
+            }
+            record
+        } else {
+            // Identities of other providers are not linked implicitly even if the email is the same.
+            // They are linked explicitly by the link account mutation.
+            let record = UserRecord {
+                id: self.new_user_id(&identity.email).await?,
+                email: identity.email.clone(),
+                identities: vec![identity.clone()],
+                role: Role::default(),
+                feed_token: None,
+                counted: false,
+            };
+            self.users.put_user(record.clone()).await?;
+            // Subject is logged so that operators can assign roles to the account
+            tracing::info!(
+                enduser.id = record.id,
+                provider = %identity.provider,
+                subject = identity.subject,
+                "Create user"
+            );
+            record
+        };
+
+        let role = self.resolve_role(&record);
+
+        Ok(User::new(record.id, identity.email).with_role(role))
+    }
+
+    /// Admin takes precedence over read only when the linked accounts are given different roles
+    fn resolve_role(&self, record: &UserRecord) -> Role {
+        let assigned = record
+            .identities
+            .iter()
+            .filter_map(|identity| self.roles.get(&identity.account()))
+            .collect::<Vec<_>>();
+        if assigned.contains(&&Role::Admin) {
+            Role::Admin
+        } else if assigned.contains(&&Role::ReadOnly) {
+            Role::ReadOnly
+        } else {
+            record.role
+        }
+    }
+
+    /// Users who subscribed before user records were persisted are identified by the email derived id.
+    /// The first user signing in with the email takes over the id to keep the subscriptions.
+    async fn new_user_id(&self, email: &str) -> Result<String, RepositoryError> {
+        let legacy_id = User::legacy_id(email);
+        if self.users.fetch_user(&legacy_id).await?.is_none()
+            && !self
+                .subscriptions
+                .fetch_subscribed_feeds(&legacy_id)
+                .await?
+                .urls
+                .is_empty()
+        {
+            return Ok(legacy_id);
+        }
+        Ok(User::generate_id())
+    }
 }
 
 impl Authenticate for Authenticator {
@@ -119,3 +267,116 @@ impl Authenticate for Authenticator {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use synd_feed::types::FeedUrl;
+
+    use crate::repository::{memory::MemoryRepository, types::FeedSubscription};
+
+    use super::*;
+
+    fn identity(provider: IdentityProvider, subject: &str, email: &str) -> Identity {
+        Identity {
+            provider,
+            subject: subject.to_owned(),
+            email: email.to_owned(),
+        }
+    }
+
+    fn authenticator(repo: &Arc<MemoryRepository>) -> Authenticator {
+        Authenticator::new(repo.clone(), repo.clone()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn resolve_user_by_provider_subject() {
+        let repo = Arc::new(MemoryRepository::default());
+        let auth = authenticator(&repo);
+
+        let github = identity(IdentityProvider::Github, "gh-1", "a@example.com");
+        let user = auth.resolve_user(github.clone()).await.unwrap();
+        assert_eq!(user.id().len(), 32);
+        assert_ne!(user.id(), User::legacy_id("a@example.com"));
+
+        // Email change of the account does not change the user
+        let renamed = identity(IdentityProvider::Github, "gh-1", "b@example.com");
+        let resolved = auth.resolve_user(renamed).await.unwrap();
+        assert_eq!(resolved.id(), user.id());
+        assert_eq!(resolved.email(), "b@example.com");
+
+        // Other provider sharing the email is not linked implicitly
+        let google = identity(IdentityProvider::Google, "g-1", "a@example.com");
+        let other = auth.resolve_user(google).await.unwrap();
+        assert_ne!(other.id(), user.id());
+        assert_eq!(repo.users().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn resolve_role_by_account() {
+        let repo = Arc::new(MemoryRepository::default());
+        let github = identity(IdentityProvider::Github, "gh-1", "a@example.com");
+        let google = identity(IdentityProvider::Google, "g-1", "a@example.com");
+        let auth = authenticator(&repo).with_roles([
+            (github.account(), Role::ReadOnly),
+            (google.account(), Role::Admin),
+        ]);
+
+        assert_eq!(
+            auth.resolve_user(github.clone()).await.unwrap().role(),
+            Role::ReadOnly
+        );
+        // Email is not used to grant the role
+        let other = identity(IdentityProvider::Oidc, "o-1", "a@example.com");
+        assert_eq!(auth.resolve_user(other).await.unwrap().role(), Role::Member);
+
+        // Roles of all the linked accounts are considered
+        let mut record = repo.fetch_user_by_identity(&github).await.unwrap().unwrap();
+        record.identities.push(google);
+        repo.put_user(record).await.unwrap();
+        assert_eq!(auth.resolve_user(github).await.unwrap().role(), Role::Admin);
+    }
+
+    #[tokio::test]
+    async fn resolve_user_backfill_user_stats() {
+        let github = identity(IdentityProvider::Github, "gh-1", "a@example.com");
+        let repo = Arc::new(MemoryRepository::with_users([UserRecord {
+            id: "user-a".into(),
+            email: github.email.clone(),
+            identities: vec![github.clone()],
+            role: Role::default(),
+            feed_token: None,
+            counted: false,
+        }]));
+        let auth = authenticator(&repo);
+        assert_eq!(repo.count_users().await.unwrap(), 0);
+
+        auth.resolve_user(github.clone()).await.unwrap();
+        auth.resolve_user(github).await.unwrap();
+        assert_eq!(repo.count_users().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn resolve_user_take_over_legacy_id() {
+        let repo = Arc::new(MemoryRepository::default());
+        let auth = authenticator(&repo);
+        let legacy_id = User::legacy_id("a@example.com");
+        repo.put_feed_subscription(FeedSubscription {
+            user_id: legacy_id.clone(),
+            url: FeedUrl::parse("https://example.com/feed.xml").unwrap(),
+            requirement: None,
+            category: None,
+            credential: None,
+        })
+        .await
+        .unwrap();
+
+        let github = identity(IdentityProvider::Github, "gh-1", "a@example.com");
+        let user = auth.resolve_user(github).await.unwrap();
+        assert_eq!(user.id(), legacy_id);
+
+        // The legacy id is taken over only once
+        let google = identity(IdentityProvider::Google, "g-1", "a@example.com");
+        let other = auth.resolve_user(google).await.unwrap();
+        assert_ne!(other.id(), legacy_id);
+    }
+}
@@ -1,4 +1,5 @@
 //! Module for tower layer
 pub(crate) mod authenticate;
+pub(crate) mod rate_limit;
 pub(crate) mod request_metrics;
 pub(crate) mod trace;
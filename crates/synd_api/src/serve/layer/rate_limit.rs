@@ -0,0 +1,556 @@
+use std::{
+    convert::Infallible,
+    fmt::Write as _,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use async_graphql::parser::{
+    parse_query,
+    types::{ExecutableDocument, OperationType, Selection, SelectionSet},
+};
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{HeaderValue, Method, StatusCode, header::RETRY_AFTER, request::Parts},
+    response::{IntoResponse, Response},
+};
+use futures_util::Future;
+use http_body_util::LengthLimitError;
+use moka::future::Cache;
+use ring::digest;
+use serde::Deserialize;
+use synd_o11y::metric;
+use tower::{Layer, Service};
+
+use crate::{principal::Principal, rest};
+
+/// Mutation fields which trigger outbound requests
+const EXPENSIVE_FIELDS: &[&str] = &["subscribeFeed", "refreshFeed"];
+
+/// Number of requests a principal is allowed per minute
+#[derive(Debug, Clone, Copy)]
+pub struct Quota {
+    pub per_minute: u32,
+}
+
+impl Quota {
+    fn capacity(self) -> f64 {
+        f64::from(self.per_minute)
+    }
+
+    fn refill_per_sec(self) -> f64 {
+        f64::from(self.per_minute) / 60.
+    }
+}
+
+/// Cost of the request which determines the budgets to consume
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cost {
+    Cheap,
+    Expensive,
+    /// Persisted query sent without the query which was not registered through this layer.
+    /// As it can not be classified, the client is asked to send the query
+    UnknownPersistedQuery,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Budget {
+    Default,
+    Expensive,
+}
+
+impl Budget {
+    fn as_str(self) -> &'static str {
+        match self {
+            Budget::Default => "default",
+            Budget::Expensive => "expensive",
+        }
+    }
+}
+
+/// Token bucket which is filled up to the quota per minute
+struct TokenBucket {
+    quota: Quota,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(quota: Quota, now: Instant) -> Self {
+        Self {
+            quota,
+            tokens: quota.capacity(),
+            refilled_at: now,
+        }
+    }
+
+    /// Consume a token. if there is no token, return the duration until a token is available
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.quota.refill_per_sec()).min(self.quota.capacity());
+        self.refilled_at = now;
+
+        if self.tokens >= 1. {
+            self.tokens -= 1.;
+            Ok(())
+        } else if self.quota.refill_per_sec() > 0. {
+            Err(Duration::from_secs_f64(
+                (1. - self.tokens) / self.quota.refill_per_sec(),
+            ))
+        } else {
+            Err(Duration::from_secs(60))
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    default: Quota,
+    expensive: Quota,
+    /// Max bytes of the request body read to classify the request.
+    /// Routes which have their own limit are read up to it
+    body_limit: usize,
+    buckets: Cache<(String, Budget), Arc<Mutex<TokenBucket>>>,
+    // Whether the persisted query is expensive keyed by the sha256 hash of the query
+    // Persisted queries are sent without the query once registered
+    persisted_queries: Cache<String, bool>,
+}
+
+impl RateLimitLayer {
+    pub fn new(default: Quota, expensive: Quota, body_limit: usize) -> Self {
+        let buckets = Cache::builder()
+            .max_capacity(100_000)
+            // Buckets which are not used for a while are full, so we can discard them
+            .time_to_idle(Duration::from_secs(60 * 10))
+            .build();
+
+        Self {
+            default,
+            expensive,
+            body_limit,
+            buckets,
+            persisted_queries: Cache::new(10_000),
+        }
+    }
+
+    /// Classify whether the request triggers outbound requests
+    /// Import is charged per feed by the handler with `ExpensiveBudget`
+    async fn cost(&self, parts: &Parts, body: &[u8]) -> Cost {
+        if parts.method == Method::POST && parts.uri.path() == rest::SUBSCRIPTIONS_PATH {
+            return Cost::Expensive;
+        }
+        self.graphql_cost(body).await
+    }
+
+    /// Classify whether the graphql request contains a mutation field which triggers outbound requests
+    async fn graphql_cost(&self, body: &[u8]) -> Cost {
+        let Ok(GraphQLBody { query, extensions }) = serde_json::from_slice::<GraphQLBody>(body)
+        else {
+            return Cost::Cheap;
+        };
+        let hash = extensions
+            .and_then(|ext| ext.persisted_query)
+            .map(|persisted| persisted.sha256_hash);
+
+        match (query, hash) {
+            (Some(query), hash) => {
+                let expensive = is_expensive_query(&query);
+                // Register the query in the same way as the persisted query extension,
+                // which rejects the hash not matching the query
+                if let Some(hash) = hash.filter(|hash| *hash == sha256_hex(&query)) {
+                    self.persisted_queries.insert(hash, expensive).await;
+                }
+                if expensive {
+                    Cost::Expensive
+                } else {
+                    Cost::Cheap
+                }
+            }
+            (None, Some(hash)) => match self.persisted_queries.get(&hash).await {
+                Some(true) => Cost::Expensive,
+                Some(false) => Cost::Cheap,
+                None => Cost::UnknownPersistedQuery,
+            },
+            (None, None) => Cost::Cheap,
+        }
+    }
+
+    async fn acquire(&self, principal: &str, budget: Budget) -> Result<(), Duration> {
+        let quota = match budget {
+            Budget::Default => self.default,
+            Budget::Expensive => self.expensive,
+        };
+        let bucket = self
+            .buckets
+            .get_with((principal.to_owned(), budget), async move {
+                Arc::new(Mutex::new(TokenBucket::new(quota, Instant::now())))
+            })
+            .await;
+
+        bucket.lock().unwrap().try_acquire(Instant::now())
+    }
+}
+
+/// Expensive budget of the principal for the handlers which charge per outbound request
+#[derive(Clone)]
+pub(crate) struct ExpensiveBudget {
+    limiter: RateLimitLayer,
+    principal: String,
+}
+
+impl ExpensiveBudget {
+    pub(crate) fn new(limiter: RateLimitLayer, principal: impl Into<String>) -> Self {
+        Self {
+            limiter,
+            principal: principal.into(),
+        }
+    }
+
+    /// Consume a token. if there is no token, return the duration until a token is available
+    pub(crate) async fn acquire(&self) -> Result<(), Duration> {
+        let result = self
+            .limiter
+            .acquire(&self.principal, Budget::Expensive)
+            .await;
+        if result.is_err() {
+            let budget = Budget::Expensive.as_str();
+            metric!(monotonic_counter.rate_limit.rejected = 1, budget);
+        }
+        result
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: RateLimitLayer,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let mut this = self.clone();
+        Box::pin(async move {
+            // AuthenticateLayer guarantees the principal exists except for the aggregated feed,
+            // which is authenticated with the token in the path by the handler
+            let principal = match req.extensions().get::<Principal>() {
+                Some(principal) => principal.user_id().unwrap_or("?").to_owned(),
+                None => rest::feed_token(req.uri().path())
+                    .map_or_else(|| "?".to_owned(), |token| format!("feed:{token}")),
+            };
+
+            // Read the body to determine whether the operation is expensive
+            let (mut parts, body) = req.into_parts();
+            let body_limit = rest::body_limit(&parts, this.limiter.body_limit);
+            let body = match axum::body::to_bytes(body, body_limit).await {
+                Ok(body) => body,
+                Err(err) => {
+                    // The limit may be exceeded in the outer RequestBodyLimitLayer for chunked bodies
+                    let too_large =
+                        std::iter::successors(std::error::Error::source(&err), |err| err.source())
+                            .any(<dyn std::error::Error>::is::<LengthLimitError>);
+                    let status = if too_large {
+                        StatusCode::PAYLOAD_TOO_LARGE
+                    } else {
+                        StatusCode::BAD_REQUEST
+                    };
+                    return Ok(status.into_response());
+                }
+            };
+
+            let cost = this.limiter.cost(&parts, &body).await;
+            let mut budgets = vec![Budget::Default];
+            if cost == Cost::Expensive {
+                budgets.push(Budget::Expensive);
+            }
+
+            for budget in budgets {
+                if let Err(retry_after) = this.limiter.acquire(&principal, budget).await {
+                    let budget = budget.as_str();
+                    metric!(monotonic_counter.rate_limit.rejected = 1, budget);
+                    return Ok(too_many_requests(retry_after));
+                }
+            }
+
+            if cost == Cost::UnknownPersistedQuery {
+                return Ok(persisted_query_not_found());
+            }
+
+            parts
+                .extensions
+                .insert(ExpensiveBudget::new(this.limiter.clone(), principal));
+            let req = Request::from_parts(parts, Body::from(body));
+            this.inner.call(req).await
+        })
+    }
+}
+
+fn too_many_requests(retry_after: Duration) -> Response {
+    let mut response = StatusCode::TOO_MANY_REQUESTS.into_response();
+    // Retry-After only accepts seconds
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(secs.max(1)));
+    response
+}
+
+/// Same response as the persisted query extension so that the client retries with the query
+fn persisted_query_not_found() -> Response {
+    axum::Json(serde_json::json!({
+        "data": null,
+        "errors": [{ "message": "PersistedQueryNotFound" }],
+    }))
+    .into_response()
+}
+
+fn sha256_hex(query: &str) -> String {
+    digest::digest(&digest::SHA256, query.as_bytes())
+        .as_ref()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+            hex
+        })
+}
+
+#[derive(Deserialize)]
+struct GraphQLBody {
+    query: Option<String>,
+    extensions: Option<GraphQLExtensions>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLExtensions {
+    persisted_query: Option<PersistedQuery>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQuery {
+    sha256_hash: String,
+}
+
+fn is_expensive_query(query: &str) -> bool {
+    let Ok(doc) = parse_query(query) else {
+        return false;
+    };
+
+    doc.operations
+        .iter()
+        .filter(|(_, op)| op.node.ty == OperationType::Mutation)
+        .any(|(_, op)| contains_expensive_field(&doc, &op.node.selection_set.node, 0))
+}
+
+fn contains_expensive_field(doc: &ExecutableDocument, set: &SelectionSet, depth: usize) -> bool {
+    // Guard against recursive fragments. Too deep selections are regarded as expensive
+    if depth > 10 {
+        return true;
+    }
+    set.items.iter().any(|selection| match &selection.node {
+        Selection::Field(field) => {
+            EXPENSIVE_FIELDS.contains(&field.node.name.node.as_str())
+                || contains_expensive_field(doc, &field.node.selection_set.node, depth + 1)
+        }
+        Selection::InlineFragment(fragment) => {
+            contains_expensive_field(doc, &fragment.node.selection_set.node, depth + 1)
+        }
+        Selection::FragmentSpread(spread) => doc
+            .fragments
+            .get(&spread.node.fragment_name.node)
+            .is_some_and(|fragment| {
+                contains_expensive_field(doc, &fragment.node.selection_set.node, depth + 1)
+            }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(Quota { per_minute: 2 }, now);
+
+        assert!(bucket.try_acquire(now).is_ok());
+        assert!(bucket.try_acquire(now).is_ok());
+        let retry_after = bucket.try_acquire(now).unwrap_err();
+        assert_eq!(retry_after.as_secs(), 30);
+
+        assert!(bucket.try_acquire(now + Duration::from_secs(30)).is_ok());
+    }
+
+    #[test]
+    fn retry_after_header() {
+        let response = too_many_requests(Duration::from_millis(1500));
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "2");
+    }
+
+    #[test]
+    fn detect_expensive_query() {
+        assert!(is_expensive_query(
+            "mutation { subscribeFeed(input: {url: \"https://example.com\"}) { __typename } }"
+        ));
+        assert!(is_expensive_query(
+            "mutation { admin { ...Refresh } } fragment Refresh on AdminMutation { refreshFeed(input: {url: \"https://example.com\"}) { __typename } }"
+        ));
+        assert!(!is_expensive_query(
+            "mutation { unsubscribeFeed(input: {url: \"https://example.com\"}) { __typename } }"
+        ));
+        assert!(!is_expensive_query(
+            "mutation { updateFeeds(input: {feeds: [{url: \"https://example.com\"}]}) { __typename } }"
+        ));
+        assert!(!is_expensive_query(
+            "query { subscription { feeds { nodes { url } } } }"
+        ));
+    }
+
+    #[test]
+    fn too_deep_query_is_expensive() {
+        let query = format!("mutation {{ {}id{} }}", "a { ".repeat(12), " }".repeat(12));
+        assert!(is_expensive_query(&query));
+    }
+
+    fn limiter() -> RateLimitLayer {
+        RateLimitLayer::new(Quota { per_minute: 1 }, Quota { per_minute: 1 }, 1024)
+    }
+
+    #[tokio::test]
+    async fn classify_persisted_query() {
+        let limiter = limiter();
+        let query =
+            "mutation { subscribeFeed(input: {url: \"https://example.com\"}) { __typename } }";
+        let body = |query: Option<&str>, hash: &str| {
+            let extensions =
+                serde_json::json!({ "persistedQuery": { "version": 1, "sha256Hash": hash }});
+            serde_json::json!({ "query": query, "extensions": extensions }).to_string()
+        };
+        let hash = sha256_hex(query);
+
+        // Hash is unknown before the query is registered
+        let hash_only = body(None, &hash);
+        assert_eq!(
+            limiter.graphql_cost(hash_only.as_bytes()).await,
+            Cost::UnknownPersistedQuery
+        );
+
+        // Hash which does not match the query is not registered
+        let cheap =
+            "mutation { unsubscribeFeed(input: {url: \"https://example.com\"}) { __typename } }";
+        let spoofed = body(Some(cheap), &hash);
+        assert_eq!(limiter.graphql_cost(spoofed.as_bytes()).await, Cost::Cheap);
+        assert_eq!(
+            limiter.graphql_cost(hash_only.as_bytes()).await,
+            Cost::UnknownPersistedQuery
+        );
+
+        let register = body(Some(query), &hash);
+        assert_eq!(
+            limiter.graphql_cost(register.as_bytes()).await,
+            Cost::Expensive
+        );
+        assert_eq!(
+            limiter.graphql_cost(hash_only.as_bytes()).await,
+            Cost::Expensive
+        );
+
+        let cheap_hash_only = body(None, &sha256_hex(cheap));
+        limiter
+            .graphql_cost(body(Some(cheap), &sha256_hex(cheap)).as_bytes())
+            .await;
+        assert_eq!(
+            limiter.graphql_cost(cheap_hash_only.as_bytes()).await,
+            Cost::Cheap
+        );
+
+        assert_eq!(limiter.graphql_cost(b"not json").await, Cost::Cheap);
+    }
+
+    #[tokio::test]
+    async fn reject_request() {
+        use tower::ServiceExt as _;
+
+        let service = limiter().layer(tower::service_fn(|_: Request| async {
+            Ok::<_, Infallible>(StatusCode::OK.into_response())
+        }));
+        let request = |body: Vec<u8>| {
+            Request::post("/graphql")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = service
+            .clone()
+            .oneshot(request(vec![b' '; 2048]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let hash_only = serde_json::json!({
+            "extensions": { "persistedQuery": { "version": 1, "sha256Hash": "abc" }}
+        });
+        let response = service
+            .oneshot(request(hash_only.to_string().into_bytes()))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), 1024)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("PersistedQueryNotFound"));
+    }
+
+    #[tokio::test]
+    async fn limit_aggregated_feed_by_token() {
+        use tower::ServiceExt as _;
+
+        let service = limiter().layer(tower::service_fn(|_: Request| async {
+            Ok::<_, Infallible>(StatusCode::OK.into_response())
+        }));
+        let request = |token: &str| {
+            Request::get(rest::feed_path(token))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let status = |token| {
+            let service = service.clone();
+            async move { service.oneshot(request(token)).await.unwrap().status() }
+        };
+
+        assert_eq!(status("a").await, StatusCode::OK);
+        assert_eq!(status("a").await, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(status("b").await, StatusCode::OK);
+    }
+}
@@ -1,13 +1,13 @@
-use std::{net::IpAddr, time::Duration};
+use std::{net::IpAddr, path::PathBuf, sync::Arc, time::Duration};
 
 use axum::{
     BoxError, Extension, Router,
     error_handling::HandleErrorLayer,
-    http::{StatusCode, header::AUTHORIZATION},
+    http::{StatusCode, header::AUTHORIZATION, uri::Scheme},
     response::IntoResponse,
     routing::{get, post},
 };
-use tokio::net::TcpListener;
+use tokio::net::{TcpListener, UnixListener};
 use tokio_metrics::TaskMonitor;
 use tower::{ServiceBuilder, limit::ConcurrencyLimitLayer, timeout::TimeoutLayer};
 use tower_http::{
@@ -19,42 +19,90 @@ use crate::{
     config,
     dependency::Dependency,
     gql::{self, SyndSchema},
-    serve::layer::{authenticate, request_metrics::RequestMetricsLayer, trace},
+    rest,
+    serve::layer::{
+        authenticate,
+        rate_limit::{Quota, RateLimitLayer},
+        request_metrics::RequestMetricsLayer,
+        trace,
+    },
     shutdown::Shutdown,
+    usecase::Runtime,
 };
 
 pub mod auth;
 mod probe;
+pub mod tls;
 
 pub mod layer;
 
-pub struct BindOptions {
-    pub port: u16,
-    pub addr: IpAddr,
+#[derive(Debug, Clone)]
+pub enum BindOptions {
+    Tcp {
+        addr: IpAddr,
+        port: u16,
+    },
+    /// Unix domain socket for a local reverse proxy or sidecar
+    Unix {
+        path: PathBuf,
+    },
 }
 
 pub struct ServeOptions {
     pub timeout: Duration,
     pub body_limit_bytes: usize,
     pub concurrency_limit: usize,
+    pub rate_limit: Quota,
+    pub expensive_rate_limit: Quota,
+    pub graphql: GraphQLOptions,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GraphQLOptions {
+    /// Maximum depth of graphql query
+    pub depth_limit: usize,
+    /// Maximum complexity of graphql query
+    pub complexity_limit: usize,
+    /// Number of persisted queries to keep
+    pub persisted_query_cache_size: usize,
 }
 
 #[derive(Clone)]
 pub(crate) struct Context {
     pub gql_monitor: TaskMonitor,
     pub schema: SyndSchema,
+    pub runtime: Arc<Runtime>,
 }
 
-/// Bind tcp listener and serve.
+/// Bind listener and serve.
 pub async fn listen_and_serve(
     dep: Dependency,
     bind: BindOptions,
     shutdown: Shutdown,
 ) -> anyhow::Result<()> {
-    info!(addr = %bind.addr, port = bind.port, "Listening...");
-    let listener = TcpListener::bind((bind.addr, bind.port)).await?;
+    match bind {
+        BindOptions::Tcp { addr, port } => {
+            info!(%addr, port, tls = dep.tls_config.is_some(), "Listening...");
+            let listener = TcpListener::bind((addr, port)).await?;
+
+            serve(listener, dep, shutdown).await
+        }
+        BindOptions::Unix { path } => {
+            if dep.tls_config.is_some() {
+                anyhow::bail!("tls is not supported on unix domain socket, specify no-tls");
+            }
+            remove_socket(&path)?;
+            info!(path = %path.display(), "Listening...");
+            let listener = UnixListener::bind(&path)?;
 
-    serve(listener, dep, shutdown).await
+            let result = serve_unix(listener, dep, shutdown).await;
+            // Otherwise the next process fails to bind with EADDRINUSE
+            if let Err(err) = remove_socket(&path) {
+                tracing::warn!(path = %path.display(), "Failed to remove socket: {err}");
+            }
+            result
+        }
+    }
 }
 
 /// Start api server
@@ -63,6 +111,69 @@ pub async fn serve(
     dep: Dependency,
     shutdown: Shutdown,
 ) -> anyhow::Result<()> {
+    let tls_config = dep.tls_config.clone();
+    let service = router(dep, &shutdown).into_make_service();
+    let listener = listener.into_std()?;
+
+    tracing::info!("Serving...");
+
+    match tls_config {
+        Some(tls_config) => {
+            axum_server::from_tcp_rustls(listener, tls_config)
+                .handle(shutdown.into_handle())
+                .serve(service)
+                .await?;
+        }
+        None => {
+            axum_server::from_tcp(listener)
+                .handle(shutdown.into_handle())
+                .serve(service)
+                .await?;
+        }
+    }
+
+    tracing::info!("Shutdown complete");
+
+    Ok(())
+}
+
+/// Start api server on unix domain socket without tls
+async fn serve_unix(
+    listener: UnixListener,
+    dep: Dependency,
+    shutdown: Shutdown,
+) -> anyhow::Result<()> {
+    let ct = shutdown.cancellation_token();
+    let service = router(dep, &shutdown).into_make_service();
+
+    tracing::info!("Serving...");
+
+    axum::serve(listener, service)
+        .with_graceful_shutdown(async move { ct.cancelled().await })
+        .await?;
+
+    tracing::info!("Shutdown complete");
+
+    Ok(())
+}
+
+/// Remove the socket file on shutdown or the one left by the previous process
+fn remove_socket(path: &std::path::Path) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt as _;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        Ok(_) => Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        )),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Build the routes and spawn the monitors which stop on shutdown
+fn router(dep: Dependency, shutdown: &Shutdown) -> Router {
     let Dependency {
         authenticator,
         runtime,
@@ -72,13 +183,21 @@ pub async fn serve(
                 timeout: request_timeout,
                 body_limit_bytes: request_body_limit_bytes,
                 concurrency_limit,
+                rate_limit,
+                expensive_rate_limit,
+                graphql,
             },
         monitors,
     } = dep;
 
+    let runtime = Arc::new(runtime);
     let cx = Context {
         gql_monitor: monitors.graphql_task_monitor(),
-        schema: gql::schema_builder().data(runtime).finish(),
+        schema: gql::schema_builder(&graphql)
+            .data(Arc::clone(&runtime))
+            .data(authenticator.clone())
+            .finish(),
+        runtime,
     };
 
     tokio::spawn(monitors.emit_metrics(
@@ -86,11 +205,30 @@ pub async fn serve(
         shutdown.cancellation_token(),
     ));
 
-    let service = Router::new()
+    let rate_limit =
+        RateLimitLayer::new(rate_limit, expensive_rate_limit, request_body_limit_bytes);
+    let scheme = if tls_config.is_some() {
+        Scheme::HTTPS
+    } else {
+        Scheme::HTTP
+    };
+
+    Router::new()
         .route("/graphql", post(gql::handler::graphql))
-        .layer(Extension(cx))
-        .layer(authenticate::AuthenticateLayer::new(authenticator))
+        .merge(rest::router())
+        .layer(Extension(cx.clone()))
+        .layer(rate_limit.clone())
+        .layer(authenticate::AuthenticateLayer::new(authenticator.clone()))
+        .merge(
+            rest::feed_router()
+                .layer(Extension(cx))
+                .layer(Extension(authenticator))
+                .layer(Extension(scheme))
+                // Keyed by the token as feed readers do not send the authorization header
+                .layer(rate_limit),
+        )
         .route("/graphql", get(gql::handler::graphiql))
+        .route(rest::OPENAPI_PATH, get(rest::openapi))
         .layer(
             ServiceBuilder::new()
                 .layer(SetSensitiveHeadersLayer::new(std::iter::once(
@@ -100,23 +238,15 @@ pub async fn serve(
                 .layer(HandleErrorLayer::new(handle_middleware_error))
                 .layer(TimeoutLayer::new(request_timeout))
                 .layer(ConcurrencyLimitLayer::new(concurrency_limit))
-                .layer(RequestBodyLimitLayer::new(request_body_limit_bytes))
+                // Bodies are limited per route by RateLimitLayer, which reads them to classify the request
+                .layer(RequestBodyLimitLayer::new(
+                    request_body_limit_bytes.max(rest::IMPORT_BODY_LIMIT_BYTES),
+                ))
                 .layer(CorsLayer::new()),
         )
         .route(config::serve::HEALTH_CHECK_PATH, get(probe::healthcheck))
         .layer(RequestMetricsLayer::new())
-        .fallback(not_found);
-
-    tracing::info!("Serving...");
-
-    axum_server::from_tcp_rustls(listener.into_std()?, tls_config)
-        .handle(shutdown.into_handle())
-        .serve(service.into_make_service())
-        .await?;
-
-    tracing::info!("Shutdown complete");
-
-    Ok(())
+        .fallback(not_found)
 }
 
 async fn handle_middleware_error(err: BoxError) -> (StatusCode, String) {
@@ -139,8 +269,143 @@ async fn not_found() -> impl IntoResponse {
 
 #[cfg(test)]
 mod tests {
+    use axum::{body::Body, extract::Request};
+    use futures_util::future;
+    use synd_feed::{
+        feed::{
+            cache::CacheLayer,
+            service::{FetchFeed, FetchFeedResult},
+        },
+        types::{Feed, FeedCredential, FeedUrl},
+    };
+    use tower::ServiceExt as _;
+
+    use crate::{
+        client::github::GithubClient,
+        monitor::Monitors,
+        repository::memory::MemoryRepository,
+        serve::auth::Authenticator,
+        usecase::{MakeUsecase, authorize::Authorizer},
+    };
+
     use super::*;
 
+    #[derive(Clone)]
+    struct EmptyFeeds;
+
+    #[async_trait::async_trait]
+    impl FetchFeed for EmptyFeeds {
+        async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed> {
+            let xml = format!(
+                r#"<?xml version="1.0" encoding="utf-8"?><feed xmlns="http://www.w3.org/2005/Atom"><id>{url}</id><title>{url}</title><updated>2024-01-01T00:00:00Z</updated></feed>"#
+            );
+            let feed = feed_rs::parser::parse(xml.as_bytes()).unwrap();
+            Ok(Feed::from((url, feed)))
+        }
+
+        async fn fetch_private_feed(
+            &self,
+            url: FeedUrl,
+            _credential: &FeedCredential,
+        ) -> FetchFeedResult<Feed> {
+            self.fetch_feed(url).await
+        }
+    }
+
+    /// Dependency which authenticates github tokens with the mock server
+    async fn dependency() -> Dependency {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let github_endpoint: &'static str =
+            format!("http://{}/github/graphql", listener.local_addr().unwrap()).leak();
+        tokio::spawn(synd_test::mock::serve(listener));
+
+        let repo = Arc::new(MemoryRepository::default());
+        let cache = Arc::new(CacheLayer::new(EmptyFeeds));
+        let make = MakeUsecase {
+            subscription_repo: repo.clone(),
+            user_repo: repo.clone(),
+            fetch_feed: cache.clone(),
+            feed_cache: cache,
+            credential_cipher: None,
+        };
+        let authenticator = Authenticator::new(repo.clone(), repo)
+            .unwrap()
+            .with_github_client(GithubClient::new().unwrap().with_endpoint(github_endpoint));
+        let quota = Quota { per_minute: 1000 };
+
+        Dependency {
+            authenticator,
+            runtime: Runtime::new(make, Authorizer::new()),
+            tls_config: None,
+            serve_options: ServeOptions {
+                timeout: Duration::from_secs(10),
+                body_limit_bytes: config::serve::DEFAULT_REQUEST_BODY_LIMIT_BYTES,
+                concurrency_limit: 10,
+                rate_limit: quota,
+                expensive_rate_limit: quota,
+                graphql: GraphQLOptions {
+                    depth_limit: config::serve::DEFAULT_GRAPHQL_DEPTH_LIMIT,
+                    complexity_limit: config::serve::DEFAULT_GRAPHQL_COMPLEXITY_LIMIT,
+                    persisted_query_cache_size: 1,
+                },
+            },
+            monitors: Monitors::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn import_body_limit() {
+        let shutdown = Shutdown::watch_signal(future::pending(), || {});
+        let router = router(dependency().await, &shutdown);
+        let post = |path: &str, body: String| {
+            Request::post(path)
+                .header(AUTHORIZATION, "github dummy_token")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+        let feeds = (0..100)
+            .map(|i| serde_json::json!({ "url": format!("https://example.com/{i}/feed.xml") }))
+            .collect::<Vec<_>>();
+        let import = serde_json::json!({ "feeds": feeds }).to_string();
+        // Max number of feeds exceeds the default limit
+        assert!(import.len() > config::serve::DEFAULT_REQUEST_BODY_LIMIT_BYTES);
+
+        let response = router
+            .clone()
+            .oneshot(post(rest::IMPORT_PATH, import))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["imported"], 100);
+
+        // Other routes keep the default limit
+        let query = format!(
+            r#"{{"query": "query {{ __typename }}", "padding": "{}"}}"#,
+            "a".repeat(config::serve::DEFAULT_REQUEST_BODY_LIMIT_BYTES)
+        );
+        let response = router
+            .clone()
+            .oneshot(post("/graphql", query))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let padding = "a".repeat(rest::IMPORT_BODY_LIMIT_BYTES);
+        let response = router
+            .oneshot(post(
+                rest::IMPORT_PATH,
+                format!(r#"{{"feeds": [], "padding": "{padding}"}}"#),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
     #[tokio::test]
     async fn error_mapping() {
         assert_eq!(
@@ -163,4 +428,24 @@ mod tests {
             StatusCode::NOT_FOUND
         );
     }
+
+    #[tokio::test]
+    async fn remove_socket_file() {
+        let dir = synd_test::temp_dir();
+        let path = dir.path().join("synd.sock");
+
+        let listener = UnixListener::bind(&path).unwrap();
+        drop(listener);
+        remove_socket(&path).unwrap();
+        assert!(!path.exists());
+        UnixListener::bind(&path).unwrap();
+
+        // Missing socket is not an error
+        remove_socket(&dir.path().join("missing.sock")).unwrap();
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(remove_socket(&file).is_err());
+        assert!(file.exists());
+    }
 }
@@ -0,0 +1,125 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use axum_server::tls_rustls::RustlsConfig;
+use tokio_util::sync::CancellationToken;
+
+/// Reload the certificate and the private key when they are changed on disk
+pub struct TlsReloader {
+    config: RustlsConfig,
+    certificate: PathBuf,
+    private_key: PathBuf,
+}
+
+impl TlsReloader {
+    pub fn new(config: RustlsConfig, certificate: PathBuf, private_key: PathBuf) -> Self {
+        Self {
+            config,
+            certificate,
+            private_key,
+        }
+    }
+
+    /// Check the modified time of the files at given interval until cancelled
+    pub async fn run(self, interval: Duration, ct: CancellationToken) {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // The first tick completes immediately
+        interval.tick().await;
+
+        let mut modified = self.modified().await;
+
+        loop {
+            tokio::select! {
+                biased;
+                () = ct.cancelled() => break,
+                _ = interval.tick() => {},
+            }
+
+            let current = self.modified().await;
+            if current == modified {
+                continue;
+            }
+
+            // Certificate and private key might be updated non-atomically.
+            // In that case reloading fails, so retry on the next tick
+            match self
+                .config
+                .reload_from_pem_file(&self.certificate, &self.private_key)
+                .await
+            {
+                Ok(()) => {
+                    tracing::info!(
+                        certificate = %self.certificate.display(),
+                        "Tls certificate reloaded"
+                    );
+                    modified = current;
+                }
+                Err(err) => tracing::warn!("Failed to reload tls certificate: {err}"),
+            }
+        }
+    }
+
+    async fn modified(&self) -> [Option<SystemTime>; 2] {
+        [
+            modified(&self.certificate).await,
+            modified(&self.private_key).await,
+        ]
+    }
+}
+
+/// Return the modified time following symlinks, which are used to swap secrets in kubernetes
+async fn modified(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reload_on_change() {
+        let dir = synd_test::temp_dir();
+        let certificate = dir.path().join("cert.pem");
+        let private_key = dir.path().join("key.pem");
+        std::fs::copy(synd_test::certificate(), &certificate).unwrap();
+        std::fs::copy(synd_test::private_key(), &private_key).unwrap();
+
+        let config = RustlsConfig::from_pem_file(&certificate, &private_key)
+            .await
+            .unwrap();
+        let before = config.get_inner();
+        let reloader = TlsReloader::new(config.clone(), certificate.clone(), private_key);
+        let ct = CancellationToken::new();
+        let handle = tokio::spawn(reloader.run(Duration::from_millis(10), ct.clone()));
+
+        let is_reloaded = || !std::sync::Arc::ptr_eq(&before, &config.get_inner());
+        // Wait for a few ticks. unchanged files are never reloaded, so this can not be flaky
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!is_reloaded());
+
+        // Update modified time
+        let file = std::fs::File::options()
+            .append(true)
+            .open(&certificate)
+            .unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(1))
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !is_reloaded() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("certificate is not reloaded");
+
+        ct.cancel();
+        handle.await.unwrap();
+    }
+}
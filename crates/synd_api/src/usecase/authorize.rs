@@ -1,5 +1,7 @@
 use std::ops::Deref;
 
+use synd_o11y::{audit, tracing_subscriber::audit::Audit};
+
 use crate::principal::Principal;
 
 use super::Usecase;
@@ -38,9 +40,83 @@ impl Authorizer {
         usecase: &U,
         input: &U::Input,
     ) -> Result<Authorized<Principal>, Unauthorized> {
+        let permission = usecase.permission();
+        if !principal.permits(permission) {
+            let reason = format!(
+                "permission denied: {role:?} does not have {permission} permission",
+                role = principal.role(),
+                permission = permission.as_str(),
+            );
+            audit!({ Audit::REASON } = reason.as_str());
+            return Err(Unauthorized);
+        }
+
         usecase
             .authorize(principal, input)
             .await
             .map(Authorized::new)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        principal::{Permission, Role, User},
+        usecase::{Error, Input, MakeUsecase, Output},
+    };
+
+    use super::*;
+
+    struct Operate;
+
+    impl Usecase for Operate {
+        type Input = ();
+        type Output = ();
+        type Error = ();
+
+        fn new(_make: &MakeUsecase) -> Self {
+            Operate
+        }
+
+        fn permission(&self) -> Permission {
+            Permission::Admin
+        }
+
+        async fn authorize(
+            &self,
+            principal: Principal,
+            (): &(),
+        ) -> Result<Principal, Unauthorized> {
+            Ok(principal)
+        }
+
+        async fn usecase(&self, _: Input<()>) -> Result<Output<()>, Error<()>> {
+            Ok(Output { output: () })
+        }
+    }
+
+    #[tokio::test]
+    async fn deny_when_role_lacks_permission() {
+        let authorizer = Authorizer::new();
+        let principal = |role| Principal::User(User::new("id", "email").with_role(role));
+
+        assert!(
+            authorizer
+                .authorize(principal(Role::Admin), &Operate, &())
+                .await
+                .is_ok()
+        );
+        assert!(
+            authorizer
+                .authorize(principal(Role::Member), &Operate, &())
+                .await
+                .is_err()
+        );
+        assert!(
+            authorizer
+                .authorize(principal(Role::ReadOnly), &Operate, &())
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use std::{convert::Infallible, sync::Arc};
+
+use crate::{
+    principal::{Permission, Principal},
+    repository::UserRepository,
+    usecase::{Input, Output},
+};
+
+use super::{Usecase, authorize::Unauthorized};
+
+pub struct CountUsers {
+    pub repository: Arc<dyn UserRepository>,
+}
+
+pub struct CountUsersInput {}
+
+pub struct CountUsersOutput {
+    pub count: u64,
+}
+
+impl Usecase for CountUsers {
+    type Input = CountUsersInput;
+
+    type Output = CountUsersOutput;
+
+    type Error = Infallible;
+
+    fn new(make: &super::MakeUsecase) -> Self {
+        Self {
+            repository: make.user_repo.clone(),
+        }
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Admin
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &CountUsersInput,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        _: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
+        let count = self.repository.count_users().await?;
+
+        Ok(Output {
+            output: CountUsersOutput { count },
+        })
+    }
+}
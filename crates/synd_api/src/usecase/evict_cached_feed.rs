@@ -0,0 +1,65 @@
+use std::{convert::Infallible, sync::Arc};
+
+use synd_feed::{feed::cache::ManageFeedCache, types::FeedUrl};
+
+use crate::{
+    principal::{Permission, Principal},
+    usecase::{Input, Output},
+};
+
+use super::{Usecase, authorize::Unauthorized};
+
+pub struct EvictCachedFeed {
+    pub feed_cache: Arc<dyn ManageFeedCache>,
+}
+
+pub struct EvictCachedFeedInput {
+    pub url: FeedUrl,
+}
+
+pub struct EvictCachedFeedOutput {
+    /// Whether the feed was cached
+    pub evicted: bool,
+}
+
+impl Usecase for EvictCachedFeed {
+    type Input = EvictCachedFeedInput;
+
+    type Output = EvictCachedFeedOutput;
+
+    type Error = Infallible;
+
+    fn new(make: &super::MakeUsecase) -> Self {
+        Self {
+            feed_cache: make.feed_cache.clone(),
+        }
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Admin
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &EvictCachedFeedInput,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            input: EvictCachedFeedInput { url },
+            ..
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
+        tracing::info!("Evict cached feed: {url}");
+
+        let evicted = self.feed_cache.evict_feed(&url).await;
+
+        Ok(Output {
+            output: EvictCachedFeedOutput { evicted },
+        })
+    }
+}
@@ -3,33 +3,77 @@ use std::{cmp::Ordering, collections::HashMap, sync::Arc};
 use futures_util::{StreamExt, stream::FuturesUnordered};
 use synd_feed::{
     feed::{cache::FetchCachedFeed, service::FetchFeedError},
-    types::{self, Annotated, Entry, EntryId, FeedMeta, FeedUrl},
+    types::{
+        self, Annotated, Category, Entry, EntryId, FeedCredential, FeedMeta, FeedType, FeedUrl,
+        Requirement,
+    },
 };
 use thiserror::Error;
 
 use crate::{
-    principal::Principal,
+    credential::CredentialCipher,
+    principal::{Permission, Principal},
     repository::{
         SubscriptionRepository,
         types::{FeedAnnotations, SubscribedFeeds},
     },
-    usecase::{Error, Input, MakeUsecase, Output, Usecase, authorize::Unauthorized},
+    usecase::{
+        Error, Input, MakeUsecase, Output, Usecase, authorize::Unauthorized, decrypt_credential,
+    },
 };
 
 pub struct FetchEntries {
     pub repository: Arc<dyn SubscriptionRepository>,
     pub fetch_feed: Arc<dyn FetchCachedFeed>,
+    pub credential_cipher: Option<Arc<CredentialCipher>>,
 }
 
 pub struct FetchEntriesInput {
     pub after: Option<EntryId<'static>>,
     pub first: usize,
+    pub filter: EntryFilter,
+    /// Collapse the entries of the same article published in several feeds
+    pub deduplicate: bool,
+}
+
+/// Condition on the feed annotations which entries must satisfy
+#[derive(Default, Debug, Clone)]
+pub struct EntryFilter {
+    /// Entries of the feeds whose requirement satisfies this requirement
+    pub requirement: Option<Requirement>,
+    /// Entries of the feeds belonging to one of these categories
+    /// If empty, all categories are allowed
+    pub categories: Vec<Category<'static>>,
+    /// Entries of this feed only. the other feeds are not fetched
+    pub feed_url: Option<FeedUrl>,
+}
+
+impl EntryFilter {
+    /// Requirement of the feeds which are not annotated
+    const DEFAULT_REQUIREMENT: Requirement = Requirement::Should;
+
+    fn matches(&self, feed: &Annotated<FeedMeta>) -> bool {
+        let requirement = self.requirement.is_none_or(|condition| {
+            feed.requirement
+                .unwrap_or(Self::DEFAULT_REQUIREMENT)
+                .is_satisfied(condition)
+        });
+        let category = self.categories.is_empty()
+            || feed
+                .category
+                .as_ref()
+                .is_some_and(|category| self.categories.contains(category));
+
+        requirement && category
+    }
 }
 
 #[derive(Default)]
 pub struct FetchEntriesOutput {
     pub entries: Vec<(types::Entry, types::FeedUrl)>,
     pub feeds: HashMap<types::FeedUrl, Annotated<types::FeedMeta>>,
+    /// Feeds of the duplicates collapsed into the entry
+    pub duplicates: HashMap<EntryId<'static>, Vec<types::FeedUrl>>,
 }
 
 #[derive(Error, Debug)]
@@ -47,9 +91,14 @@ impl Usecase for FetchEntries {
         Self {
             repository: make.subscription_repo.clone(),
             fetch_feed: make.fetch_feed.clone(),
+            credential_cipher: make.credential_cipher.clone(),
         }
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Read
+    }
+
     async fn authorize(
         &self,
         principal: Principal,
@@ -63,21 +112,34 @@ impl Usecase for FetchEntries {
         &self,
         Input {
             principal,
-            input: FetchEntriesInput { after, first },
+            input:
+                FetchEntriesInput {
+                    after,
+                    first,
+                    filter,
+                    deduplicate,
+                },
         }: Input<Self::Input>,
     ) -> Result<Output<Self::Output>, Error<Self::Error>> {
         let user_id = principal
             .user_id()
             .expect("user id not found. this is a bug");
 
-        let SubscribedFeeds { urls, annotations } =
-            self.repository.fetch_subscribed_feeds(user_id).await?;
+        let SubscribedFeeds {
+            mut urls,
+            annotations,
+        } = self.repository.fetch_subscribed_feeds(user_id).await?;
+        if let Some(feed_url) = filter.feed_url.as_ref() {
+            urls.retain(|url| url == feed_url);
+        }
 
         let output = self
-            .operation(urls, annotations)
+            .operation(user_id, urls, annotations)
             .fetch()
             .await
+            .filter(&filter)
             .sort()
+            .deduplicate(deduplicate)
             .paginate(first, after);
 
         Ok(output)
@@ -87,14 +149,36 @@ impl Usecase for FetchEntries {
 impl FetchEntries {
     fn operation(
         &self,
+        user_id: &str,
         urls: Vec<FeedUrl>,
         annotations: Option<HashMap<FeedUrl, FeedAnnotations>>,
     ) -> FetchOperation {
         let len = urls.len();
+        let feeds = urls
+            .into_iter()
+            .filter_map(|url| {
+                match decrypt_credential(
+                    self.credential_cipher.as_deref(),
+                    user_id,
+                    &url,
+                    annotations
+                        .as_ref()
+                        .and_then(|annotations| annotations.get(&url)),
+                ) {
+                    Ok(credential) => Some((url, credential)),
+                    // Skipped in the same way as the feeds which failed to fetch
+                    Err(err) => {
+                        tracing::warn!(%url, "Failed to fetch feed {err:?}");
+                        None
+                    }
+                }
+            })
+            .collect();
         FetchOperation {
-            urls: Some(urls),
+            feeds: Some(feeds),
             metas: HashMap::with_capacity(len),
             entries: Vec::with_capacity(len * 5),
+            duplicates: HashMap::new(),
             annotations,
             fetch_feed: self.fetch_feed.clone(),
         }
@@ -102,8 +186,8 @@ impl FetchEntries {
 }
 
 struct FetchOperation {
-    // urls to fetch. wrap `Option` for take ownership
-    urls: Option<Vec<FeedUrl>>,
+    // urls to fetch with their credentials. wrap `Option` for take ownership
+    feeds: Option<Vec<(FeedUrl, Option<FeedCredential>)>>,
     // feed annotations got from repository
     annotations: Option<HashMap<FeedUrl, FeedAnnotations>>,
     // fetch service
@@ -112,6 +196,14 @@ struct FetchOperation {
     // output
     metas: HashMap<FeedUrl, Annotated<FeedMeta>>,
     entries: Vec<(Entry, FeedUrl)>,
+    duplicates: HashMap<EntryId<'static>, Vec<FeedUrl>>,
+}
+
+/// Key to identify the same article
+#[derive(PartialEq, Eq, Hash)]
+enum DuplicateKey {
+    Link(String),
+    Content(u64),
 }
 
 impl FetchOperation {
@@ -120,7 +212,7 @@ impl FetchOperation {
         let mut tasks = FuturesUnordered::new();
         let in_flight_limit = 10;
 
-        for url in self.urls.take().unwrap() {
+        for (url, credential) in self.feeds.take().unwrap() {
             if tasks.len() >= in_flight_limit {
                 if let Some(result) = tasks.next().await {
                     self.handle(result);
@@ -128,7 +220,12 @@ impl FetchOperation {
             }
 
             let fetch_feed = Arc::clone(&self.fetch_feed);
-            tasks.push(async move { fetch_feed.fetch_feed(url).await });
+            tasks.push(async move {
+                match credential {
+                    Some(credential) => fetch_feed.fetch_private_feed(url, &credential).await,
+                    None => fetch_feed.fetch_feed(url).await,
+                }
+            });
         }
 
         while let Some(result) = tasks.next().await {
@@ -169,9 +266,18 @@ impl FetchOperation {
         );
     }
 
+    // retain entries of the feeds which match the filter
+    fn filter(mut self, filter: &EntryFilter) -> Self {
+        let metas = &self.metas;
+        self.entries
+            .retain(|(_, feed_url)| metas.get(feed_url).is_some_and(|feed| filter.matches(feed)));
+        self
+    }
+
     // sort entries
     fn sort(mut self) -> Self {
-        self.entries.sort_unstable_by(|(a, _), (b, _)| {
+        // Stable so that the entries published at the same time keep the order of the feeds
+        self.entries.sort_by(|(a, _), (b, _)| {
             match (a.published().or(a.updated()), b.published().or(b.updated())) {
                 (Some(a), Some(b)) => b.cmp(&a),
                 (None, Some(_)) => Ordering::Greater,
@@ -182,6 +288,53 @@ impl FetchOperation {
         self
     }
 
+    // collapse the entries which share the link or the content into the first one.
+    // since entries are sorted, the latest one is kept
+    fn deduplicate(mut self, enabled: bool) -> Self {
+        if !enabled {
+            return self;
+        }
+        let mut entries: Vec<(Entry, FeedUrl)> = Vec::with_capacity(self.entries.len());
+        let mut positions: HashMap<DuplicateKey, usize> = HashMap::new();
+
+        for (entry, feed_url) in std::mem::take(&mut self.entries) {
+            let feed_type = self
+                .metas
+                .get(&feed_url)
+                .map_or(FeedType::Atom, |meta| meta.feed.r#type());
+            // The content is checked first as it is more specific than the link,
+            // which may be shared by the entries of a feed
+            let keys = [
+                entry.content_digest().map(DuplicateKey::Content),
+                entry.canonical_url(feed_type).map(DuplicateKey::Link),
+            ];
+
+            // Entries of the same feed are distinct even if they share the link or the content,
+            // like the entries of a feed which always links to the top page
+            if let Some(position) = keys.iter().flatten().find_map(|key| {
+                positions
+                    .get(key)
+                    .copied()
+                    .filter(|position| entries[*position].1 != feed_url)
+            }) {
+                let feeds = self.duplicates.entry(entries[position].0.id()).or_default();
+                // Keys of the duplicate are not registered not to collapse the entries
+                // which share only them into the entry matched by the other key
+                if !feeds.contains(&feed_url) {
+                    feeds.push(feed_url);
+                }
+            } else {
+                for key in keys.into_iter().flatten() {
+                    positions.entry(key).or_insert(entries.len());
+                }
+                entries.push((entry, feed_url));
+            }
+        }
+
+        self.entries = entries;
+        self
+    }
+
     // paginate entries and return output
     fn paginate(
         mut self,
@@ -209,7 +362,205 @@ impl FetchOperation {
             output: FetchEntriesOutput {
                 entries,
                 feeds: self.metas,
+                duplicates: self.duplicates,
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use synd_feed::feed::service::FetchFeedResult;
+
+    use super::*;
+
+    const BLOG: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Blog</title>
+  <id>urn:blog</id>
+  <updated>2024-01-02T00:01:00Z</updated>
+  <entry>
+    <title>First</title>
+    <id>blog-1</id>
+    <link rel="alternate" href="https://blog.example.com/1"/>
+    <updated>2024-01-02T00:01:00Z</updated>
+  </entry>
+  <entry>
+    <title>Second</title>
+    <id>blog-2</id>
+    <link rel="alternate" href="https://blog.example.com/2"/>
+    <content type="html">&lt;p&gt;second post&lt;/p&gt;</content>
+    <updated>2024-01-01T00:01:00Z</updated>
+  </entry>
+</feed>"#;
+
+    const PLANET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Planet</title>
+    <link>https://planet.example.com</link>
+    <item>
+      <title>First</title>
+      <guid>planet-1</guid>
+      <link>http://blog.example.com/1/?utm_source=planet</link>
+      <pubDate>Tue, 02 Jan 2024 00:00:00 +0000</pubDate>
+    </item>
+    <item>
+      <title>Second</title>
+      <guid>planet-2</guid>
+      <link>https://planet.example.com/2</link>
+      <description>&lt;p&gt;second  post&lt;/p&gt;</description>
+      <pubDate>Mon, 01 Jan 2024 00:00:00 +0000</pubDate>
+    </item>
+  </channel>
+</rss>"#;
+
+    struct StaticFeeds(HashMap<FeedUrl, Arc<types::Feed>>);
+
+    #[async_trait]
+    impl FetchCachedFeed for StaticFeeds {
+        async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<types::Feed>> {
+            Ok(Arc::clone(&self.0[&url]))
+        }
+
+        async fn fetch_private_feed(
+            &self,
+            url: FeedUrl,
+            _credential: &FeedCredential,
+        ) -> FetchFeedResult<Arc<types::Feed>> {
+            self.fetch_feed(url).await
+        }
+
+        async fn fetch_feeds_parallel(
+            &self,
+            feeds: &[(FeedUrl, Option<FeedCredential>)],
+        ) -> Vec<FetchFeedResult<Arc<types::Feed>>> {
+            let mut results = Vec::new();
+            for (url, _) in feeds {
+                results.push(self.fetch_feed(url.clone()).await);
+            }
+            results
+        }
+    }
+
+    const NEWS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>News</title>
+    <link>https://news.example.com</link>
+    <item>
+      <title>Today</title>
+      <guid>news-2</guid>
+      <link>https://news.example.com</link>
+      <description>See the top page</description>
+      <pubDate>Tue, 02 Jan 2024 00:00:00 +0000</pubDate>
+    </item>
+    <item>
+      <title>Yesterday</title>
+      <guid>news-1</guid>
+      <link>https://news.example.com</link>
+      <description>See the top page</description>
+      <pubDate>Mon, 01 Jan 2024 00:00:00 +0000</pubDate>
+    </item>
+  </channel>
+</rss>"#;
+
+    fn operation() -> FetchOperation {
+        operation_with(&[
+            ("https://blog.example.com/feed.xml", BLOG),
+            ("https://planet.example.com/rss.xml", PLANET),
+        ])
+    }
+
+    fn operation_with(feeds: &[(&str, &str)]) -> FetchOperation {
+        let feeds = feeds
+            .iter()
+            .map(|(url, src)| {
+                let url = FeedUrl::parse(url).unwrap();
+                let feed = feed_rs::parser::parse(src.as_bytes()).unwrap();
+                (url.clone(), Arc::new(types::Feed::from((url, feed))))
+            })
+            .collect::<HashMap<_, _>>();
+
+        FetchOperation {
+            feeds: Some(feeds.keys().map(|url| (url.clone(), None)).collect()),
+            annotations: None,
+            fetch_feed: Arc::new(StaticFeeds(feeds)),
+            metas: HashMap::new(),
+            entries: Vec::new(),
+            duplicates: HashMap::new(),
+        }
+    }
+
+    fn entry_ids(output: &FetchEntriesOutput) -> Vec<String> {
+        output
+            .entries
+            .iter()
+            .map(|(entry, _)| entry.id().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn deduplicate() {
+        let Output { output } = operation()
+            .fetch()
+            .await
+            .sort()
+            .deduplicate(false)
+            .paginate(10, None);
+        assert_eq!(
+            entry_ids(&output),
+            vec!["blog-1", "planet-1", "blog-2", "planet-2"]
+        );
+        assert!(output.duplicates.is_empty());
+
+        let Output { output } = operation()
+            .fetch()
+            .await
+            .sort()
+            .deduplicate(true)
+            .paginate(10, None);
+        assert_eq!(entry_ids(&output), vec!["blog-1", "blog-2"]);
+
+        let planet = FeedUrl::parse("https://planet.example.com/rss.xml").unwrap();
+        // Collapsed by the link
+        assert_eq!(
+            output.duplicates.get(&EntryId::from("blog-1")),
+            Some(&vec![planet.clone()])
+        );
+        // Collapsed by the content
+        assert_eq!(
+            output.duplicates.get(&EntryId::from("blog-2")),
+            Some(&vec![planet])
+        );
+    }
+
+    #[tokio::test]
+    async fn deduplicate_only_across_feeds() {
+        let Output { output } = operation_with(&[
+            ("https://news.example.com/rss.xml", NEWS),
+            ("https://mirror.example.com/rss.xml", NEWS),
+        ])
+        .fetch()
+        .await
+        .sort()
+        .deduplicate(true)
+        .paginate(10, None);
+
+        // Entries sharing the link in the same feed are kept,
+        // while the ones of the other feed are collapsed
+        assert_eq!(entry_ids(&output), vec!["news-2", "news-1"]);
+        let (feed_urls, duplicates): (Vec<_>, Vec<_>) = output
+            .entries
+            .iter()
+            .map(|(entry, feed_url)| (feed_url, output.duplicates.get(&entry.id())))
+            .unzip();
+        assert_eq!(feed_urls[0], feed_urls[1]);
+        assert_eq!(duplicates[0].map(Vec::len), Some(1));
+        assert_ne!(duplicates[0].unwrap()[0], *feed_urls[0]);
+        // Collapsed into the entry sharing the content rather than the first one sharing the link
+        assert_eq!(duplicates[1].map(Vec::len), Some(1));
+        assert_ne!(duplicates[1].unwrap()[0], *feed_urls[1]);
+    }
+}
@@ -7,14 +7,18 @@ use synd_feed::{
 use thiserror::Error;
 
 use crate::{
-    principal::Principal,
+    credential::CredentialCipher,
+    principal::{Permission, Principal},
     repository::{SubscriptionRepository, types::SubscribedFeeds},
-    usecase::{Error, Input, MakeUsecase, Output, Usecase, authorize::Unauthorized},
+    usecase::{
+        Error, Input, MakeUsecase, Output, Usecase, authorize::Unauthorized, decrypt_credential,
+    },
 };
 
 pub struct FetchSubscribedFeeds {
     pub repository: Arc<dyn SubscriptionRepository>,
     pub fetch_feed: Arc<dyn FetchCachedFeed>,
+    pub credential_cipher: Option<Arc<CredentialCipher>>,
 }
 
 pub struct FetchSubscribedFeedsInput {
@@ -43,9 +47,14 @@ impl Usecase for FetchSubscribedFeeds {
         Self {
             repository: make.subscription_repo.clone(),
             fetch_feed: make.fetch_feed.clone(),
+            credential_cipher: make.credential_cipher.clone(),
         }
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Read
+    }
+
     async fn authorize(
         &self,
         principal: Principal,
@@ -87,15 +96,43 @@ impl Usecase for FetchSubscribedFeeds {
             urls
         };
 
-        // fetch feeds
-        let fetched_feeds = self.fetch_feed.fetch_feeds_parallel(&urls).await;
+        // fetch feeds with their credentials
+        let credentials = urls
+            .iter()
+            .map(|url| {
+                decrypt_credential(
+                    self.credential_cipher.as_deref(),
+                    user_id,
+                    url,
+                    annotations
+                        .as_ref()
+                        .and_then(|annotations| annotations.get(url)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let feeds = urls
+            .iter()
+            .zip(&credentials)
+            .filter_map(|(url, credential)| {
+                credential
+                    .as_ref()
+                    .ok()
+                    .map(|credential| (url.clone(), credential.clone()))
+            })
+            .collect::<Vec<_>>();
+        let mut fetched_feeds = self
+            .fetch_feed
+            .fetch_feeds_parallel(&feeds)
+            .await
+            .into_iter();
 
         // annotate fetched feeds
-        let feeds = fetched_feeds
+        let feeds = credentials
             .into_iter()
             .zip(urls)
-            .map(|(result, url)| {
-                result
+            .map(|(credential, url)| {
+                credential
+                    .and_then(|_| fetched_feeds.next().expect("fetched for each credential"))
                     .map(|feed| {
                         match annotations
                             .as_mut()
@@ -0,0 +1,59 @@
+use std::{convert::Infallible, sync::Arc};
+
+use synd_feed::feed::cache::{CacheEntry, CacheStats, ManageFeedCache};
+
+use crate::{
+    principal::{Permission, Principal},
+    usecase::{Input, Output},
+};
+
+use super::{Usecase, authorize::Unauthorized};
+
+pub struct InspectFeedCache {
+    pub feed_cache: Arc<dyn ManageFeedCache>,
+}
+
+pub struct InspectFeedCacheInput {}
+
+pub struct InspectFeedCacheOutput {
+    pub stats: CacheStats,
+    pub entries: Vec<CacheEntry>,
+}
+
+impl Usecase for InspectFeedCache {
+    type Input = InspectFeedCacheInput;
+
+    type Output = InspectFeedCacheOutput;
+
+    type Error = Infallible;
+
+    fn new(make: &super::MakeUsecase) -> Self {
+        Self {
+            feed_cache: make.feed_cache.clone(),
+        }
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Admin
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &InspectFeedCacheInput,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        _: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
+        let stats = self.feed_cache.stats().await;
+        let entries = self.feed_cache.entries();
+
+        Ok(Output {
+            output: InspectFeedCacheOutput { stats, entries },
+        })
+    }
+}
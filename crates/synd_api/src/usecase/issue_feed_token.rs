@@ -0,0 +1,104 @@
+use std::{convert::Infallible, fmt::Write as _, sync::Arc};
+
+use rand::RngCore;
+
+use crate::{
+    principal::{Permission, Principal, Role},
+    repository::{UserRepository, types::UserRecord},
+    usecase::{Input, Output},
+};
+
+use super::{Usecase, authorize::Unauthorized};
+
+/// Issue the secret token to read the aggregated feed
+/// The previously issued token is revoked
+pub struct IssueFeedToken {
+    pub repository: Arc<dyn UserRepository>,
+}
+
+pub struct IssueFeedTokenInput {}
+
+pub struct IssueFeedTokenOutput {
+    pub token: String,
+}
+
+impl Usecase for IssueFeedToken {
+    type Input = IssueFeedTokenInput;
+
+    type Output = IssueFeedTokenOutput;
+
+    type Error = Infallible;
+
+    fn new(make: &super::MakeUsecase) -> Self {
+        Self {
+            repository: make.user_repo.clone(),
+        }
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &IssueFeedTokenInput,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input { principal, .. }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
+        let Principal::User(user) = &*principal;
+
+        let mut record = match self.repository.fetch_user(user.id()).await? {
+            Some(record) => record,
+            None => UserRecord {
+                id: user.id().to_owned(),
+                email: user.email().to_owned(),
+                identities: Vec::new(),
+                role: Role::default(),
+                feed_token: None,
+                counted: false,
+            },
+        };
+
+        let token = generate_token();
+        record.feed_token = Some(token.clone());
+        self.repository.put_user(record).await?;
+
+        tracing::info!(enduser.id = user.id(), "Issue feed token");
+
+        Ok(Output {
+            output: IssueFeedTokenOutput { token },
+        })
+    }
+}
+
+/// Generate 256 bits random token encoded in hex
+fn generate_token() -> String {
+    let mut bytes = [0_u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut token, b| {
+            let _ = write!(token, "{b:02x}");
+            token
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token() {
+        let token = generate_token();
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_token());
+    }
+}
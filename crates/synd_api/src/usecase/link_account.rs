@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::{
+    principal::{Identity, Permission, Principal, Role},
+    repository::{UserRepository, types::UserRecord},
+    usecase::{Input, Output},
+};
+
+use super::{Usecase, authorize::Unauthorized};
+
+pub struct LinkAccount {
+    pub repository: Arc<dyn UserRepository>,
+}
+
+pub struct LinkAccountInput {
+    /// Identity verified by the provider to link
+    pub identity: Identity,
+}
+
+pub struct LinkAccountOutput {
+    pub user: UserRecord,
+}
+
+#[derive(Error, Debug)]
+pub enum LinkAccountError {
+    #[error("{provider} identity is already linked to another user")]
+    AlreadyLinked { provider: String },
+}
+
+impl Usecase for LinkAccount {
+    type Input = LinkAccountInput;
+
+    type Output = LinkAccountOutput;
+
+    type Error = LinkAccountError;
+
+    fn new(make: &super::MakeUsecase) -> Self {
+        Self {
+            repository: make.user_repo.clone(),
+        }
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &LinkAccountInput,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: LinkAccountInput { identity },
+            ..
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
+        let Principal::User(user) = &*principal;
+
+        let mut record = match self.repository.fetch_user(user.id()).await? {
+            Some(record) => record,
+            None => UserRecord {
+                id: user.id().to_owned(),
+                email: user.email().to_owned(),
+                identities: Vec::new(),
+                role: Role::default(),
+                feed_token: None,
+                counted: false,
+            },
+        };
+
+        if record.is_linked(&identity) {
+            return Ok(Output {
+                output: LinkAccountOutput { user: record },
+            });
+        }
+
+        if let Some(linked) = self.repository.fetch_user_by_identity(&identity).await? {
+            if linked.id != record.id {
+                return Err(super::Error::Usecase(LinkAccountError::AlreadyLinked {
+                    provider: identity.provider.to_string(),
+                }));
+            }
+        }
+
+        tracing::info!(
+            enduser.id = record.id,
+            provider = %identity.provider,
+            "Link account"
+        );
+
+        record.identities.push(identity);
+        self.repository.put_user(record.clone()).await?;
+
+        Ok(Output {
+            output: LinkAccountOutput { user: record },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        principal::{IdentityProvider, User},
+        repository::memory::MemoryRepository,
+        usecase::authorize::Authorizer,
+    };
+
+    use super::*;
+
+    fn identity(provider: IdentityProvider, subject: &str) -> Identity {
+        Identity {
+            provider,
+            subject: subject.to_owned(),
+            email: "a@example.com".to_owned(),
+        }
+    }
+
+    async fn link(
+        repo: &Arc<MemoryRepository>,
+        user_id: &str,
+        identity: Identity,
+    ) -> Result<Output<LinkAccountOutput>, super::super::Error<LinkAccountError>> {
+        let uc = LinkAccount {
+            repository: repo.clone(),
+        };
+        let input = LinkAccountInput { identity };
+        let principal = Authorizer::new()
+            .authorize(
+                Principal::User(User::new(user_id, "a@example.com")),
+                &uc,
+                &input,
+            )
+            .await
+            .unwrap();
+        uc.usecase(Input { principal, input }).await
+    }
+
+    #[tokio::test]
+    async fn link_identity() {
+        let repo = Arc::new(MemoryRepository::default());
+        let google = identity(IdentityProvider::Google, "g-1");
+
+        let user = link(&repo, "user-a", google.clone())
+            .await
+            .unwrap()
+            .output
+            .user;
+        assert!(user.is_linked(&google));
+
+        // Linking again is idempotent
+        let user = link(&repo, "user-a", google).await.unwrap().output.user;
+        assert_eq!(user.identities.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn already_linked_to_another_user() {
+        let repo = Arc::new(MemoryRepository::default());
+        let github = identity(IdentityProvider::Github, "gh-1");
+        link(&repo, "user-a", github.clone()).await.unwrap();
+
+        let err = link(&repo, "user-b", github).await.err().unwrap();
+        assert!(matches!(
+            err,
+            super::super::Error::Usecase(LinkAccountError::AlreadyLinked { provider }) if provider == "github"
+        ));
+        assert!(
+            repo.fetch_user("user-b")
+                .await
+                .unwrap()
+                .is_none_or(|user| user.identities.is_empty())
+        );
+    }
+}
@@ -3,6 +3,11 @@ pub use subscribe_feed::{
     SubscribeFeed, SubscribeFeedError, SubscribeFeedInput, SubscribeFeedOutput,
 };
 
+mod update_feeds;
+pub use update_feeds::{
+    UpdateFeedInput, UpdateFeeds, UpdateFeedsError, UpdateFeedsInput, UpdateFeedsOutput,
+};
+
 mod unsubscribe_feed;
 pub use unsubscribe_feed::{UnsubscribeFeed, UnsubscribeFeedInput, UnsubscribeFeedOutput};
 
@@ -12,27 +17,60 @@ pub use fetch_subscribed_feeds::{
     FetchSubscribedFeedsOutput,
 };
 
+mod link_account;
+pub use link_account::{LinkAccount, LinkAccountError, LinkAccountInput, LinkAccountOutput};
+
 mod fetch_entries;
-pub use fetch_entries::{FetchEntries, FetchEntriesError, FetchEntriesInput, FetchEntriesOutput};
+pub use fetch_entries::{
+    EntryFilter, FetchEntries, FetchEntriesError, FetchEntriesInput, FetchEntriesOutput,
+};
+
+mod inspect_feed_cache;
+pub use inspect_feed_cache::{InspectFeedCache, InspectFeedCacheInput, InspectFeedCacheOutput};
+
+mod refresh_cached_feed;
+pub use refresh_cached_feed::{
+    RefreshCachedFeed, RefreshCachedFeedError, RefreshCachedFeedInput, RefreshCachedFeedOutput,
+};
+
+mod evict_cached_feed;
+pub use evict_cached_feed::{EvictCachedFeed, EvictCachedFeedInput, EvictCachedFeedOutput};
+
+mod count_users;
+pub use count_users::{CountUsers, CountUsersInput, CountUsersOutput};
+
+mod issue_feed_token;
+pub use issue_feed_token::{IssueFeedToken, IssueFeedTokenInput, IssueFeedTokenOutput};
 
 use tracing::error;
 
 pub mod authorize;
 use std::{future::Future, sync::Arc};
 
-use synd_feed::feed::cache::FetchCachedFeed;
+use synd_feed::{
+    feed::{
+        cache::{FetchCachedFeed, ManageFeedCache},
+        service::FetchFeedError,
+    },
+    types::{FeedCredential, FeedUrl},
+};
 use synd_o11y::{audit, metric, tracing_subscriber::audit::Audit};
 
 use crate::{
-    principal::Principal,
-    repository::{RepositoryError, SubscriptionRepository},
+    credential::CredentialCipher,
+    principal::{Permission, Principal},
+    repository::{RepositoryError, SubscriptionRepository, UserRepository, types::FeedAnnotations},
 };
 
 use self::authorize::{Authorized, Authorizer, Unauthorized};
 
 pub struct MakeUsecase {
     pub subscription_repo: Arc<dyn SubscriptionRepository>,
+    pub user_repo: Arc<dyn UserRepository>,
     pub fetch_feed: Arc<dyn FetchCachedFeed>,
+    pub feed_cache: Arc<dyn ManageFeedCache>,
+    /// Encrypt feed credentials. if not configured, subscribing with a credential is rejected
+    pub credential_cipher: Option<Arc<CredentialCipher>>,
 }
 
 impl MakeUsecase {
@@ -73,6 +111,9 @@ pub trait Usecase {
         name.split("::").last().unwrap_or("?")
     }
 
+    /// Permission which the principal is required to have
+    fn permission(&self) -> Permission;
+
     /// Authorize given principal
     fn authorize(
         &self,
@@ -151,3 +192,52 @@ impl Runtime {
 pub trait Context {
     fn principal(&self) -> Principal;
 }
+
+/// Decrypt the credential of the subscribed feed
+/// If the credential can not be decrypted, the feed is not fetched as the request would be unauthenticated
+fn decrypt_credential(
+    cipher: Option<&CredentialCipher>,
+    user_id: &str,
+    url: &FeedUrl,
+    annotations: Option<&FeedAnnotations>,
+) -> Result<Option<FeedCredential>, FetchFeedError> {
+    let Some(encrypted) = annotations.and_then(|annotations| annotations.credential.as_ref())
+    else {
+        return Ok(None);
+    };
+    let Some(cipher) = cipher else {
+        return Err(FetchFeedError::Other(anyhow::anyhow!(
+            "feed credential is stored but the credential key is not configured"
+        )));
+    };
+    cipher
+        .decrypt(user_id, url, encrypted)
+        .map(Some)
+        .map_err(|err| FetchFeedError::Other(anyhow::anyhow!("feed credential: {err}")))
+}
+
+/// Evict the feed fetched with the stored credential of the subscription unless the credential is kept,
+/// so that the cache neither holds the credential nor refreshes the feed with it
+async fn evict_stored_credential(
+    repository: &dyn SubscriptionRepository,
+    feed_cache: &dyn ManageFeedCache,
+    cipher: Option<&CredentialCipher>,
+    user_id: &str,
+    url: &FeedUrl,
+    keep: Option<&FeedCredential>,
+) -> Result<(), RepositoryError> {
+    let annotations = repository
+        .fetch_subscribed_feeds(user_id)
+        .await?
+        .annotations;
+    let stored = annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get(url));
+    // The feed fetched with an undecryptable credential can not be in the cache
+    if let Ok(Some(stored)) = decrypt_credential(cipher, user_id, url, stored) {
+        if keep != Some(&stored) {
+            feed_cache.evict_private_feed(url, &stored).await;
+        }
+    }
+    Ok(())
+}
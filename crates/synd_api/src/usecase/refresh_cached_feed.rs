@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use synd_feed::{
+    feed::{cache::ManageFeedCache, service::FetchFeedError},
+    types::{Feed, FeedUrl},
+};
+use thiserror::Error;
+
+use crate::{
+    principal::{Permission, Principal},
+    usecase::{Input, Output},
+};
+
+use super::{Usecase, authorize::Unauthorized};
+
+pub struct RefreshCachedFeed {
+    pub feed_cache: Arc<dyn ManageFeedCache>,
+}
+
+pub struct RefreshCachedFeedInput {
+    pub url: FeedUrl,
+}
+
+pub struct RefreshCachedFeedOutput {
+    pub feed: Arc<Feed>,
+}
+
+#[derive(Error, Debug)]
+pub enum RefreshCachedFeedError {
+    #[error("fetch feed error: {0}")]
+    FetchFeed(FetchFeedError),
+}
+
+impl Usecase for RefreshCachedFeed {
+    type Input = RefreshCachedFeedInput;
+
+    type Output = RefreshCachedFeedOutput;
+
+    type Error = RefreshCachedFeedError;
+
+    fn new(make: &super::MakeUsecase) -> Self {
+        Self {
+            feed_cache: make.feed_cache.clone(),
+        }
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Admin
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &RefreshCachedFeedInput,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            input: RefreshCachedFeedInput { url },
+            ..
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
+        tracing::info!("Refresh cached feed: {url}");
+
+        let feed = self
+            .feed_cache
+            .refresh_feed(url)
+            .await
+            .map_err(|err| super::Error::Usecase(RefreshCachedFeedError::FetchFeed(err)))?;
+
+        Ok(Output {
+            output: RefreshCachedFeedOutput { feed },
+        })
+    }
+}
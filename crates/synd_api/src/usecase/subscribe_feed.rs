@@ -1,16 +1,20 @@
 use std::sync::Arc;
 
 use synd_feed::{
-    feed::{cache::FetchCachedFeed, service::FetchFeedError},
-    types::{Annotated, Category, Feed, FeedUrl, Requirement},
+    feed::{
+        cache::{FetchCachedFeed, ManageFeedCache},
+        service::FetchFeedError,
+    },
+    types::{Annotated, Category, Feed, FeedCredential, FeedUrl, Requirement},
 };
 use synd_o11y::metric;
 use thiserror::Error;
 
 use crate::{
-    principal::Principal,
+    credential::{CredentialCipher, CredentialError},
+    principal::{Permission, Principal},
     repository::{self, SubscriptionRepository},
-    usecase::{Input, Output},
+    usecase::{Input, Output, evict_stored_credential},
 };
 
 use super::{Usecase, authorize::Unauthorized};
@@ -18,12 +22,16 @@ use super::{Usecase, authorize::Unauthorized};
 pub struct SubscribeFeed {
     pub repository: Arc<dyn SubscriptionRepository>,
     pub fetch_feed: Arc<dyn FetchCachedFeed>,
+    pub feed_cache: Arc<dyn ManageFeedCache>,
+    pub credential_cipher: Option<Arc<CredentialCipher>>,
 }
 
 pub struct SubscribeFeedInput {
     pub url: FeedUrl,
     pub requirement: Option<Requirement>,
     pub category: Option<Category<'static>>,
+    /// Credential to fetch the private feed
+    pub credential: Option<FeedCredential>,
 }
 
 pub struct SubscribeFeedOutput {
@@ -34,6 +42,10 @@ pub struct SubscribeFeedOutput {
 pub enum SubscribeFeedError {
     #[error("fetch feed error: {0}")]
     FetchFeed(FetchFeedError),
+    #[error("feed credential is not enabled on this server")]
+    CredentialNotEnabled,
+    #[error("credential error: {0}")]
+    Credential(CredentialError),
 }
 
 impl Usecase for SubscribeFeed {
@@ -47,9 +59,15 @@ impl Usecase for SubscribeFeed {
         Self {
             repository: make.subscription_repo.clone(),
             fetch_feed: make.fetch_feed.clone(),
+            feed_cache: make.feed_cache.clone(),
+            credential_cipher: make.credential_cipher.clone(),
         }
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
     async fn authorize(
         &self,
         principal: Principal,
@@ -67,26 +85,66 @@ impl Usecase for SubscribeFeed {
                     url,
                     requirement,
                     category,
+                    credential,
                 },
             ..
         }: Input<Self::Input>,
     ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
         tracing::debug!("Subscribe feed: {url}");
 
-        let feed = self
-            .fetch_feed
-            .fetch_feed(url.clone())
-            .await
-            .map_err(|err| super::Error::Usecase(SubscribeFeedError::FetchFeed(err)))?;
+        let Some(user_id) = principal.user_id() else {
+            return Err(super::Error::Unauthorized(Unauthorized));
+        };
+
+        // Check before fetching not to leave the private feed in the cache
+        let cipher = match (credential.as_ref(), self.credential_cipher.as_ref()) {
+            (Some(_), None) => {
+                return Err(super::Error::Usecase(
+                    SubscribeFeedError::CredentialNotEnabled,
+                ));
+            }
+            (_, cipher) => cipher,
+        };
+
+        let feed = match credential.as_ref() {
+            Some(credential) => {
+                self.fetch_feed
+                    .fetch_private_feed(url.clone(), credential)
+                    .await
+            }
+            None => self.fetch_feed.fetch_feed(url.clone()).await,
+        }
+        .map_err(|err| super::Error::Usecase(SubscribeFeedError::FetchFeed(err)))?;
 
         tracing::debug!("{:?}", feed.meta());
 
+        let url = feed.meta().url().to_owned();
+        evict_stored_credential(
+            self.repository.as_ref(),
+            self.feed_cache.as_ref(),
+            cipher.map(AsRef::as_ref),
+            user_id,
+            &url,
+            credential.as_ref(),
+        )
+        .await?;
+
+        let credential = match (credential, cipher) {
+            (Some(credential), Some(cipher)) => Some(
+                cipher
+                    .encrypt(user_id, &url, &credential)
+                    .map_err(|err| super::Error::Usecase(SubscribeFeedError::Credential(err)))?,
+            ),
+            _ => None,
+        };
+
         self.repository
             .put_feed_subscription(repository::types::FeedSubscription {
-                user_id: principal.user_id().unwrap().to_owned(),
-                url: feed.meta().url().to_owned(),
+                user_id: user_id.to_owned(),
+                url,
                 requirement,
                 category: category.clone(),
+                credential,
             })
             .await?;
 
@@ -103,3 +161,150 @@ impl Usecase for SubscribeFeed {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+    use synd_feed::feed::{
+        cache::CacheLayer,
+        service::{FetchFeed, FetchFeedResult},
+    };
+
+    use crate::{
+        principal::User,
+        repository::memory::MemoryRepository,
+        usecase::{
+            FetchSubscribedFeeds, FetchSubscribedFeedsInput, MakeUsecase, Runtime, UnsubscribeFeed,
+            UnsubscribeFeedInput, authorize::Authorizer,
+        },
+    };
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct EmptyFeed;
+
+    #[async_trait::async_trait]
+    impl FetchFeed for EmptyFeed {
+        async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed> {
+            let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>urn:empty</id><title>empty</title><updated>2024-01-01T00:00:00Z</updated></feed>"#;
+            Ok(Feed::from((
+                url,
+                feed_rs::parser::parse(xml.as_bytes()).unwrap(),
+            )))
+        }
+
+        async fn fetch_private_feed(
+            &self,
+            url: FeedUrl,
+            _credential: &FeedCredential,
+        ) -> FetchFeedResult<Feed> {
+            self.fetch_feed(url).await
+        }
+    }
+
+    fn cipher(key: u8) -> Arc<CredentialCipher> {
+        Arc::new(CredentialCipher::from_base64(&STANDARD.encode([key; 32])).unwrap())
+    }
+
+    fn runtime(
+        repo: &Arc<MemoryRepository>,
+        cache: &Arc<CacheLayer<EmptyFeed>>,
+        cipher: Arc<CredentialCipher>,
+    ) -> Runtime {
+        let make = MakeUsecase {
+            subscription_repo: repo.clone(),
+            user_repo: repo.clone(),
+            fetch_feed: cache.clone(),
+            feed_cache: cache.clone(),
+            credential_cipher: Some(cipher),
+        };
+        Runtime::new(make, Authorizer::new())
+    }
+
+    fn principal() -> Principal {
+        Principal::User(User::new("user-a", "a@example.com"))
+    }
+
+    fn bearer(token: &str) -> FeedCredential {
+        FeedCredential::Bearer {
+            token: token.to_owned(),
+        }
+    }
+
+    fn private_entries(cache: &CacheLayer<EmptyFeed>) -> usize {
+        cache.entries().iter().filter(|entry| entry.private).count()
+    }
+
+    #[tokio::test]
+    async fn evict_private_feed_on_credential_change() {
+        let principal = principal();
+        let repo = Arc::new(MemoryRepository::default());
+        let cache = Arc::new(CacheLayer::new(EmptyFeed));
+        let runtime = runtime(&repo, &cache, cipher(1));
+        let url = FeedUrl::parse("https://private.example.com/feed.xml").unwrap();
+        let subscribe = |credential: Option<FeedCredential>| {
+            runtime.run::<SubscribeFeed, _, _>(
+                &principal,
+                SubscribeFeedInput {
+                    url: url.clone(),
+                    requirement: None,
+                    category: None,
+                    credential,
+                },
+            )
+        };
+
+        subscribe(Some(bearer("a"))).await.unwrap();
+        assert_eq!(private_entries(&cache), 1);
+
+        // Resubscribing with the same credential keeps the cache
+        subscribe(Some(bearer("a"))).await.unwrap();
+        assert_eq!(private_entries(&cache), 1);
+
+        subscribe(Some(bearer("b"))).await.unwrap();
+        assert!(!cache.evict_private_feed(&url, &bearer("a")).await);
+        assert_eq!(private_entries(&cache), 1);
+
+        runtime
+            .run::<UnsubscribeFeed, _, _>(&principal, UnsubscribeFeedInput { url: url.clone() })
+            .await
+            .unwrap();
+        assert_eq!(private_entries(&cache), 0);
+    }
+
+    #[tokio::test]
+    async fn undecryptable_credential_is_an_error() {
+        let principal = principal();
+        let repo = Arc::new(MemoryRepository::default());
+        let cache = Arc::new(CacheLayer::new(EmptyFeed));
+        let url = FeedUrl::parse("https://private.example.com/feed.xml").unwrap();
+        runtime(&repo, &cache, cipher(1))
+            .run::<SubscribeFeed, _, _>(
+                &principal,
+                SubscribeFeedInput {
+                    url: url.clone(),
+                    requirement: None,
+                    category: None,
+                    credential: Some(bearer("a")),
+                },
+            )
+            .await
+            .unwrap();
+
+        // Key was changed
+        let feeds = runtime(&repo, &cache, cipher(2))
+            .run::<FetchSubscribedFeeds, _, _>(
+                &principal,
+                FetchSubscribedFeedsInput {
+                    after: None,
+                    first: 10,
+                },
+            )
+            .await
+            .unwrap()
+            .output
+            .feeds;
+        assert!(matches!(&feeds[..], [Err((err_url, _))] if *err_url == url));
+    }
+}
@@ -1,18 +1,21 @@
 use std::sync::Arc;
 
-use synd_feed::types::FeedUrl;
+use synd_feed::{feed::cache::ManageFeedCache, types::FeedUrl};
 use synd_o11y::metric;
 
 use crate::{
-    principal::Principal,
+    credential::CredentialCipher,
+    principal::{Permission, Principal},
     repository::{self, SubscriptionRepository},
-    usecase::{Input, Output},
+    usecase::{Input, Output, evict_stored_credential},
 };
 
 use super::{Usecase, authorize::Unauthorized};
 
 pub struct UnsubscribeFeed {
     pub repository: Arc<dyn SubscriptionRepository>,
+    pub feed_cache: Arc<dyn ManageFeedCache>,
+    pub credential_cipher: Option<Arc<CredentialCipher>>,
 }
 
 pub struct UnsubscribeFeedInput {
@@ -31,9 +34,15 @@ impl Usecase for UnsubscribeFeed {
     fn new(make: &super::MakeUsecase) -> Self {
         Self {
             repository: make.subscription_repo.clone(),
+            feed_cache: make.feed_cache.clone(),
+            credential_cipher: make.credential_cipher.clone(),
         }
     }
 
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
     async fn authorize(
         &self,
         principal: Principal,
@@ -52,12 +61,27 @@ impl Usecase for UnsubscribeFeed {
     ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
         tracing::debug!("Unsubscribe feed: {url}");
 
+        let Some(user_id) = principal.user_id() else {
+            return Err(super::Error::Unauthorized(Unauthorized));
+        };
+
+        evict_stored_credential(
+            self.repository.as_ref(),
+            self.feed_cache.as_ref(),
+            self.credential_cipher.as_deref(),
+            user_id,
+            &url,
+            None,
+        )
+        .await?;
+
         self.repository
             .delete_feed_subscription(repository::types::FeedSubscription {
-                user_id: principal.user_id().unwrap().to_owned(),
+                user_id: user_id.to_owned(),
                 url,
                 requirement: None,
                 category: None,
+                credential: None,
             })
             .await?;
 
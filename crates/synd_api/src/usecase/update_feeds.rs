@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use synd_feed::types::{Category, FeedUrl, Requirement};
+use thiserror::Error;
+
+use crate::{
+    principal::{Permission, Principal},
+    repository::{self, SubscriptionRepository},
+    usecase::{Input, Output},
+};
+
+use super::{Usecase, authorize::Unauthorized};
+
+/// Update the annotations of the subscribed feeds without fetching them
+pub struct UpdateFeeds {
+    pub repository: Arc<dyn SubscriptionRepository>,
+}
+
+pub struct UpdateFeedsInput {
+    pub feeds: Vec<UpdateFeedInput>,
+}
+
+pub struct UpdateFeedInput {
+    pub url: FeedUrl,
+    pub requirement: Option<Requirement>,
+    pub category: Option<Category<'static>>,
+}
+
+pub struct UpdateFeedsOutput {}
+
+#[derive(Error, Debug)]
+pub enum UpdateFeedsError {
+    #[error("feed is not subscribed: {0}")]
+    NotSubscribed(FeedUrl),
+}
+
+impl Usecase for UpdateFeeds {
+    type Input = UpdateFeedsInput;
+
+    type Output = UpdateFeedsOutput;
+
+    type Error = UpdateFeedsError;
+
+    fn new(make: &super::MakeUsecase) -> Self {
+        Self {
+            repository: make.subscription_repo.clone(),
+        }
+    }
+
+    fn permission(&self) -> Permission {
+        Permission::Write
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &UpdateFeedsInput,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: UpdateFeedsInput { feeds },
+            ..
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
+        let Some(user_id) = principal.user_id() else {
+            return Err(super::Error::Unauthorized(Unauthorized));
+        };
+
+        let mut annotations = self
+            .repository
+            .fetch_subscribed_feeds(user_id)
+            .await?
+            .annotations
+            .unwrap_or_default();
+
+        // Check all feeds before updating not to apply the edition partially
+        if let Some(feed) = feeds
+            .iter()
+            .find(|feed| !annotations.contains_key(&feed.url))
+        {
+            return Err(super::Error::Usecase(UpdateFeedsError::NotSubscribed(
+                feed.url.clone(),
+            )));
+        }
+
+        for UpdateFeedInput {
+            url,
+            requirement,
+            category,
+        } in feeds
+        {
+            tracing::debug!("Update feed: {url}");
+
+            // The stored credential is kept as the feed is not fetched
+            let credential = annotations
+                .remove(&url)
+                .and_then(|annotations| annotations.credential);
+            self.repository
+                .put_feed_subscription(repository::types::FeedSubscription {
+                    user_id: user_id.to_owned(),
+                    url,
+                    requirement,
+                    category,
+                    credential,
+                })
+                .await?;
+        }
+
+        Ok(Output {
+            output: UpdateFeedsOutput {},
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+    use synd_feed::{
+        feed::{
+            cache::CacheLayer,
+            service::{FetchFeed, FetchFeedResult},
+        },
+        types::{Feed, FeedCredential},
+    };
+
+    use crate::{
+        credential::CredentialCipher,
+        principal::User,
+        repository::{memory::MemoryRepository, types::FeedSubscription},
+        usecase::{MakeUsecase, Runtime, authorize::Authorizer},
+    };
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Unreachable;
+
+    #[async_trait::async_trait]
+    impl FetchFeed for Unreachable {
+        async fn fetch_feed(&self, _url: FeedUrl) -> FetchFeedResult<Feed> {
+            unreachable!("updating feeds must not fetch them")
+        }
+
+        async fn fetch_private_feed(
+            &self,
+            _url: FeedUrl,
+            _credential: &FeedCredential,
+        ) -> FetchFeedResult<Feed> {
+            unreachable!("updating feeds must not fetch them")
+        }
+    }
+
+    fn runtime(repo: &Arc<MemoryRepository>) -> Runtime {
+        let cache = Arc::new(CacheLayer::new(Unreachable));
+        let make = MakeUsecase {
+            subscription_repo: repo.clone(),
+            user_repo: repo.clone(),
+            fetch_feed: cache.clone(),
+            feed_cache: cache,
+            credential_cipher: None,
+        };
+        Runtime::new(make, Authorizer::new())
+    }
+
+    fn url(s: &str) -> FeedUrl {
+        FeedUrl::parse(s).unwrap()
+    }
+
+    #[tokio::test]
+    async fn update_annotations_keeping_credential() {
+        let principal = Principal::User(User::new("user-a", "a@example.com"));
+        let repo = Arc::new(MemoryRepository::default());
+        let credential = CredentialCipher::from_base64(&STANDARD.encode([1; 32]))
+            .unwrap()
+            .encrypt(
+                "user-a",
+                &url("https://a.example.com/feed.xml"),
+                &FeedCredential::Bearer {
+                    token: "token".to_owned(),
+                },
+            )
+            .unwrap();
+        for (feed, credential) in [
+            ("https://a.example.com/feed.xml", Some(credential.clone())),
+            ("https://b.example.com/feed.xml", None),
+        ] {
+            repo.put_feed_subscription(FeedSubscription {
+                user_id: "user-a".to_owned(),
+                url: url(feed),
+                requirement: None,
+                category: None,
+                credential,
+            })
+            .await
+            .unwrap();
+        }
+        let runtime = runtime(&repo);
+        let update = |feeds: Vec<UpdateFeedInput>| {
+            runtime.run::<UpdateFeeds, _, _>(&principal, UpdateFeedsInput { feeds })
+        };
+
+        let err = update(vec![
+            UpdateFeedInput {
+                url: url("https://a.example.com/feed.xml"),
+                requirement: Some(Requirement::Must),
+                category: None,
+            },
+            UpdateFeedInput {
+                url: url("https://c.example.com/feed.xml"),
+                requirement: Some(Requirement::Must),
+                category: None,
+            },
+        ])
+        .await
+        .err()
+        .unwrap();
+        assert!(
+            matches!(err, crate::usecase::Error::Usecase(UpdateFeedsError::NotSubscribed(not_subscribed)) if not_subscribed == url("https://c.example.com/feed.xml"))
+        );
+
+        update(vec![
+            UpdateFeedInput {
+                url: url("https://a.example.com/feed.xml"),
+                requirement: Some(Requirement::Must),
+                category: Some(Category::new("rust").unwrap()),
+            },
+            UpdateFeedInput {
+                url: url("https://b.example.com/feed.xml"),
+                requirement: Some(Requirement::May),
+                category: None,
+            },
+        ])
+        .await
+        .unwrap();
+
+        let annotations = repo
+            .fetch_subscribed_feeds("user-a")
+            .await
+            .unwrap()
+            .annotations
+            .unwrap();
+        let a = &annotations[&url("https://a.example.com/feed.xml")];
+        assert_eq!(a.requirement, Some(Requirement::Must));
+        assert_eq!(a.category, Some(Category::new("rust").unwrap()));
+        assert_eq!(a.credential, Some(credential));
+        let b = &annotations[&url("https://b.example.com/feed.xml")];
+        assert_eq!(b.requirement, Some(Requirement::May));
+        assert_eq!(b.credential, None);
+    }
+}
@@ -30,6 +30,7 @@ mod private {
 
     impl Sealed for provider::Github {}
     impl Sealed for provider::Google {}
+    impl Sealed for provider::Oidc {}
 }
 
 #[derive(Clone)]
@@ -0,0 +1,153 @@
+use std::borrow::Cow;
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    device_flow::{DeviceAuthorizationRequest, Provider},
+    oidc::ProviderMetadata,
+};
+
+/// Generic `OpenID` Connect provider which supports the device authorization grant
+#[derive(Clone)]
+pub struct Oidc {
+    client_id: Cow<'static, str>,
+    client_secret: Option<Cow<'static, str>>,
+    scope: Cow<'static, str>,
+    device_authorization_endpoint: Url,
+    token_endpoint: Url,
+}
+
+impl Oidc {
+    const GRANT_TYPE: &'static str = "urn:ietf:params:oauth:grant-type:device_code";
+    /// `offline_access` is required by most providers to issue a refresh token
+    const DEFAULT_SCOPE: &'static str = "openid email offline_access";
+
+    pub fn new(
+        client_id: impl Into<Cow<'static, str>>,
+        device_authorization_endpoint: Url,
+        token_endpoint: Url,
+    ) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: None,
+            scope: Self::DEFAULT_SCOPE.into(),
+            device_authorization_endpoint,
+            token_endpoint,
+        }
+    }
+
+    /// Construct `Oidc` from discovered provider metadata.
+    /// Return `None` if the provider does not support the device authorization grant
+    pub fn from_metadata(
+        client_id: impl Into<Cow<'static, str>>,
+        metadata: &ProviderMetadata,
+    ) -> Option<Self> {
+        let device_authorization_endpoint = metadata.device_authorization_endpoint.clone()?;
+        Some(Self::new(
+            client_id,
+            device_authorization_endpoint,
+            metadata.token_endpoint.clone(),
+        ))
+    }
+
+    #[must_use]
+    pub fn with_client_secret(self, client_secret: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            client_secret: Some(client_secret.into()),
+            ..self
+        }
+    }
+
+    /// Set scopes to request. `openid` is always requested
+    #[must_use]
+    pub fn with_scopes<S: AsRef<str>>(self, scopes: impl IntoIterator<Item = S>) -> Self {
+        let mut scope = String::from("openid");
+        for s in scopes {
+            let s = s.as_ref();
+            if s != "openid" {
+                scope.push(' ');
+                scope.push_str(s);
+            }
+        }
+        Self {
+            scope: scope.into(),
+            ..self
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeviceAccessTokenRequest<'s> {
+    grant_type: Cow<'static, str>,
+    pub device_code: Cow<'s, str>,
+    pub client_id: Cow<'s, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<Cow<'s, str>>,
+}
+
+impl Provider for Oidc {
+    type DeviceAccessTokenRequest<'d> = DeviceAccessTokenRequest<'d>;
+
+    fn device_authorization_endpoint(&self) -> Url {
+        self.device_authorization_endpoint.clone()
+    }
+
+    fn token_endpoint(&self) -> Url {
+        self.token_endpoint.clone()
+    }
+
+    fn device_authorization_request(&self) -> DeviceAuthorizationRequest {
+        DeviceAuthorizationRequest {
+            client_id: self.client_id.clone(),
+            scope: self.scope.clone(),
+        }
+    }
+
+    fn device_access_token_request<'d, 'p: 'd>(
+        &'p self,
+        device_code: &'d str,
+    ) -> DeviceAccessTokenRequest<'d> {
+        DeviceAccessTokenRequest {
+            grant_type: Self::GRANT_TYPE.into(),
+            device_code: device_code.into(),
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oidc() -> Oidc {
+        Oidc::new(
+            "synd",
+            Url::parse("https://example.com/device").unwrap(),
+            Url::parse("https://example.com/token").unwrap(),
+        )
+    }
+
+    #[test]
+    fn default_scope() {
+        assert_eq!(
+            oidc().device_authorization_request().scope,
+            "openid email offline_access"
+        );
+    }
+
+    #[test]
+    fn with_scopes_always_request_openid() {
+        let scope = |scopes: &[&str]| {
+            oidc()
+                .with_scopes(scopes)
+                .device_authorization_request()
+                .scope
+                .into_owned()
+        };
+        assert_eq!(scope(&["email", "profile"]), "openid email profile");
+        assert_eq!(scope(&["openid", "email"]), "openid email");
+        assert_eq!(scope(&[]), "openid");
+    }
+}
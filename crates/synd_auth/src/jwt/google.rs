@@ -9,27 +9,10 @@ use chrono::{DateTime, TimeZone, Utc};
 use jsonwebtoken::{Algorithm, DecodingKey, Validation};
 use reqwest::{Client, Url};
 use serde::{Deserialize, Serialize};
-use thiserror::Error;
-
-use crate::{USER_AGENT, config};
-
-type Kid = String;
-
-#[derive(Debug, Error)]
-pub enum JwtError {
-    #[error("fetch pem: {0}")]
-    FetchPem(#[from] reqwest::Error),
-    #[error("decoding key pem not found")]
-    DecodingKeyPemNotFound,
-    #[error("decode id token: {0}")]
-    Decode(#[from] jsonwebtoken::errors::Error),
-    #[error("invalid jwt header: {0}")]
-    InvalidHeader(String),
-    #[error("refresh id token: {0}")]
-    RefreshToken(reqwest::Error),
-    #[error("unexpected algorithm: {0:?}")]
-    UnexpectedAlgorithm(Algorithm),
-}
+
+use crate::{USER_AGENT, config, jwt::Kid};
+
+pub use crate::jwt::JwtError;
 
 #[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
@@ -1 +1,25 @@
+use jsonwebtoken::Algorithm;
+use thiserror::Error;
+
 pub mod google;
+pub mod oidc;
+
+type Kid = String;
+
+#[derive(Debug, Error)]
+pub enum JwtError {
+    #[error("fetch pem: {0}")]
+    FetchPem(#[from] reqwest::Error),
+    #[error("fetch jwks: {0}")]
+    FetchJwks(reqwest::Error),
+    #[error("decoding key pem not found")]
+    DecodingKeyPemNotFound,
+    #[error("decode id token: {0}")]
+    Decode(#[from] jsonwebtoken::errors::Error),
+    #[error("invalid jwt header: {0}")]
+    InvalidHeader(String),
+    #[error("refresh id token: {0}")]
+    RefreshToken(reqwest::Error),
+    #[error("unexpected algorithm: {0:?}")]
+    UnexpectedAlgorithm(Algorithm),
+}
@@ -0,0 +1,375 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, Validation,
+    jwk::{Jwk, JwkSet},
+};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    USER_AGENT,
+    jwt::{JwtError, Kid},
+    oidc::ProviderMetadata,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub iss: String,
+    pub sub: String,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl Claims {
+    /// Return `DateTime` at when `Claims` expire
+    pub fn expired_at(&self) -> DateTime<Utc> {
+        Utc.timestamp_opt(self.exp, 0)
+            .single()
+            .unwrap_or_else(Utc::now)
+    }
+
+    /// Return the email only if the provider asserts it is verified
+    pub fn verified_email(&self) -> Option<&str> {
+        match self.email_verified {
+            Some(true) => self.email.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Decoding key with the algorithm declared in the jwk
+struct Key {
+    decoding_key: DecodingKey,
+    algorithm: Option<Algorithm>,
+}
+
+impl Key {
+    fn from_jwk(jwk: &Jwk) -> Result<Self, jsonwebtoken::errors::Error> {
+        Ok(Self {
+            decoding_key: DecodingKey::from_jwk(jwk)?,
+            algorithm: jwk
+                .common
+                .key_algorithm
+                .and_then(|alg| Algorithm::from_str(&alg.to_string()).ok()),
+        })
+    }
+}
+
+/// Generic `OpenID` Connect id token service which verifies id tokens with the provider's JWKS
+#[derive(Clone)]
+pub struct JwtService {
+    client: Client,
+    issuer: Cow<'static, str>,
+    client_id: Cow<'static, str>,
+    client_secret: Option<Cow<'static, str>>,
+    jwks_uri: Url,
+    token_endpoint: Url,
+    /// Algorithms accepted for id tokens. The token header is not trusted for this
+    algorithms: Vec<Algorithm>,
+    key_cache: Arc<RwLock<HashMap<Kid, Arc<Key>>>>,
+}
+
+impl JwtService {
+    /// The default signing algorithm of id tokens
+    /// <https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata>
+    const DEFAULT_ALGORITHM: Algorithm = Algorithm::RS256;
+
+    pub fn new(
+        issuer: impl Into<Cow<'static, str>>,
+        client_id: impl Into<Cow<'static, str>>,
+        jwks_uri: Url,
+        token_endpoint: Url,
+    ) -> Self {
+        let client = reqwest::ClientBuilder::new()
+            .user_agent(USER_AGENT)
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        Self {
+            client,
+            issuer: issuer.into(),
+            client_id: client_id.into(),
+            client_secret: None,
+            jwks_uri,
+            token_endpoint,
+            algorithms: vec![Self::DEFAULT_ALGORITHM],
+            key_cache: Arc::new(RwLock::default()),
+        }
+    }
+
+    /// Construct `JwtService` from discovered provider metadata
+    pub fn from_metadata(
+        client_id: impl Into<Cow<'static, str>>,
+        metadata: &ProviderMetadata,
+    ) -> Self {
+        let service = Self::new(
+            metadata.issuer.clone(),
+            client_id,
+            metadata.jwks_uri.clone(),
+            metadata.token_endpoint.clone(),
+        );
+        let algorithms = metadata
+            .id_token_signing_alg_values_supported
+            .iter()
+            .filter_map(|alg| Algorithm::from_str(alg).ok())
+            .collect::<Vec<_>>();
+        if algorithms.is_empty() {
+            service
+        } else {
+            service.with_algorithms(algorithms)
+        }
+    }
+
+    /// Set the algorithms accepted for id tokens.
+    /// HMAC algorithms are ignored because id tokens are verified with the public keys
+    #[must_use]
+    pub fn with_algorithms(self, algorithms: impl IntoIterator<Item = Algorithm>) -> Self {
+        Self {
+            algorithms: algorithms
+                .into_iter()
+                .filter(|alg| {
+                    !matches!(alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512)
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_client_secret(self, client_secret: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            client_secret: Some(client_secret.into()),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_jwks_uri(self, jwks_uri: Url) -> Self {
+        Self { jwks_uri, ..self }
+    }
+
+    #[must_use]
+    pub fn with_token_endpoint(self, token_endpoint: Url) -> Self {
+        Self {
+            token_endpoint,
+            ..self
+        }
+    }
+
+    /// Decode and validate JWT id token
+    pub async fn decode_id_token(&self, id_token: &str) -> Result<Claims, JwtError> {
+        let header = jsonwebtoken::decode_header(id_token).map_err(JwtError::Decode)?;
+        let kid = header
+            .kid
+            .ok_or_else(|| JwtError::InvalidHeader("kid not found".into()))?;
+        let key = self.lookup_decoding_key(&kid).await?;
+        let algorithm = self.pin_algorithm(&key, header.alg)?;
+        let validation = {
+            let mut v = Validation::new(algorithm);
+            v.set_audience(&[self.client_id.as_ref()]);
+            v.set_issuer(&[self.issuer.as_ref()]);
+            v.set_required_spec_claims(&["exp"]);
+            v.validate_exp = true;
+            v
+        };
+
+        jsonwebtoken::decode(id_token, &key.decoding_key, &validation)
+            .map_err(JwtError::Decode)
+            .map(|data| data.claims)
+    }
+
+    /// Choose the algorithm from the jwk or the provider metadata, not from the token header
+    fn pin_algorithm(&self, key: &Key, header_alg: Algorithm) -> Result<Algorithm, JwtError> {
+        let algorithm = match key.algorithm {
+            Some(algorithm) => algorithm,
+            None => self
+                .algorithms
+                .iter()
+                .copied()
+                .find(|alg| *alg == header_alg)
+                .ok_or(JwtError::UnexpectedAlgorithm(header_alg))?,
+        };
+        if algorithm == header_alg && self.algorithms.contains(&algorithm) {
+            Ok(algorithm)
+        } else {
+            Err(JwtError::UnexpectedAlgorithm(header_alg))
+        }
+    }
+
+    /// Decode JWT id token without signature validation
+    pub fn decode_id_token_insecure(
+        &self,
+        id_token: &str,
+        validate_exp: bool,
+    ) -> Result<Claims, JwtError> {
+        let decoding_key = DecodingKey::from_secret(&[]);
+        let validation = {
+            let mut v = Validation::default();
+            v.insecure_disable_signature_validation();
+            v.set_audience(&[self.client_id.as_ref()]);
+            v.set_issuer(&[self.issuer.as_ref()]);
+            v.set_required_spec_claims(&["exp"]);
+            v.validate_exp = validate_exp;
+            v
+        };
+
+        jsonwebtoken::decode(id_token, &decoding_key, &validation)
+            .map_err(JwtError::Decode)
+            .map(|data| data.claims)
+    }
+
+    async fn lookup_decoding_key(&self, kid: &str) -> Result<Arc<Key>, JwtError> {
+        if let Some(key) = self.key_cache.read().unwrap().get(kid) {
+            return Ok(key.clone());
+        }
+
+        // The provider may have rotated its keys
+        self.refresh_key_cache().await?;
+
+        self.key_cache
+            .read()
+            .unwrap()
+            .get(kid)
+            .cloned()
+            .ok_or(JwtError::DecodingKeyPemNotFound)
+    }
+
+    async fn refresh_key_cache(&self) -> Result<(), JwtError> {
+        let keys = self.fetch_jwks().await?.keys.into_iter().filter_map(|jwk| {
+            let kid = jwk.common.key_id.clone()?;
+            match Key::from_jwk(&jwk) {
+                Ok(key) => Some((kid, Arc::new(key))),
+                Err(err) => {
+                    tracing::warn!("failed to create jwt decoding key from jwk: {err}");
+                    None
+                }
+            }
+        });
+
+        let mut cache = self.key_cache.write().unwrap();
+        keys.for_each(|(kid, de_key)| {
+            cache.insert(kid, de_key);
+        });
+
+        Ok(())
+    }
+
+    async fn fetch_jwks(&self) -> Result<JwkSet, JwtError> {
+        self.client
+            .get(self.jwks_uri.clone())
+            .header(http::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(JwtError::FetchJwks)?
+            .json::<JwkSet>()
+            .await
+            .map_err(JwtError::FetchJwks)
+    }
+
+    /// Refresh id token
+    /// <https://openid.net/specs/openid-connect-core-1_0.html#RefreshTokens>
+    pub async fn refresh_id_token(&self, refresh_token: &str) -> Result<String, JwtError> {
+        #[derive(Serialize)]
+        struct Request<'s> {
+            client_id: &'s str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            client_secret: Option<&'s str>,
+            refresh_token: &'s str,
+            grant_type: &'static str,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            id_token: String,
+        }
+
+        let request = &Request {
+            client_id: self.client_id.as_ref(),
+            client_secret: self.client_secret.as_deref(),
+            refresh_token,
+            grant_type: "refresh_token",
+        };
+        let response = self
+            .client
+            .post(self.token_endpoint.clone())
+            .header(http::header::ACCEPT, "application/json")
+            .form(request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(JwtError::RefreshToken)?
+            .json::<Response>()
+            .await
+            .map_err(JwtError::RefreshToken)?;
+
+        Ok(response.id_token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(email_verified: Option<bool>) -> Claims {
+        Claims {
+            iss: "https://accounts.example.com".into(),
+            sub: "user".into(),
+            email: Some("user@example.com".into()),
+            email_verified,
+            iat: 0,
+            exp: 0,
+        }
+    }
+
+    #[test]
+    fn verified_email_requires_email_verified() {
+        assert_eq!(
+            claims(Some(true)).verified_email(),
+            Some("user@example.com")
+        );
+        assert_eq!(claims(Some(false)).verified_email(), None);
+        assert_eq!(claims(None).verified_email(), None);
+    }
+
+    #[test]
+    fn pin_algorithm_ignore_token_header() {
+        let service = JwtService::new(
+            "https://accounts.example.com",
+            "synd",
+            Url::parse("https://accounts.example.com/jwks").unwrap(),
+            Url::parse("https://accounts.example.com/token").unwrap(),
+        )
+        .with_algorithms([Algorithm::ES256, Algorithm::HS256]);
+        let key = |algorithm| Key {
+            decoding_key: DecodingKey::from_secret(b"secret"),
+            algorithm,
+        };
+
+        assert_eq!(
+            service.pin_algorithm(&key(None), Algorithm::ES256).unwrap(),
+            Algorithm::ES256
+        );
+        // HMAC is never accepted even if the provider metadata lists it
+        assert!(service.pin_algorithm(&key(None), Algorithm::HS256).is_err());
+        assert!(service.pin_algorithm(&key(None), Algorithm::RS256).is_err());
+        // The algorithm of the jwk takes precedence over the token header
+        assert!(
+            service
+                .pin_algorithm(&key(Some(Algorithm::ES256)), Algorithm::ES384)
+                .is_err()
+        );
+    }
+}
@@ -5,5 +5,6 @@
 mod config;
 pub mod device_flow;
 pub mod jwt;
+pub mod oidc;
 
 const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
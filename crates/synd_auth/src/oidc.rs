@@ -0,0 +1,104 @@
+//! `OpenID` Connect provider discovery
+//! <https://openid.net/specs/openid-connect-discovery-1_0.html>
+use std::time::Duration;
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::USER_AGENT;
+
+#[derive(Debug, Error)]
+pub enum DiscoveryError {
+    #[error("invalid issuer url: {0}")]
+    InvalidIssuer(#[from] url::ParseError),
+    #[error("fetch provider metadata: {0}")]
+    Fetch(#[from] reqwest::Error),
+    #[error("issuer mismatch. expected: {expected} actual: {actual}")]
+    IssuerMismatch { expected: String, actual: String },
+}
+
+/// `OpenID` Provider Metadata
+/// <https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub jwks_uri: Url,
+    pub token_endpoint: Url,
+    /// <https://datatracker.ietf.org/doc/html/rfc8628#section-4>
+    pub device_authorization_endpoint: Option<Url>,
+    /// JWS algorithms the provider signs id tokens with
+    #[serde(default)]
+    pub id_token_signing_alg_values_supported: Vec<String>,
+}
+
+impl ProviderMetadata {
+    const WELL_KNOWN_PATH: &'static str = ".well-known/openid-configuration";
+
+    /// Fetch provider metadata from the issuer's well known configuration endpoint
+    pub async fn discover(issuer: &Url) -> Result<Self, DiscoveryError> {
+        let client = reqwest::ClientBuilder::new()
+            .user_agent(USER_AGENT)
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        let metadata = client
+            .get(Self::configuration_url(issuer)?)
+            .header(http::header::ACCEPT, "application/json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ProviderMetadata>()
+            .await?;
+
+        // The issuer value returned must be identical to the issuer URL used for discovery
+        let expected = issuer.as_str().trim_end_matches('/');
+        if metadata.issuer.trim_end_matches('/') != expected {
+            return Err(DiscoveryError::IssuerMismatch {
+                expected: expected.to_owned(),
+                actual: metadata.issuer,
+            });
+        }
+
+        Ok(metadata)
+    }
+
+    fn configuration_url(issuer: &Url) -> Result<Url, url::ParseError> {
+        // Make sure to keep the path of the issuer. `Url::join` replaces the last segment
+        // if the path does not end with '/'
+        let mut issuer = issuer.clone();
+        if !issuer.path().ends_with('/') {
+            issuer.set_path(&format!("{}/", issuer.path()));
+        }
+        issuer.join(Self::WELL_KNOWN_PATH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configuration_url_keep_issuer_path() {
+        let cases = [
+            (
+                "https://accounts.example.com",
+                "https://accounts.example.com/.well-known/openid-configuration",
+            ),
+            (
+                "https://example.com/realms/synd",
+                "https://example.com/realms/synd/.well-known/openid-configuration",
+            ),
+            (
+                "https://example.com/realms/synd/",
+                "https://example.com/realms/synd/.well-known/openid-configuration",
+            ),
+        ];
+        for (issuer, expected) in cases {
+            let url = ProviderMetadata::configuration_url(&Url::parse(issuer).unwrap()).unwrap();
+            assert_eq!(url.as_str(), expected);
+        }
+    }
+}
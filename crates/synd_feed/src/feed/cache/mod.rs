@@ -1,16 +1,112 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
 
 use crate::{
     feed::service::{FetchFeed, FetchFeedResult},
-    types::{self, FeedUrl},
+    types::{self, CredentialIdentity, FeedCredential, FeedUrl},
 };
 
 mod periodic_refresher;
 pub use periodic_refresher::PeriodicRefresher;
 
-type Cache = moka::future::Cache<FeedUrl, Arc<types::Feed>>;
+type Cache = moka::future::Cache<CacheKey, CachedFeed>;
+
+/// Feeds fetched with a credential are cached separately for each credential
+/// so that private contents are not shared with other users
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    url: FeedUrl,
+    credential: Option<CredentialIdentity>,
+}
+
+impl CacheKey {
+    fn new(url: FeedUrl, credential: Option<&FeedCredential>) -> Self {
+        Self {
+            url,
+            credential: credential.map(FeedCredential::identity),
+        }
+    }
+}
+
+/// Feed stored in the cache together with the time it was fetched
+#[derive(Clone)]
+pub struct CachedFeed {
+    feed: Arc<types::Feed>,
+    fetched_at: Instant,
+    // Required to refresh the private feed
+    credential: Option<FeedCredential>,
+}
+
+impl CachedFeed {
+    pub fn new(feed: Arc<types::Feed>) -> Self {
+        Self {
+            feed,
+            fetched_at: Instant::now(),
+            credential: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_credential(self, credential: FeedCredential) -> Self {
+        Self {
+            credential: Some(credential),
+            ..self
+        }
+    }
+
+    pub fn feed(&self) -> &Arc<types::Feed> {
+        &self.feed
+    }
+
+    /// Elapsed time since the feed was fetched
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
+}
+
+/// Snapshot of the feed cache statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entry_count: u64,
+    pub weighted_size: u64,
+    pub hit_count: u64,
+    pub miss_count: u64,
+}
+
+impl CacheStats {
+    /// Return the ratio of cache hits to lookups. if there is no lookup, return 0
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hit_count + self.miss_count;
+        if lookups == 0 {
+            0.
+        } else {
+            self.hit_count as f64 / lookups as f64
+        }
+    }
+}
+
+/// Cached feed url with the age of the cached entry
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub url: FeedUrl,
+    pub age: Duration,
+    /// Whether the feed was fetched with a credential
+    pub private: bool,
+}
+
+#[derive(Default)]
+struct Counter {
+    hit: AtomicU64,
+    miss: AtomicU64,
+}
 
 #[derive(Clone, Copy)]
 pub struct CacheConfig {
@@ -49,19 +145,41 @@ impl CacheConfig {
 #[async_trait]
 pub trait FetchCachedFeed: Send + Sync {
     async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<types::Feed>>;
+    /// Fetch the feed with the credential. the cache is not shared with other credentials
+    async fn fetch_private_feed(
+        &self,
+        url: FeedUrl,
+        credential: &FeedCredential,
+    ) -> FetchFeedResult<Arc<types::Feed>>;
     /// Fetch feeds by spawning tasks
     async fn fetch_feeds_parallel(
         &self,
-        urls: &[FeedUrl],
+        feeds: &[(FeedUrl, Option<FeedCredential>)],
     ) -> Vec<FetchFeedResult<Arc<types::Feed>>>;
 }
 
+/// Operations to inspect and manipulate the feed cache
+#[async_trait]
+pub trait ManageFeedCache: Send + Sync {
+    async fn stats(&self) -> CacheStats;
+    /// Return cached entries ordered by url
+    fn entries(&self) -> Vec<CacheEntry>;
+    /// Fetch the feed bypassing the cache and replace the cached entry
+    async fn refresh_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<types::Feed>>;
+    /// Remove the feed fetched with any credential from the cache. return true if the feed was cached
+    async fn evict_feed(&self, url: &FeedUrl) -> bool;
+    /// Remove the feed fetched with the credential so that the credential is no longer kept
+    /// and the feed is no longer refreshed. return true if the feed was cached
+    async fn evict_private_feed(&self, url: &FeedUrl, credential: &FeedCredential) -> bool;
+}
+
 #[derive(Clone)]
 pub struct CacheLayer<S> {
     service: S,
     // Use Arc to avoid expensive clone
     // https://github.com/moka-rs/moka?tab=readme-ov-file#avoiding-to-clone-the-value-at-get
     cache: Cache,
+    counter: Arc<Counter>,
 }
 impl<S> CacheLayer<S> {
     /// Construct `CacheLayer` with default config
@@ -77,14 +195,18 @@ impl<S> CacheLayer<S> {
         } = config;
 
         let cache = moka::future::Cache::builder()
-            .weigher(|_key, value: &Arc<types::Feed>| -> u32 {
-                value.approximate_size().try_into().unwrap_or(u32::MAX)
+            .weigher(|_key, value: &CachedFeed| -> u32 {
+                value.feed.approximate_size().try_into().unwrap_or(u32::MAX)
             })
             .max_capacity(max_cache_size)
             .time_to_live(time_to_live)
             .build();
 
-        Self { service, cache }
+        Self {
+            service,
+            cache,
+            counter: Arc::new(Counter::default()),
+        }
     }
 }
 
@@ -104,30 +226,32 @@ where
 {
     #[tracing::instrument(skip_all, fields(%url))]
     async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<types::Feed>> {
-        // lookup cache
-        if let Some(feed) = self.cache.get(&url).await {
-            tracing::debug!(url = url.as_str(), "Feed cache hit");
-            return Ok(feed);
-        }
-
-        let feed = self.service.fetch_feed(url.clone()).await.map(Arc::new)?;
-
-        self.cache.insert(url, Arc::clone(&feed)).await;
+        self.fetch(url, None).await
+    }
 
-        Ok(feed)
+    #[tracing::instrument(skip_all, fields(%url))]
+    async fn fetch_private_feed(
+        &self,
+        url: FeedUrl,
+        credential: &FeedCredential,
+    ) -> FetchFeedResult<Arc<types::Feed>> {
+        self.fetch(url, Some(credential)).await
     }
 
     /// Fetch feeds by spawning tasks
     async fn fetch_feeds_parallel(
         &self,
-        urls: &[FeedUrl],
+        feeds: &[(FeedUrl, Option<FeedCredential>)],
     ) -> Vec<FetchFeedResult<Arc<types::Feed>>> {
-        let mut handles = Vec::with_capacity(urls.len());
+        let mut handles = Vec::with_capacity(feeds.len());
 
-        for url in urls {
+        for (url, credential) in feeds {
             let this = self.clone();
             let url = url.clone();
-            handles.push(tokio::spawn(async move { this.fetch_feed(url).await }));
+            let credential = credential.clone();
+            handles.push(tokio::spawn(async move {
+                this.fetch(url, credential.as_ref()).await
+            }));
         }
 
         let mut results = Vec::with_capacity(handles.len());
@@ -140,3 +264,233 @@ where
         results
     }
 }
+
+impl<S> CacheLayer<S>
+where
+    S: FetchFeed,
+{
+    async fn fetch(
+        &self,
+        url: FeedUrl,
+        credential: Option<&FeedCredential>,
+    ) -> FetchFeedResult<Arc<types::Feed>> {
+        let key = CacheKey::new(url, credential);
+
+        // lookup cache
+        if let Some(cached) = self.cache.get(&key).await {
+            tracing::debug!(url = key.url.as_str(), "Feed cache hit");
+            self.counter.hit.fetch_add(1, Ordering::Relaxed);
+            return Ok(cached.feed);
+        }
+        self.counter.miss.fetch_add(1, Ordering::Relaxed);
+
+        let feed = match credential {
+            Some(credential) => {
+                self.service
+                    .fetch_private_feed(key.url.clone(), credential)
+                    .await
+            }
+            None => self.service.fetch_feed(key.url.clone()).await,
+        }
+        .map(Arc::new)?;
+
+        let cached = CachedFeed::new(Arc::clone(&feed));
+        let cached = match credential {
+            Some(credential) => cached.with_credential(credential.clone()),
+            None => cached,
+        };
+        self.cache.insert(key, cached).await;
+
+        Ok(feed)
+    }
+}
+
+#[async_trait]
+impl<S> ManageFeedCache for CacheLayer<S>
+where
+    S: FetchFeed + Clone + 'static,
+{
+    async fn stats(&self) -> CacheStats {
+        // Apply pending evictions so that counts reflect the current state
+        self.cache.run_pending_tasks().await;
+
+        CacheStats {
+            entry_count: self.cache.entry_count(),
+            weighted_size: self.cache.weighted_size(),
+            hit_count: self.counter.hit.load(Ordering::Relaxed),
+            miss_count: self.counter.miss.load(Ordering::Relaxed),
+        }
+    }
+
+    fn entries(&self) -> Vec<CacheEntry> {
+        let mut entries = self
+            .cache
+            .iter()
+            .map(|(key, cached)| CacheEntry {
+                url: key.url.clone(),
+                age: cached.age(),
+                private: key.credential.is_some(),
+            })
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by(|a, b| a.url.as_str().cmp(b.url.as_str()));
+        entries
+    }
+
+    #[tracing::instrument(skip_all, fields(%url))]
+    async fn refresh_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<types::Feed>> {
+        let feed = self.service.fetch_feed(url.clone()).await.map(Arc::new)?;
+
+        self.cache
+            .insert(CacheKey::new(url, None), CachedFeed::new(Arc::clone(&feed)))
+            .await;
+
+        Ok(feed)
+    }
+
+    async fn evict_feed(&self, url: &FeedUrl) -> bool {
+        let keys = self
+            .cache
+            .iter()
+            .filter(|(key, _)| &key.url == url)
+            .map(|(key, _)| Arc::unwrap_or_clone(key))
+            .collect::<Vec<_>>();
+
+        let mut evicted = false;
+        for key in keys {
+            evicted |= self.cache.remove(&key).await.is_some();
+        }
+        evicted
+    }
+
+    async fn evict_private_feed(&self, url: &FeedUrl, credential: &FeedCredential) -> bool {
+        self.cache
+            .remove(&CacheKey::new(url.clone(), Some(credential)))
+            .await
+            .is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{feed::service::FetchFeedResult, types::Feed};
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Fetcher;
+
+    impl Fetcher {
+        // Title is used to tell which credential the feed was fetched with
+        fn feed(url: FeedUrl, title: Option<&str>) -> Feed {
+            let feed = feed_rs::model::Feed {
+                feed_type: feed_rs::model::FeedType::Atom,
+                id: "ID".into(),
+                title: title.map(|title| feed_rs::model::Text {
+                    content_type: "text/plain".parse().unwrap(),
+                    src: None,
+                    content: title.to_owned(),
+                }),
+                updated: None,
+                authors: Vec::new(),
+                description: None,
+                links: Vec::new(),
+                categories: Vec::new(),
+                contributors: Vec::new(),
+                generator: None,
+                icon: None,
+                language: None,
+                logo: None,
+                published: None,
+                rating: None,
+                rights: None,
+                ttl: None,
+                entries: Vec::new(),
+            };
+            (url, feed).into()
+        }
+    }
+
+    #[async_trait]
+    impl FetchFeed for Fetcher {
+        async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed> {
+            Ok(Fetcher::feed(url, None))
+        }
+
+        async fn fetch_private_feed(
+            &self,
+            url: FeedUrl,
+            credential: &FeedCredential,
+        ) -> FetchFeedResult<Feed> {
+            let title = match credential {
+                FeedCredential::Basic { username, .. } => username,
+                FeedCredential::Bearer { token } => token,
+            };
+            Ok(Fetcher::feed(url, Some(title)))
+        }
+    }
+
+    #[tokio::test]
+    async fn manage_cache() {
+        let cache = CacheLayer::new(Fetcher);
+        let url = FeedUrl::parse("https://example.ymgyt.io/atom.xml").unwrap();
+
+        cache.fetch_feed(url.clone()).await.unwrap();
+        cache.fetch_feed(url.clone()).await.unwrap();
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entry_count, 1);
+        assert_eq!((stats.hit_count, stats.miss_count), (1, 1));
+        assert!((stats.hit_ratio() - 0.5).abs() < f64::EPSILON);
+
+        let entries = cache.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].url, url);
+
+        cache.refresh_feed(url.clone()).await.unwrap();
+        assert_eq!(cache.stats().await.miss_count, 1);
+
+        assert!(cache.evict_feed(&url).await);
+        assert!(!cache.evict_feed(&url).await);
+        assert_eq!(cache.stats().await.entry_count, 0);
+    }
+
+    #[tokio::test]
+    async fn private_feed_is_not_shared() {
+        let cache = CacheLayer::new(Fetcher);
+        let url = FeedUrl::parse("https://example.ymgyt.io/atom.xml").unwrap();
+        let alice = FeedCredential::Bearer {
+            token: "alice".into(),
+        };
+        let bob = FeedCredential::Bearer {
+            token: "bob".into(),
+        };
+
+        let public = cache.fetch_feed(url.clone()).await.unwrap();
+        let private = cache.fetch_private_feed(url.clone(), &alice).await.unwrap();
+        assert_eq!(public.meta().title(), None);
+        assert_eq!(private.meta().title(), Some("alice"));
+
+        let other = cache.fetch_private_feed(url.clone(), &bob).await.unwrap();
+        assert_eq!(other.meta().title(), Some("bob"));
+        let stats = cache.stats().await;
+        assert_eq!(stats.entry_count, 3);
+        assert_eq!((stats.hit_count, stats.miss_count), (0, 3));
+
+        let fetched = cache
+            .fetch_feeds_parallel(&[(url.clone(), Some(alice)), (url.clone(), None)])
+            .await;
+        assert_eq!(fetched[0].as_ref().unwrap().meta().title(), Some("alice"));
+        assert_eq!(fetched[1].as_ref().unwrap().meta().title(), None);
+        assert_eq!(cache.stats().await.hit_count, 2);
+
+        let entries = cache.entries();
+        assert_eq!(entries.iter().filter(|entry| entry.private).count(), 2);
+
+        assert!(cache.evict_private_feed(&url, &bob).await);
+        assert!(!cache.evict_private_feed(&url, &bob).await);
+        assert_eq!(cache.stats().await.entry_count, 2);
+
+        assert!(cache.evict_feed(&url).await);
+        assert_eq!(cache.stats().await.entry_count, 0);
+    }
+}
@@ -6,7 +6,7 @@ use tracing::{info, warn};
 
 use crate::feed::service::FetchFeed;
 
-use super::Cache;
+use super::{Cache, CachedFeed};
 
 pub struct PeriodicRefresher<S> {
     service: S,
@@ -52,15 +52,26 @@ where
     #[tracing::instrument(skip_all, name = "feed::cache::refresh")]
     async fn refresh(&self) {
         // It is safe to insert while iterating to cache.
-        for (feed_url, _) in &self.cache {
-            let feed_url = Arc::unwrap_or_clone(feed_url);
-            match self.service.fetch_feed(feed_url.clone()).await {
+        for (key, cached) in &self.cache {
+            let feed_url = key.url.clone();
+            let fetched = match cached.credential.as_ref() {
+                Some(credential) => self.service.fetch_private_feed(feed_url, credential).await,
+                None => self.service.fetch_feed(feed_url).await,
+            };
+            match fetched {
                 Ok(new_feed) => {
-                    self.cache.insert(feed_url, Arc::new(new_feed)).await;
+                    let new_cached = CachedFeed::new(Arc::new(new_feed));
+                    let new_cached = match cached.credential.clone() {
+                        Some(credential) => new_cached.with_credential(credential),
+                        None => new_cached,
+                    };
+                    self.cache
+                        .insert(Arc::unwrap_or_clone(key), new_cached)
+                        .await;
                 }
                 Err(err) => {
                     warn!(
-                        url = feed_url.as_str(),
+                        url = key.url.as_str(),
                         "Failed to refresh feed cache: {err}"
                     );
                 }
@@ -106,9 +117,11 @@ mod tests {
 
     use crate::{
         feed::service::FetchFeedResult,
-        types::{Feed, FeedUrl},
+        types::{Feed, FeedCredential, FeedUrl},
     };
 
+    use super::super::CacheKey;
+
     use super::*;
 
     #[derive(Clone)]
@@ -126,6 +139,14 @@ mod tests {
                 Ok(feed)
             }
         }
+
+        async fn fetch_private_feed(
+            &self,
+            url: FeedUrl,
+            _credential: &FeedCredential,
+        ) -> FetchFeedResult<Feed> {
+            self.fetch_feed(url).await
+        }
     }
 
     #[tokio::test]
@@ -133,12 +154,29 @@ mod tests {
         let cache = {
             let cache = Cache::new(1024);
             let (url, feed) = feed();
-            cache.insert(url.clone(), Arc::new(feed.clone())).await;
+            cache
+                .insert(
+                    CacheKey::new(url.clone(), None),
+                    CachedFeed::new(Arc::new(feed.clone())),
+                )
+                .await;
+
+            let credential = FeedCredential::Bearer {
+                token: "xxx".into(),
+            };
+            cache
+                .insert(
+                    CacheKey::new(url.clone(), Some(&credential)),
+                    CachedFeed::new(Arc::new(feed.clone())).with_credential(credential),
+                )
+                .await;
 
             let url2: Url = url.into();
             let url2 = url2.join("bad").unwrap();
             let url2: FeedUrl = url2.into();
-            cache.insert(url2, Arc::new(feed)).await;
+            cache
+                .insert(CacheKey::new(url2, None), CachedFeed::new(Arc::new(feed)))
+                .await;
             cache
         };
 
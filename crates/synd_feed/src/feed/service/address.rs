@@ -0,0 +1,295 @@
+use std::{
+    collections::HashSet,
+    error::Error as StdError,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
+
+use ipnet::IpNet;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use url::{Host, Url};
+
+use super::FetchFeedError;
+
+/// Policy on the addresses from which feeds are fetched.
+/// Private and reserved addresses are blocked unless they are explicitly allowed
+#[derive(Clone, Debug, Default)]
+pub struct AddressPolicy {
+    allowed_hosts: HashSet<String>,
+    allowed_networks: Vec<IpNet>,
+}
+
+/// Feed url resolved to the address which is not allowed by the policy
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("address {addr} of {host} is not allowed")]
+pub struct BlockedAddress {
+    pub host: String,
+    pub addr: IpAddr,
+}
+
+impl AddressPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow given host, ip address or network in CIDR notation.
+    /// ex. `feeds.internal`, `127.0.0.1`, `10.0.0.0/8`
+    #[must_use]
+    pub fn with_allowed(mut self, allowed: &str) -> Self {
+        if let Ok(network) = allowed.parse::<IpNet>() {
+            self.allowed_networks.push(network);
+        } else if let Ok(addr) = allowed.parse::<IpAddr>() {
+            self.allowed_networks.push(IpNet::from(addr));
+        } else {
+            self.allowed_hosts.insert(allowed.to_ascii_lowercase());
+        }
+        self
+    }
+
+    fn is_allowed_host(&self, host: &str) -> bool {
+        self.allowed_hosts.contains(&host.to_ascii_lowercase())
+    }
+
+    fn is_allowed_addr(&self, addr: IpAddr) -> bool {
+        !is_reserved(addr)
+            || self
+                .allowed_networks
+                .iter()
+                .any(|network| network.contains(&addr))
+    }
+
+    /// Check the url whose host is an ip address.
+    /// Those urls are connected without name resolution, so the resolver can not check them
+    pub(super) fn check_url(&self, url: &Url) -> Result<(), BlockedAddress> {
+        let addr = match url.host() {
+            Some(Host::Ipv4(addr)) => IpAddr::V4(addr),
+            Some(Host::Ipv6(addr)) => IpAddr::V6(addr),
+            Some(Host::Domain(_)) | None => return Ok(()),
+        };
+        if self.is_allowed_addr(addr) {
+            Ok(())
+        } else {
+            Err(BlockedAddress {
+                host: addr.to_string(),
+                addr,
+            })
+        }
+    }
+
+    /// Resolve the host of the url and check the addresses.
+    /// Used when the host is resolved by the proxy instead of the resolver
+    pub(super) async fn check_resolved_url(&self, url: &Url) -> Result<(), FetchFeedError> {
+        let Some(Host::Domain(host)) = url.host() else {
+            return self.check_url(url).map_err(FetchFeedError::Blocked);
+        };
+        if self.is_allowed_host(host) {
+            return Ok(());
+        }
+        let addrs = tokio::net::lookup_host((host, url.port_or_known_default().unwrap_or(0)))
+            .await?
+            .collect::<Vec<_>>();
+        self.check_addrs(host, &addrs)
+            .map_err(FetchFeedError::Blocked)
+    }
+
+    fn check_addrs(&self, host: &str, addrs: &[SocketAddr]) -> Result<(), BlockedAddress> {
+        if self.is_allowed_host(host) {
+            return Ok(());
+        }
+        // Reject the host even if only some of the addresses are blocked,
+        // otherwise the internal network is reachable by mixing public addresses
+        match addrs.iter().find(|addr| !self.is_allowed_addr(addr.ip())) {
+            Some(addr) => Err(BlockedAddress {
+                host: host.to_owned(),
+                addr: addr.ip(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Find the blocked address in the source chain of the request error
+    pub(super) fn blocked_address(err: &reqwest::Error) -> Option<BlockedAddress> {
+        let mut source = err.source();
+        while let Some(err) = source {
+            if let Some(blocked) = err.downcast_ref::<BlockedAddress>() {
+                return Some(blocked.clone());
+            }
+            source = err.source();
+        }
+        None
+    }
+}
+
+/// Resolver which applies the policy to the resolved addresses.
+/// Since the checked addresses are the ones to connect, the check can not be bypassed by dns rebinding
+pub(super) struct PolicyResolver {
+    policy: Arc<AddressPolicy>,
+}
+
+impl PolicyResolver {
+    pub(super) fn new(policy: Arc<AddressPolicy>) -> Self {
+        Self { policy }
+    }
+}
+
+impl Resolve for PolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = Arc::clone(&self.policy);
+        let host = name.as_str().to_owned();
+        Box::pin(async move {
+            // Port is replaced with the one of the url
+            let addrs = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .collect::<Vec<_>>();
+            policy.check_addrs(&host, &addrs)?;
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Return true if the address is not globally reachable
+fn is_reserved(addr: IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(addr) => is_reserved_v4(addr),
+        IpAddr::V6(addr) => {
+            // Addresses which embed an ipv4 address are checked as ipv4.
+            // ipv4-mapped `::ffff:a.b.c.d` and ipv4-compatible `::a.b.c.d` including `::` and `::1`
+            if let Some(v4) = addr.to_ipv4() {
+                return is_reserved_v4(v4);
+            }
+            let [.., a, b, c, d] = addr.octets();
+            match addr.segments() {
+                // NAT64 64:ff9b::/96
+                [0x64, 0xff9b, 0, 0, 0, 0, _, _] => is_reserved_v4(Ipv4Addr::new(a, b, c, d)),
+                // 6to4 2002::/16 embeds the ipv4 address in the following 32 bits
+                [0x2002, high, low, ..] => {
+                    let [a, b] = high.to_be_bytes();
+                    let [c, d] = low.to_be_bytes();
+                    is_reserved_v4(Ipv4Addr::new(a, b, c, d))
+                }
+                _ => is_reserved_v6(addr),
+            }
+        }
+    }
+}
+
+fn is_reserved_v4(addr: Ipv4Addr) -> bool {
+    let [a, b, c, _] = addr.octets();
+    addr.is_unspecified()
+        || addr.is_loopback()
+        || addr.is_private()
+        || addr.is_link_local()
+        || addr.is_broadcast()
+        || addr.is_documentation()
+        || addr.is_multicast()
+        // "This network" 0.0.0.0/8
+        || a == 0
+        // Shared address space 100.64.0.0/10
+        || (a == 100 && (b & 0b1100_0000) == 64)
+        // IETF protocol assignments 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking 198.18.0.0/15
+        || (a == 198 && (b & 0xfe) == 18)
+        // Reserved 240.0.0.0/4
+        || a >= 240
+}
+
+fn is_reserved_v6(addr: Ipv6Addr) -> bool {
+    let [first, second, ..] = addr.segments();
+    addr.is_unspecified()
+        || addr.is_loopback()
+        || addr.is_multicast()
+        || addr.is_unique_local()
+        || addr.is_unicast_link_local()
+        // Documentation 2001:db8::/32
+        || (first == 0x2001 && second == 0x0db8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserved_addresses() {
+        let reserved = [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fc00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+            "::127.0.0.1",
+            "::a9fe:a9fe",
+            "2002:7f00:1::",
+            "2002:c0a8:101::1",
+        ];
+        for addr in reserved {
+            assert!(is_reserved(addr.parse().unwrap()), "{addr}");
+        }
+
+        let global = [
+            "93.184.216.34",
+            "1.1.1.1",
+            "2606:4700::1111",
+            "::1.1.1.1",
+            "2002:101:101::1",
+        ];
+        for addr in global {
+            assert!(!is_reserved(addr.parse().unwrap()), "{addr}");
+        }
+    }
+
+    #[test]
+    fn allowlist() {
+        let policy = AddressPolicy::new()
+            .with_allowed("Feeds.Internal")
+            .with_allowed("10.0.0.0/8")
+            .with_allowed("::1");
+
+        assert!(policy.is_allowed_addr("10.1.2.3".parse().unwrap()));
+        assert!(policy.is_allowed_addr("::1".parse().unwrap()));
+        assert!(!policy.is_allowed_addr("192.168.1.1".parse().unwrap()));
+
+        let loopback = [SocketAddr::from(([127, 0, 0, 1], 0))];
+        assert!(policy.check_addrs("feeds.internal", &loopback).is_ok());
+        let err = policy.check_addrs("evil.example", &loopback).unwrap_err();
+        assert_eq!(err.host, "evil.example");
+
+        // Mixed public and private addresses are rejected
+        let mixed = [
+            SocketAddr::from(([1, 1, 1, 1], 0)),
+            SocketAddr::from(([192, 168, 1, 1], 0)),
+        ];
+        assert!(policy.check_addrs("mixed.example", &mixed).is_err());
+    }
+
+    #[test]
+    fn check_ip_url() {
+        let policy = AddressPolicy::new();
+        let blocked = [
+            "http://127.0.0.1/feed.xml",
+            "http://[::1]:8080/feed.xml",
+            "http://169.254.169.254/latest/meta-data",
+        ];
+        for url in blocked {
+            assert!(
+                policy.check_url(&Url::parse(url).unwrap()).is_err(),
+                "{url}"
+            );
+        }
+        assert!(
+            policy
+                .check_url(&Url::parse("https://blog.example.com/feed.xml").unwrap())
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,606 @@
+use std::{borrow::Borrow, collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use feed_rs::parser::{ParseErrorKind, ParseFeedError, Parser};
+use reqwest::{
+    Certificate, Proxy,
+    header::{AUTHORIZATION, HeaderMap, LOCATION},
+    redirect,
+};
+use url::Url;
+
+use crate::types::{Feed, FeedCredential, FeedUrl};
+
+mod address;
+use address::PolicyResolver;
+pub use address::{AddressPolicy, BlockedAddress};
+
+pub type FetchFeedResult<T> = std::result::Result<T, FetchFeedError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum FetchFeedError {
+    #[error("fetch failed")]
+    Fetch(#[from] reqwest::Error),
+    #[error("blocked: {0}")]
+    Blocked(BlockedAddress),
+    #[error("response size limit exceeded")]
+    ResponseLimitExceed,
+    #[error("invalid feed: {0}")]
+    InvalidFeed(ParseErrorKind),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json format error: {0}")]
+    JsonFormat(#[from] serde_json::Error),
+    #[error("unsupported json version: {0}")]
+    JsonUnsupportedVersion(String),
+    #[error("xml format error: {0}")]
+    XmlFormat(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl FetchFeedError {
+    /// Distinguish the requests blocked by the address policy from other failures
+    fn from_request(err: reqwest::Error) -> Self {
+        match AddressPolicy::blocked_address(&err) {
+            Some(blocked) => FetchFeedError::Blocked(blocked),
+            None => FetchFeedError::Fetch(err),
+        }
+    }
+}
+
+impl From<ParseFeedError> for FetchFeedError {
+    fn from(err: ParseFeedError) -> Self {
+        match err {
+            ParseFeedError::ParseError(kind) => FetchFeedError::InvalidFeed(kind),
+            ParseFeedError::IoError(io_err) => FetchFeedError::Io(io_err),
+            ParseFeedError::JsonSerde(json_err) => FetchFeedError::JsonFormat(json_err),
+            ParseFeedError::JsonUnsupportedVersion(version) => {
+                FetchFeedError::JsonUnsupportedVersion(version)
+            }
+            ParseFeedError::XmlReader(xml_err) => FetchFeedError::XmlFormat(format!("{xml_err}")),
+        }
+    }
+}
+
+#[async_trait]
+pub trait FetchFeed: Send + Sync {
+    async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed>;
+    /// Fetch the feed which requires authentication
+    async fn fetch_private_feed(
+        &self,
+        url: FeedUrl,
+        credential: &FeedCredential,
+    ) -> FetchFeedResult<Feed>;
+}
+
+#[async_trait]
+impl<T> FetchFeed for Arc<T>
+where
+    T: FetchFeed,
+{
+    async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed> {
+        self.fetch_feed(url).await
+    }
+
+    async fn fetch_private_feed(
+        &self,
+        url: FeedUrl,
+        credential: &FeedCredential,
+    ) -> FetchFeedResult<Feed> {
+        self.fetch_private_feed(url, credential).await
+    }
+}
+
+/// Configuration of the http client which fetches feeds
+#[derive(Clone, Debug)]
+pub struct FetchConfig {
+    timeout: Duration,
+    connect_timeout: Duration,
+    proxy: Option<Url>,
+    root_certificates: Vec<Certificate>,
+    host_headers: HashMap<String, HeaderMap>,
+    address_policy: Option<AddressPolicy>,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(10),
+            proxy: None,
+            root_certificates: Vec::new(),
+            host_headers: HashMap::new(),
+            address_policy: None,
+        }
+    }
+}
+
+impl FetchConfig {
+    #[must_use]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    #[must_use]
+    pub fn with_connect_timeout(self, connect_timeout: Duration) -> Self {
+        Self {
+            connect_timeout,
+            ..self
+        }
+    }
+
+    /// Send all requests through the proxy
+    #[must_use]
+    pub fn with_proxy(self, proxy: Url) -> Self {
+        Self {
+            proxy: Some(proxy),
+            ..self
+        }
+    }
+
+    /// Trust the certificate in addition to the built-in root certificates
+    #[must_use]
+    pub fn with_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Add the headers to the requests to the host
+    #[must_use]
+    pub fn with_host_headers(mut self, host: impl Into<String>, mut headers: HeaderMap) -> Self {
+        // Headers like authorization or cookie should not be logged
+        headers
+            .values_mut()
+            .for_each(|value| value.set_sensitive(true));
+        self.host_headers
+            .entry(host.into().to_ascii_lowercase())
+            .or_default()
+            .extend(headers);
+        self
+    }
+
+    /// Block private and reserved addresses except the ones allowed by the policy.
+    /// The addresses are checked after name resolution and on every redirect
+    #[must_use]
+    pub fn with_address_policy(self, policy: AddressPolicy) -> Self {
+        Self {
+            address_policy: Some(policy),
+            ..self
+        }
+    }
+}
+
+/// Feed Process entry point
+#[derive(Clone)]
+pub struct FeedService {
+    http: reqwest::Client,
+    buff_limit: usize,
+    host_headers: Arc<HashMap<String, HeaderMap>>,
+    address_policy: Option<Arc<AddressPolicy>>,
+    // The proxy resolves the host instead of the resolver,
+    // so the addresses are resolved and checked before each request including redirects
+    resolve_before_request: bool,
+}
+
+#[async_trait]
+impl FetchFeed for FeedService {
+    async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed> {
+        self.fetch(url, None).await
+    }
+
+    async fn fetch_private_feed(
+        &self,
+        url: FeedUrl,
+        credential: &FeedCredential,
+    ) -> FetchFeedResult<Feed> {
+        self.fetch(url, Some(credential)).await
+    }
+}
+
+impl FeedService {
+    const MAX_REDIRECTS: usize = 10;
+
+    async fn fetch(
+        &self,
+        url: FeedUrl,
+        credential: Option<&FeedCredential>,
+    ) -> FetchFeedResult<Feed> {
+        use futures_util::StreamExt;
+        if let Some(policy) = self.address_policy.as_ref() {
+            policy
+                .check_url(url.borrow())
+                .map_err(FetchFeedError::Blocked)?;
+        }
+
+        let response = self.send_resolved(url.borrow(), credential).await?;
+
+        let mut stream = response
+            .error_for_status()
+            .map_err(FetchFeedError::Fetch)?
+            .bytes_stream();
+
+        let mut buff = Vec::new();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(FetchFeedError::Fetch)?;
+            if buff.len() + chunk.len() > self.buff_limit {
+                return Err(FetchFeedError::ResponseLimitExceed);
+            }
+            buff.extend(chunk);
+        }
+
+        self.parse(url, buff.as_slice())
+    }
+
+    pub fn new(user_agent: &str, buff_limit: usize) -> Self {
+        Self::from_config(user_agent, buff_limit, FetchConfig::default()).unwrap()
+    }
+
+    pub fn from_config(
+        user_agent: &str,
+        buff_limit: usize,
+        FetchConfig {
+            timeout,
+            connect_timeout,
+            proxy,
+            root_certificates,
+            host_headers,
+            address_policy,
+        }: FetchConfig,
+    ) -> Result<Self, reqwest::Error> {
+        let mut builder = reqwest::ClientBuilder::new()
+            .user_agent(user_agent)
+            .timeout(timeout)
+            .connect_timeout(connect_timeout);
+        for certificate in root_certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        // Redirects are followed by `send_resolved` to apply the headers of each host
+        builder = builder.redirect(redirect::Policy::none());
+
+        let address_policy = address_policy.map(Arc::new);
+        // The proxy is configured by the operator, so the address of the proxy is not checked
+        let resolve_before_request = address_policy.is_some() && proxy.is_some();
+        if let Some(policy) = address_policy.as_ref().filter(|_| !resolve_before_request) {
+            builder = builder.dns_resolver(Arc::new(PolicyResolver::new(Arc::clone(policy))));
+        }
+
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+
+        Ok(Self {
+            http: builder.build()?,
+            buff_limit,
+            host_headers: Arc::new(host_headers),
+            address_policy,
+            resolve_before_request,
+        })
+    }
+
+    /// Send the request following redirects up to the same limit as the default policy.
+    /// The headers of the host are applied to each request, as reqwest copies the headers to the redirected hosts.
+    /// The redirected urls are checked by the address policy, and through the proxy the resolved addresses are also checked.
+    /// As the proxy resolves the host again, the check can be bypassed by dns rebinding
+    async fn send_resolved(
+        &self,
+        url: &Url,
+        credential: Option<&FeedCredential>,
+    ) -> FetchFeedResult<reqwest::Response> {
+        let policy = self.address_policy.as_deref();
+        let mut current = url.clone();
+        for _ in 0..=Self::MAX_REDIRECTS {
+            if let Some(policy) = policy.filter(|_| self.resolve_before_request) {
+                policy.check_resolved_url(&current).await?;
+            }
+
+            // Like reqwest, the credential is not sent to other origins on redirect
+            let credential = credential.filter(|_| current.origin() == url.origin());
+            let response = self
+                .request(&current, credential)
+                .send()
+                .await
+                .map_err(FetchFeedError::from_request)?;
+
+            let location = response
+                .status()
+                .is_redirection()
+                .then(|| response.headers().get(LOCATION))
+                .flatten()
+                .and_then(|location| location.to_str().ok());
+            let Some(location) = location else {
+                return Ok(response);
+            };
+            current = current.join(location).map_err(|err| {
+                FetchFeedError::Other(anyhow::anyhow!("redirect location: {err}"))
+            })?;
+            if let Some(policy) = policy {
+                policy
+                    .check_url(&current)
+                    .map_err(FetchFeedError::Blocked)?;
+            }
+        }
+        Err(FetchFeedError::Other(anyhow::anyhow!("too many redirects")))
+    }
+
+    fn request(&self, url: &Url, credential: Option<&FeedCredential>) -> reqwest::RequestBuilder {
+        let mut request = self.http.get(url.clone());
+
+        if let Some(credential) = credential {
+            request = match credential {
+                FeedCredential::Basic { username, password } => {
+                    request.basic_auth(username, password.as_ref())
+                }
+                FeedCredential::Bearer { token } => request.bearer_auth(token),
+            };
+        }
+
+        // Url parser normalizes the host in lowercase
+        if let Some(headers) = url.host_str().and_then(|host| self.host_headers.get(host)) {
+            let mut headers = headers.clone();
+            // Credential of the subscription takes precedence over the host configuration
+            if credential.is_some() {
+                headers.remove(AUTHORIZATION);
+            }
+            request = request.headers(headers);
+        }
+
+        request
+    }
+
+    pub fn parse<S>(&self, url: FeedUrl, source: S) -> FetchFeedResult<Feed>
+    where
+        S: std::io::Read,
+    {
+        let parser = Self::build_parser(&url);
+
+        parser
+            .parse(source)
+            .map(|feed| Feed::from((url, feed)))
+            .map_err(FetchFeedError::from)
+    }
+
+    fn build_parser(base_uri: impl AsRef<str>) -> Parser {
+        feed_rs::parser::Builder::new()
+            .base_uri(Some(base_uri))
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{AUTHORIZATION, COOKIE, HeaderValue};
+
+    use super::*;
+
+    #[test]
+    fn inject_host_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer xxx"));
+        headers.insert(COOKIE, HeaderValue::from_static("session=yyy"));
+        let config = FetchConfig::default()
+            .with_timeout(Duration::from_secs(3))
+            .with_proxy(Url::parse("http://proxy.example.com:3128").unwrap())
+            .with_host_headers("Internal.example.com", headers);
+        let service = FeedService::from_config("synd", 1024, config).unwrap();
+
+        let request = service
+            .request(
+                &Url::parse("https://internal.example.com/feed.xml").unwrap(),
+                None,
+            )
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()[AUTHORIZATION], "Bearer xxx");
+        assert_eq!(request.headers()[COOKIE], "session=yyy");
+        assert!(request.headers()[AUTHORIZATION].is_sensitive());
+
+        let request = service
+            .request(
+                &Url::parse("https://blog.example.com/feed.xml").unwrap(),
+                None,
+            )
+            .build()
+            .unwrap();
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+
+        // Credential overrides the authorization header of the host
+        let request = service
+            .request(
+                &Url::parse("https://internal.example.com/feed.xml").unwrap(),
+                Some(&FeedCredential::Bearer {
+                    token: "private".into(),
+                }),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(request.headers().get_all(AUTHORIZATION).iter().count(), 1);
+        assert_eq!(request.headers()[AUTHORIZATION], "Bearer private");
+        assert_eq!(request.headers()[COOKIE], "session=yyy");
+    }
+
+    #[tokio::test]
+    async fn block_private_address() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Redirect every request to the loopback address
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0_u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 302 Found\r\nlocation: http://127.0.0.1:{port}/private\r\ncontent-length: 0\r\n\r\n"
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let fetch = |policy: AddressPolicy, url: String| async move {
+            let config = FetchConfig::default().with_address_policy(policy);
+            let service = FeedService::from_config("synd", 1024, config).unwrap();
+            service.fetch_feed(FeedUrl::parse(&url).unwrap()).await
+        };
+
+        // Blocked after name resolution
+        let err = fetch(
+            AddressPolicy::new(),
+            format!("http://localhost:{port}/feed.xml"),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, FetchFeedError::Blocked(ref blocked) if blocked.host == "localhost"));
+
+        // Blocked without name resolution
+        let err = fetch(
+            AddressPolicy::new(),
+            format!("http://127.0.0.1:{port}/feed.xml"),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, FetchFeedError::Blocked(_)));
+
+        // Allowed host can not redirect to the blocked address
+        let err = fetch(
+            AddressPolicy::new().with_allowed("localhost"),
+            format!("http://localhost:{port}/feed.xml"),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, FetchFeedError::Blocked(ref blocked) if blocked.host == "127.0.0.1"));
+
+        // Allowed network is fetched until the redirects exceed the limit
+        let err = fetch(
+            AddressPolicy::new().with_allowed("127.0.0.0/8"),
+            format!("http://localhost:{port}/feed.xml"),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(err, FetchFeedError::Other(ref err) if err.to_string() == "too many redirects"),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn block_private_address_through_proxy() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Proxy which redirects every request to the loopback address
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0_u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::Relaxed);
+                let response = "HTTP/1.1 302 Found\r\nlocation: http://127.0.0.1/private\r\ncontent-length: 0\r\n\r\n";
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let fetch = |policy: AddressPolicy, url: &str| {
+            let config = FetchConfig::default()
+                .with_proxy(Url::parse(&format!("http://127.0.0.1:{port}")).unwrap())
+                .with_address_policy(policy);
+            let service = FeedService::from_config("synd", 1024, config).unwrap();
+            let url = FeedUrl::parse(url).unwrap();
+            async move { service.fetch_feed(url).await }
+        };
+
+        // Resolved and blocked before the request is sent to the proxy
+        let err = fetch(AddressPolicy::new(), "http://localhost/feed.xml")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FetchFeedError::Blocked(ref blocked) if blocked.host == "localhost"));
+        assert_eq!(requests.load(Ordering::Relaxed), 0);
+
+        // Redirected url is also checked
+        let err = fetch(
+            AddressPolicy::new().with_allowed("localhost"),
+            "http://localhost/feed.xml",
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, FetchFeedError::Blocked(ref blocked) if blocked.host == "127.0.0.1"));
+        assert_eq!(requests.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn host_headers_are_not_sent_to_redirected_host() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            sync::mpsc,
+        };
+
+        // Record the request and respond with the response
+        async fn serve(response: String) -> (u16, mpsc::UnboundedReceiver<String>) {
+            let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+                .await
+                .unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let (tx, rx) = mpsc::unbounded_channel();
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let mut buf = [0_u8; 4096];
+                    let n = stream.read(&mut buf).await.unwrap_or_default();
+                    let _ = tx.send(String::from_utf8_lossy(&buf[..n]).to_ascii_lowercase());
+                    let _ = stream.write_all(response.as_bytes()).await;
+                }
+            });
+            (port, rx)
+        }
+
+        let feed = r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>urn:feed</id><title>feed</title><updated>2024-01-01T00:00:00Z</updated></feed>"#;
+        let (other, mut other_requests) = serve(format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/atom+xml\r\ncontent-length: {}\r\n\r\n{feed}",
+            feed.len()
+        ))
+        .await;
+        let (port, mut requests) = serve(format!(
+            "HTTP/1.1 302 Found\r\nlocation: http://127.0.0.1:{other}/feed.xml\r\ncontent-length: 0\r\n\r\n"
+        ))
+        .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("secret"));
+        let config = FetchConfig::default().with_host_headers("localhost", headers);
+        let service = FeedService::from_config("synd", 1024, config).unwrap();
+
+        service
+            .fetch_feed(FeedUrl::parse(&format!("http://localhost:{port}/feed.xml")).unwrap())
+            .await
+            .unwrap();
+
+        assert!(requests.recv().await.unwrap().contains("x-api-key: secret"));
+        assert!(!other_requests.recv().await.unwrap().contains("x-api-key"));
+    }
+
+    #[test]
+    fn from_feed_rs_parse_feed_error() {
+        assert!(matches!(
+            FetchFeedError::from(ParseFeedError::ParseError(ParseErrorKind::NoFeedRoot)),
+            FetchFeedError::InvalidFeed(_)
+        ));
+        assert!(matches!(
+            FetchFeedError::from(ParseFeedError::IoError(std::io::Error::from(
+                std::io::ErrorKind::UnexpectedEof
+            ))),
+            FetchFeedError::Io(_)
+        ));
+        assert!(matches!(
+            FetchFeedError::from(ParseFeedError::JsonUnsupportedVersion("dummy".into())),
+            FetchFeedError::JsonUnsupportedVersion(_)
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use url::Url;
+
+/// Normalize the link so that the links of the same article published in different feeds are equal.
+/// Scheme, `www.` prefix, fragment, tracking parameters and trailing slash are ignored
+pub(super) fn canonical_url(link: &str) -> Option<String> {
+    let mut url = Url::parse(link).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    url.set_fragment(None);
+
+    let query = url
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_"))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+    if query.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(query);
+    }
+
+    let host = url.host_str()?;
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    let path = url.path().trim_end_matches('/');
+    Some(match url.query() {
+        Some(query) => format!("{host}{path}?{query}"),
+        None => format!("{host}{path}"),
+    })
+}
+
+/// Digest of the title and the text which is not affected by the markup and the whitespaces.
+/// The digest is only comparable within the process
+pub(super) fn content_digest(title: Option<&str>, text: &str) -> Option<u64> {
+    let mut words = text.split_whitespace().peekable();
+    words.peek()?;
+
+    let mut hasher = DefaultHasher::new();
+    title.map(str::trim).hash(&mut hasher);
+    words.for_each(|word| word.hash(&mut hasher));
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_url() {
+        let canonical = canonical_url("https://blog.example.com/posts/1/").unwrap();
+        assert_eq!(canonical, "blog.example.com/posts/1");
+
+        for same in [
+            "http://blog.example.com/posts/1",
+            "https://www.blog.example.com/posts/1#comments",
+            "https://blog.example.com/posts/1?utm_source=planet&utm_medium=rss",
+        ] {
+            assert_eq!(
+                canonical_url(same).as_deref(),
+                Some(canonical.as_str()),
+                "{same}"
+            );
+        }
+
+        assert_eq!(
+            canonical_url("https://blog.example.com/post?id=1&utm_source=rss").as_deref(),
+            Some("blog.example.com/post?id=1")
+        );
+        assert_ne!(
+            canonical_url("https://blog.example.com/post?id=1"),
+            canonical_url("https://blog.example.com/post?id=2")
+        );
+        assert_eq!(canonical_url("mailto:someone@example.com"), None);
+        assert_eq!(canonical_url("not a url"), None);
+    }
+
+    #[test]
+    fn digest() {
+        let digest = content_digest(Some("Title"), "Hello  world\n").unwrap();
+        assert_eq!(content_digest(Some(" Title "), "Hello world"), Some(digest));
+        assert_ne!(content_digest(Some("Other"), "Hello world"), Some(digest));
+        assert_eq!(content_digest(Some("Title"), " \n "), None);
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt::Write as _;
+
+/// Width used when the text is not wrapped
+/// Large enough for paragraphs to fit in a line, so that clients can wrap them to their width
+const UNWRAPPED_WIDTH: usize = 4096;
+
+/// Content of the entry written by the publisher, which is html or plain text
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Content<'a> {
+    source: &'a str,
+    is_html: bool,
+}
+
+impl<'a> Content<'a> {
+    pub(super) fn new(source: &'a str, content_type: &str) -> Self {
+        // Atom text constructs are parsed as text/plain, text/html or application/xhtml+xml
+        // Rss does not specify the type, so the content is assumed to be html
+        Self {
+            source,
+            is_html: content_type != "text/plain",
+        }
+    }
+
+    /// Remove scripts, styles and other elements which are not safe to embed
+    pub(super) fn sanitized_html(self) -> String {
+        if self.is_html {
+            ammonia::clean(self.source)
+        } else {
+            escape_html(self.source)
+        }
+    }
+
+    /// Render as plain text with markdown like decorations.
+    /// Links are collected as footnotes at the end of the text.
+    /// If width is not given, only explicit line breaks are kept
+    pub(super) fn text(self, width: Option<usize>) -> String {
+        if !self.is_html {
+            return self.source.to_owned();
+        }
+        let width = width.unwrap_or(UNWRAPPED_WIDTH).clamp(1, UNWRAPPED_WIDTH);
+        match html2text::config::plain()
+            .allow_width_overflow()
+            .string_from_read(self.source.as_bytes(), width)
+        {
+            Ok(text) => text,
+            Err(err) => {
+                tracing::warn!("Failed to render html as text: {err}");
+                self.source.to_owned()
+            }
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                c => {
+                    let _ = escaped.write_char(c);
+                }
+            }
+            escaped
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTML: &str = r#"<p>Hello <strong>world</strong> &amp; <a href="https://example.com/a" onclick="alert(1)">link</a></p><script>alert("xss")</script><style>p { color: red }</style>"#;
+
+    #[test]
+    fn sanitize() {
+        let html = Content::new(HTML, "text/html").sanitized_html();
+        assert!(!html.contains("script"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("color: red"));
+        assert!(html.contains(r#"href="https://example.com/a""#));
+        assert!(html.contains("<strong>world</strong>"));
+
+        let plain = Content::new("1 < 2 & <b>", "text/plain").sanitized_html();
+        assert_eq!(plain, "1 &lt; 2 &amp; &lt;b&gt;");
+    }
+
+    #[test]
+    fn text_with_footnotes() {
+        let text = Content::new(HTML, "text/html").text(None);
+        assert_eq!(
+            text,
+            "Hello **world** & [link][1]\n\n[1]: https://example.com/a\n"
+        );
+
+        // Plain text is returned as is
+        let text = Content::new("<b>not html</b>", "text/plain").text(None);
+        assert_eq!(text, "<b>not html</b>");
+    }
+
+    #[test]
+    fn text_wrapping() {
+        let html = "<p>aaa bbb ccc ddd</p>";
+        assert_eq!(
+            Content::new(html, "text/html").text(Some(8)),
+            "aaa bbb\nccc ddd\n"
+        );
+        assert_eq!(
+            Content::new(html, "text/html").text(None),
+            "aaa bbb ccc ddd\n"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::{self, Write as _};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Credential to fetch the feed which requires authentication
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FeedCredential {
+    /// Http basic authentication
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    /// Bearer token in the authorization header
+    Bearer { token: String },
+}
+
+impl FeedCredential {
+    /// Return the identity of the credential which does not reveal the secret
+    pub fn identity(&self) -> CredentialIdentity {
+        let mut hasher = Sha256::new();
+        match self {
+            FeedCredential::Basic { username, password } => {
+                hasher.update(b"basic\0");
+                hasher.update(username.as_bytes());
+                hasher.update(b"\0");
+                hasher.update(password.as_deref().unwrap_or_default().as_bytes());
+            }
+            FeedCredential::Bearer { token } => {
+                hasher.update(b"bearer\0");
+                hasher.update(token.as_bytes());
+            }
+        }
+        CredentialIdentity(hasher.finalize().into())
+    }
+}
+
+// Avoid leaking the secret in logs
+impl fmt::Debug for FeedCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeedCredential::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .finish_non_exhaustive(),
+            FeedCredential::Bearer { .. } => f.debug_struct("Bearer").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Hash of the credential to distinguish the feeds fetched with different credentials
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CredentialIdentity([u8; 32]);
+
+impl fmt::Debug for CredentialIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CredentialIdentity({self})")
+    }
+}
+
+impl fmt::Display for CredentialIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Prefix is enough to identify in logs
+        self.0[..4].iter().try_for_each(|b| write!(f, "{b:02x}"))?;
+        f.write_char('…')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity() {
+        let basic = FeedCredential::Basic {
+            username: "user".into(),
+            password: Some("pass".into()),
+        };
+        assert_eq!(basic.identity(), basic.clone().identity());
+        assert_ne!(
+            basic.identity(),
+            FeedCredential::Basic {
+                username: "user".into(),
+                password: Some("other".into()),
+            }
+            .identity()
+        );
+        assert_ne!(
+            FeedCredential::Bearer {
+                token: "user".into()
+            }
+            .identity(),
+            FeedCredential::Basic {
+                username: "user".into(),
+                password: None,
+            }
+            .identity()
+        );
+    }
+
+    #[test]
+    fn debug_does_not_reveal_secret() {
+        let debug = format!(
+            "{:?}",
+            FeedCredential::Basic {
+                username: "user".into(),
+                password: Some("secret".into()),
+            }
+        );
+        assert!(!debug.contains("secret"));
+        let debug = format!(
+            "{:?}",
+            FeedCredential::Bearer {
+                token: "secret".into()
+            }
+        );
+        assert!(!debug.contains("secret"));
+    }
+
+    #[test]
+    fn serde() {
+        let credential = FeedCredential::Bearer {
+            token: "xxx".into(),
+        };
+        let json = serde_json::to_string(&credential).unwrap();
+        assert_eq!(json, r#"{"type":"bearer","token":"xxx"}"#);
+        assert_eq!(
+            serde_json::from_str::<FeedCredential>(&json).unwrap(),
+            credential
+        );
+    }
+}
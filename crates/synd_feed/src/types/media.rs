@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use feed_rs::model::{self as feedrs};
+
+/// Media file attached to the entry, such as the audio of a podcast episode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enclosure {
+    pub url: String,
+    pub mime_type: Option<String>,
+    /// Size in bytes
+    pub length: Option<u64>,
+    pub duration: Option<Duration>,
+}
+
+/// Podcast metadata of the entry.
+/// Itunes elements are parsed as media rss, so both of them are reflected
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Episode {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub authors: Vec<String>,
+    pub duration: Option<Duration>,
+}
+
+impl Episode {
+    fn is_empty(&self) -> bool {
+        self == &Episode::default()
+    }
+}
+
+/// Rss enclosure and media rss contents are parsed as media objects,
+/// while atom enclosures remain as links
+pub(super) fn enclosures(entry: &feedrs::Entry) -> Vec<Enclosure> {
+    let mut enclosures: Vec<Enclosure> = entry
+        .media
+        .iter()
+        .flat_map(|media| {
+            media.content.iter().filter_map(|content| {
+                Some(Enclosure {
+                    url: content.url.as_ref()?.to_string(),
+                    mime_type: content.content_type.as_ref().map(ToString::to_string),
+                    length: content.size,
+                    duration: content.duration.or(media.duration),
+                })
+            })
+        })
+        .collect();
+
+    for link in &entry.links {
+        if link.rel.as_deref() != Some("enclosure")
+            || enclosures
+                .iter()
+                .any(|enclosure| enclosure.url == link.href)
+        {
+            continue;
+        }
+        enclosures.push(Enclosure {
+            url: link.href.clone(),
+            mime_type: link.media_type.clone(),
+            length: link.length,
+            duration: None,
+        });
+    }
+
+    enclosures
+}
+
+pub(super) fn thumbnails(entry: &feedrs::Entry) -> impl Iterator<Item = &str> {
+    entry
+        .media
+        .iter()
+        .flat_map(|media| media.thumbnails.iter())
+        .map(|thumbnail| thumbnail.image.uri.as_str())
+}
+
+pub(super) fn episode(entry: &feedrs::Entry) -> Option<Episode> {
+    let episode = entry
+        .media
+        .iter()
+        .fold(Episode::default(), |mut episode, media| {
+            if episode.title.is_none() {
+                episode.title = media.title.as_ref().map(|text| text.content.clone());
+            }
+            if episode.description.is_none() {
+                episode.description = media.description.as_ref().map(|text| text.content.clone());
+            }
+            if episode.duration.is_none() {
+                episode.duration = media
+                    .duration
+                    .or_else(|| media.content.iter().find_map(|content| content.duration));
+            }
+            episode
+                .authors
+                .extend(media.credits.iter().map(|credit| credit.entity.clone()));
+            episode
+        });
+
+    (!episode.is_empty()).then_some(episode)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Feed, FeedUrl};
+
+    use super::*;
+
+    const PODCAST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+  <channel>
+    <title>Podcast</title>
+    <link>https://podcast.example.com</link>
+    <itunes:image href="https://podcast.example.com/cover.jpg"/>
+    <itunes:explicit>true</itunes:explicit>
+    <item>
+      <title>Episode 1</title>
+      <guid>episode-1</guid>
+      <enclosure url="https://podcast.example.com/1.mp3" length="1024" type="audio/mpeg"/>
+      <itunes:title>The first episode</itunes:title>
+      <itunes:author>Alice</itunes:author>
+      <itunes:duration>01:02:03</itunes:duration>
+      <itunes:image href="https://podcast.example.com/1.jpg"/>
+    </item>
+    <item>
+      <title>Post</title>
+      <guid>post</guid>
+    </item>
+  </channel>
+</rss>"#;
+
+    fn parse(src: &str) -> Feed {
+        let url = FeedUrl::parse("https://podcast.example.com/feed.xml").unwrap();
+        let feed = feed_rs::parser::parse(src.as_bytes()).unwrap();
+        Feed::from((url, feed))
+    }
+
+    #[test]
+    fn podcast() {
+        let feed = parse(PODCAST);
+        let mut entries = feed.entries();
+        let episode = entries.next().unwrap();
+        let duration = Duration::from_secs(3723);
+
+        assert_eq!(
+            episode.enclosures(),
+            vec![Enclosure {
+                url: "https://podcast.example.com/1.mp3".into(),
+                mime_type: Some("audio/mpeg".into()),
+                length: Some(1024),
+                duration: Some(duration),
+            }]
+        );
+        assert_eq!(
+            episode.thumbnails().collect::<Vec<_>>(),
+            vec!["https://podcast.example.com/1.jpg"]
+        );
+        assert_eq!(
+            episode.episode(),
+            Some(Episode {
+                title: Some("The first episode".into()),
+                description: None,
+                authors: vec!["Alice".into()],
+                duration: Some(duration),
+            })
+        );
+        assert_eq!(
+            feed.meta().image_url(),
+            Some("https://podcast.example.com/cover.jpg")
+        );
+        assert!(feed.meta().is_explicit());
+
+        let post = entries.next().unwrap();
+        assert!(post.enclosures().is_empty());
+        assert_eq!(post.episode(), None);
+    }
+
+    #[test]
+    fn atom_enclosure() {
+        let src = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Atom</title>
+  <id>urn:atom</id>
+  <updated>2024-01-01T00:00:00Z</updated>
+  <entry>
+    <title>Entry</title>
+    <id>urn:atom:1</id>
+    <updated>2024-01-01T00:00:00Z</updated>
+    <link rel="alternate" href="https://atom.example.com/1"/>
+    <link rel="enclosure" href="https://atom.example.com/1.ogg" type="audio/ogg" length="2048"/>
+  </entry>
+</feed>"#;
+        let feed = parse(src);
+        let entry = feed.entries().next().unwrap();
+
+        assert_eq!(
+            entry.enclosures(),
+            vec![Enclosure {
+                url: "https://atom.example.com/1.ogg".into(),
+                mime_type: Some("audio/ogg".into()),
+                length: Some(2048),
+                duration: None,
+            }]
+        );
+        assert!(!feed.meta().is_explicit());
+    }
+}
@@ -1,7 +1,7 @@
 use std::{borrow::Cow, fmt::Display};
 
 use chrono::{DateTime, Utc};
-use feed_rs::model::{self as feedrs, Generator, Link, Person, Text};
+use feed_rs::model::{self as feedrs, Generator, Image, Link, MediaRating, Person, Text};
 
 pub type Time = DateTime<Utc>;
 
@@ -17,7 +17,18 @@ pub use url::FeedUrl;
 mod feed_type;
 pub use feed_type::FeedType;
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+mod credential;
+pub use credential::{CredentialIdentity, FeedCredential};
+
+mod content;
+use content::Content;
+
+mod media;
+pub use media::{Enclosure, Episode};
+
+mod canonical;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub struct EntryId<'a>(Cow<'a, str>);
 
 impl<'a, T> From<T> for EntryId<'a>
@@ -70,10 +81,77 @@ impl Entry {
             .and_then(|content| content.body.as_deref())
     }
 
+    /// Summary sanitized to be safely embedded in html
+    pub fn summary_html(&self) -> Option<String> {
+        self.summary_source().map(Content::sanitized_html)
+    }
+
+    /// Summary rendered as plain text with links as footnotes.
+    /// If width is not given, lines are not wrapped
+    pub fn summary_text(&self, width: Option<usize>) -> Option<String> {
+        self.summary_source().map(|content| content.text(width))
+    }
+
+    /// Content sanitized to be safely embedded in html
+    pub fn content_html(&self) -> Option<String> {
+        self.content_source().map(Content::sanitized_html)
+    }
+
+    /// Content rendered as plain text with links as footnotes.
+    /// If width is not given, lines are not wrapped
+    pub fn content_text(&self, width: Option<usize>) -> Option<String> {
+        self.content_source().map(|content| content.text(width))
+    }
+
+    fn summary_source(&self) -> Option<Content<'_>> {
+        self.0
+            .summary
+            .as_ref()
+            .map(|text| Content::new(&text.content, text.content_type.essence_str()))
+    }
+
+    fn content_source(&self) -> Option<Content<'_>> {
+        self.0.content.as_ref().and_then(|content| {
+            content
+                .body
+                .as_deref()
+                .map(|body| Content::new(body, content.content_type.essence_str()))
+        })
+    }
+
     pub fn website_url(&self, feed_type: FeedType) -> Option<&str> {
         link::find_website_url(feed_type, &self.0.links)
     }
 
+    /// Link normalized to identify the article regardless of the feed which published it
+    pub fn canonical_url(&self, feed_type: FeedType) -> Option<String> {
+        self.website_url(feed_type)
+            .and_then(canonical::canonical_url)
+    }
+
+    /// Digest of the title and the text to identify the article whose link or id changed
+    pub fn content_digest(&self) -> Option<u64> {
+        let text = self
+            .content_text(None)
+            .or_else(|| self.summary_text(None))?;
+        canonical::content_digest(self.title(), &text)
+    }
+
+    /// Media files attached to the entry
+    pub fn enclosures(&self) -> Vec<Enclosure> {
+        media::enclosures(&self.0)
+    }
+
+    /// Urls of the thumbnail images
+    pub fn thumbnails(&self) -> impl Iterator<Item = &str> {
+        media::thumbnails(&self.0)
+    }
+
+    /// Podcast metadata if the entry is an episode
+    pub fn episode(&self) -> Option<Episode> {
+        media::episode(&self.0)
+    }
+
     /// Return approximate entry bytes size
     pub fn approximate_size(&self) -> usize {
         let content_size = self
@@ -105,6 +183,8 @@ pub struct FeedMeta {
     links: Vec<Link>,
     generator: Option<Generator>,
     published: Option<Time>,
+    logo: Option<Image>,
+    rating: Option<MediaRating>,
 }
 
 #[derive(Debug, Clone)]
@@ -171,6 +251,18 @@ impl FeedMeta {
     pub fn generator(&self) -> Option<&str> {
         self.generator.as_ref().map(|g| g.content.as_str())
     }
+
+    /// Url of the feed image such as podcast artwork
+    pub fn image_url(&self) -> Option<&str> {
+        self.logo.as_ref().map(|image| image.uri.as_str())
+    }
+
+    /// Return true if the podcast is marked as explicit
+    pub fn is_explicit(&self) -> bool {
+        self.rating
+            .as_ref()
+            .is_some_and(|rating| rating.urn == "itunes" && rating.value == "true")
+    }
 }
 
 impl<'a> From<&'a FeedMeta> for Cow<'a, FeedMeta> {
@@ -222,6 +314,8 @@ impl From<(FeedUrl, feed_rs::model::Feed)> for Feed {
             generator,
             published,
             entries,
+            logo,
+            rating,
             ..
         } = feed;
         let meta = FeedMeta {
@@ -234,6 +328,8 @@ impl From<(FeedUrl, feed_rs::model::Feed)> for Feed {
             links,
             generator,
             published,
+            logo,
+            rating,
         };
         let entries = entries.into_iter().map(Entry).collect();
 
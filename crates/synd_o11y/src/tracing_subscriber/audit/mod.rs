@@ -46,6 +46,8 @@ impl Audit {
     pub const USER_ID: &'static str = opentelemetry_semantic_conventions::attribute::USER_ID;
     pub const OPERATION: &'static str = "operation";
     pub const RESULT: &'static str = "result";
+    /// Why the operation resulted as it did. ex. the reason of denial
+    pub const REASON: &'static str = "reason";
 
     /// # Panics
     /// panic when directive is invalid
@@ -114,6 +116,7 @@ impl field::Visit for AuditEventVisitor<'_> {
             Audit::USER_ID => self.ctx.user_id = Some(value.to_owned()),
             Audit::OPERATION => self.ctx.operation = Some(value.to_string()),
             Audit::RESULT => self.ctx.result = Some(value.to_string()),
+            Audit::REASON => self.ctx.reason = Some(value.to_string()),
             _ => {}
         }
     }
@@ -123,6 +126,7 @@ struct AuditContext {
     user_id: Option<String>,
     operation: Option<String>,
     result: Option<String>,
+    reason: Option<String>,
 }
 
 impl AuditContext {
@@ -131,6 +135,7 @@ impl AuditContext {
             user_id: None,
             operation: None,
             result: None,
+            reason: None,
         }
     }
 }
@@ -185,6 +190,7 @@ where
             user_id,
             operation,
             result,
+            reason,
         }) = extensions.remove::<AuditContext>()
         else {
             return;
@@ -201,6 +207,7 @@ where
             { Audit::USER_ID } = user_id,
             { Audit::OPERATION } = operation,
             { Audit::RESULT } = result,
+            { Audit::REASON } = reason.as_deref(),
         );
     }
 }
@@ -287,6 +294,37 @@ mod tests {
         assert_eq!(ctx.user_id.as_deref(), Some("user-a"));
         assert_eq!(ctx.operation.as_deref(), Some("create_foo"));
         assert_eq!(ctx.result.as_deref(), Some("success"));
+        assert_eq!(ctx.reason, None);
+    }
+
+    #[test]
+    fn record_reason() {
+        let ctx = Arc::new(Mutex::new(AuditContext::new()));
+        let ctx2 = Arc::clone(&ctx);
+        let on_event = move |event: &Event<'_>| {
+            if event.metadata().name() == Audit::EMIT_EVENT_NAME {
+                let mut ctx = ctx2.lock().unwrap();
+                event.record(&mut AuditEventVisitor { ctx: &mut ctx });
+            }
+        };
+        let test_layer = TestLayer { on_event };
+        let subscriber = tracing_subscriber::registry()
+            .with(layer())
+            .with(test_layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = crate::audit_span!();
+            let _enter = span.enter();
+            crate::audit!(
+                { Audit::RESULT } = "unauthorized",
+                { Audit::REASON } = "permission denied",
+            );
+        });
+
+        let ctx = Arc::into_inner(ctx).unwrap().into_inner().unwrap();
+
+        assert_eq!(ctx.result.as_deref(), Some("unauthorized"));
+        assert_eq!(ctx.reason.as_deref(), Some("permission denied"));
     }
 
     #[test]
@@ -1,6 +1,10 @@
 use std::time::Duration;
 
-use crate::config;
+use crate::{
+    config,
+    keymap::KeymapsConfig,
+    ui::{components::filter::SavedFilter, theme::Theme},
+};
 
 #[derive(Debug, Clone, Default)]
 pub struct Features {
@@ -15,6 +19,11 @@ pub struct Config {
     pub entries_per_pagination: i64,
     pub feeds_per_pagination: i64,
     pub features: Features,
+    pub keymaps: KeymapsConfig,
+    /// User defined themes to rotate in addition to the builtin ones
+    pub themes: Vec<Theme>,
+    /// Filters defined in the config file
+    pub saved_filters: Vec<SavedFilter>,
 }
 
 impl Default for Config {
@@ -26,6 +35,9 @@ impl Default for Config {
             entries_per_pagination: config::client::DEFAULT_ENTRIES_PER_PAGINATION,
             feeds_per_pagination: config::client::DEFAULT_FEEDS_PER_PAGINATION,
             features: Features::default(),
+            keymaps: KeymapsConfig::default(),
+            themes: Vec::new(),
+            saved_filters: Vec::new(),
         }
     }
 }
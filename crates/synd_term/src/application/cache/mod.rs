@@ -4,14 +4,18 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use serde::{Serialize, de::DeserializeOwned};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use synd_stdx::fs::{FileSystem, fsimpl};
 use thiserror::Error;
 
 use crate::{
     auth::{Credential, Unverified},
-    config,
-    ui::components::gh_notifications::GhNotificationFilterOptions,
+    config, types,
+    ui::components::{
+        entries::EntriesViewOptions, filter::SavedFilter,
+        gh_notifications::GhNotificationFilterOptions,
+    },
 };
 
 #[derive(Debug, Error)]
@@ -32,6 +36,13 @@ pub enum LoadCacheError {
     Deserialize(#[from] serde_json::Error),
 }
 
+/// Items fetched from the api at `fetched_at`
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Snapshot<Items> {
+    pub(crate) fetched_at: DateTime<Utc>,
+    pub(crate) items: Items,
+}
+
 pub struct Cache<FS = fsimpl::FileSystem> {
     dir: PathBuf,
     fs: FS,
@@ -70,6 +81,34 @@ where
         self.persist(&self.gh_notification_filter_option_file(), options.borrow())
     }
 
+    pub(crate) fn persist_entries_view_options(
+        &self,
+        options: impl Borrow<EntriesViewOptions>,
+    ) -> Result<(), PersistCacheError> {
+        self.persist(&self.entries_view_option_file(), options.borrow())
+    }
+
+    pub(crate) fn persist_saved_filters(
+        &self,
+        filters: &[SavedFilter],
+    ) -> Result<(), PersistCacheError> {
+        self.persist(&self.saved_filter_file(), filters)
+    }
+
+    pub(crate) fn persist_subscription(
+        &self,
+        snapshot: &Snapshot<&[types::Feed]>,
+    ) -> Result<(), PersistCacheError> {
+        self.persist(&self.subscription_file(), snapshot)
+    }
+
+    pub(crate) fn persist_entries(
+        &self,
+        snapshot: &Snapshot<&[types::Entry]>,
+    ) -> Result<(), PersistCacheError> {
+        self.persist(&self.entries_file(), snapshot)
+    }
+
     fn persist<T>(&self, path: &Path, entry: &T) -> Result<(), PersistCacheError>
     where
         T: ?Sized + Serialize,
@@ -107,6 +146,22 @@ where
         self.load(&self.gh_notification_filter_option_file())
     }
 
+    pub(crate) fn load_entries_view_options(&self) -> Result<EntriesViewOptions, LoadCacheError> {
+        self.load(&self.entries_view_option_file())
+    }
+
+    pub(crate) fn load_saved_filters(&self) -> Result<Vec<SavedFilter>, LoadCacheError> {
+        self.load(&self.saved_filter_file())
+    }
+
+    pub(crate) fn load_subscription(&self) -> Result<Snapshot<Vec<types::Feed>>, LoadCacheError> {
+        self.load(&self.subscription_file())
+    }
+
+    pub(crate) fn load_entries(&self) -> Result<Snapshot<Vec<types::Entry>>, LoadCacheError> {
+        self.load(&self.entries_file())
+    }
+
     fn load<T>(&self, path: &Path) -> Result<T, LoadCacheError>
     where
         T: DeserializeOwned,
@@ -131,24 +186,51 @@ where
             .join(config::cache::GH_NOTIFICATION_FILTER_OPTION_FILE)
     }
 
+    fn entries_view_option_file(&self) -> PathBuf {
+        self.dir.join(config::cache::ENTRIES_VIEW_OPTION_FILE)
+    }
+
+    fn saved_filter_file(&self) -> PathBuf {
+        self.dir.join(config::cache::SAVED_FILTER_FILE)
+    }
+
+    fn subscription_file(&self) -> PathBuf {
+        self.dir.join(config::cache::SUBSCRIPTION_FILE)
+    }
+
+    fn entries_file(&self) -> PathBuf {
+        self.dir.join(config::cache::ENTRIES_FILE)
+    }
+
     /// Remove all cache files
     pub(crate) fn clean(&self) -> io::Result<()> {
         // User can specify any directory as the cache
         // so instead of deleting the entire directory with `remove_dir_all`, delete files individually.
-        match self.fs.remove_file(self.credential_file()) {
+        [
+            self.credential_file(),
+            self.subscription_file(),
+            self.entries_file(),
+        ]
+        .into_iter()
+        .try_for_each(|file| match self.fs.remove_file(file) {
             Ok(()) => Ok(()),
             Err(err) => match err.kind() {
                 io::ErrorKind::NotFound => Ok(()),
                 _ => Err(err),
             },
-        }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::auth::Credential;
+    use fake::{Fake as _, Faker};
+
+    use crate::{
+        auth::Credential,
+        ui::components::entries::{EntryGrouping, EntrySort},
+    };
 
     use super::*;
 
@@ -165,6 +247,47 @@ mod tests {
         assert_eq!(loaded, Unverified::from(cred),);
     }
 
+    #[test]
+    fn persist_then_load_entries_view_options() {
+        let tmp = temp_dir();
+        let cache = Cache::new(tmp);
+        let options = EntriesViewOptions {
+            sort: EntrySort::Requirement,
+            grouping: EntryGrouping::Category,
+        };
+        assert!(cache.persist_entries_view_options(options).is_ok());
+
+        let loaded = cache.load_entries_view_options().unwrap();
+        assert_eq!(loaded, options);
+    }
+
+    #[test]
+    fn persist_then_load_subscription() {
+        let tmp = temp_dir();
+        let cache = Cache::new(tmp);
+        assert!(cache.load_subscription().is_err());
+
+        let feed: types::Feed = Faker.fake();
+        let fetched_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert!(
+            cache
+                .persist_subscription(&Snapshot {
+                    fetched_at,
+                    items: std::slice::from_ref(&feed),
+                })
+                .is_ok()
+        );
+
+        let loaded = cache.load_subscription().unwrap();
+        assert_eq!(loaded.fetched_at, fetched_at);
+        assert_eq!(loaded.items.len(), 1);
+        assert_eq!(loaded.items[0].url, feed.url);
+        assert_eq!(loaded.items[0].title, feed.title);
+
+        assert!(cache.clean().is_ok());
+        assert!(cache.load_subscription().is_err());
+    }
+
     #[test]
     fn filesystem_error() {
         let cache = Cache::new("/dev/null");
@@ -16,12 +16,14 @@ pub(crate) enum RequestId {
     DeviceFlowDeviceAuthorize,
     DeviceFlowPollAccessToken,
     FetchEntries,
+    FetchEntryContent,
     FetchSubscription,
     FetchGithubNotifications { page: u8 },
     FetchGithubIssue { id: IssueId },
     FetchGithubPullRequest { id: PullRequestId },
     SubscribeFeed,
     UnsubscribeFeed,
+    UpdateFeeds,
     MarkGithubNotificationAsDone { id: NotificationId },
     UnsubscribeGithubThread,
 }
@@ -1,3 +1,4 @@
+use itertools::Itertools;
 use nom_language::error::{VerboseError, VerboseErrorKind};
 use thiserror::Error;
 
@@ -5,6 +6,7 @@ use crate::{
     client::synd_api::mutation::subscribe_feed::SubscribeFeedInput,
     config::Categories,
     types::{self},
+    ui::components::filter::Query,
 };
 
 type NomError<'s> = VerboseError<&'s str>;
@@ -13,6 +15,8 @@ const CTX_REQUIREMENT: &str = "requirement";
 const CTX_CATEGORY: &str = "category";
 const CTX_CATEGORY_POST: &str = "category_post";
 const CTX_URL: &str = "url";
+const CTX_QUERY_VALUE: &str = "query_value";
+const CTX_QUERY_QUOTE: &str = "query_quote";
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub(super) enum ParseFeedError {
@@ -20,7 +24,15 @@ pub(super) enum ParseFeedError {
     Parse(String),
 }
 
-pub(super) struct InputParser<'a> {
+#[derive(Error, Debug, PartialEq, Eq)]
+pub(crate) enum ParseQueryError {
+    #[error("invalid {field} `{value}`")]
+    InvalidValue { field: &'static str, value: String },
+    #[error("{0}")]
+    Syntax(String),
+}
+
+pub(crate) struct InputParser<'a> {
     input: &'a str,
 }
 
@@ -42,7 +54,17 @@ impl<'a> InputParser<'a> {
 # MUST rust https://this-week-in-rust.org/atom.xml
 ";
 
-    pub(super) fn new(input: &'a str) -> Self {
+    pub(super) const SAVE_FILTER_PROMPT: &'static str =
+        "# Please enter the name to save the current requirement, categories and search as
+#
+# Lines starting with '#' will be ignored, and an empty name aborts saving.
+# The filter with the same name is overwritten.
+#
+# Example:
+# rust-weekly
+";
+
+    pub(crate) fn new(input: &'a str) -> Self {
         Self { input }
     }
 
@@ -77,14 +99,165 @@ impl<'a> InputParser<'a> {
             })
     }
 
-    pub(super) fn edit_feed_prompt(feed: &types::Feed) -> String {
-        format!(
-            "{}\n{requirement} {category} {feed_url}",
-            Self::SUSBSCRIBE_FEED_PROMPT,
-            requirement = feed.requirement(),
-            category = feed.category(),
-            feed_url = feed.url,
+    /// Parse each line which is neither empty nor a comment as a subscription
+    pub(super) fn parse_feed_subscriptions(
+        &self,
+        categories: &Categories,
+    ) -> Result<Vec<SubscribeFeedInput>, ParseFeedError> {
+        self.input
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|line| InputParser::new(line).parse_feed_subscription(categories))
+            .collect()
+    }
+
+    /// Parse the search query such as `feed:"this week" cat:rust req:must after:7d -title:release`
+    pub(crate) fn parse_query(&self, categories: &Categories) -> Result<Query, ParseQueryError> {
+        let tokens = query::parse(self.input).map_err(|verbose_err: NomError| {
+            // alt appends its own kind after the context, so look for the context in all errors
+            let context = verbose_err.errors.iter().find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(*context),
+                _ => None,
+            });
+            let msg = match (context, verbose_err.errors.first()) {
+                (Some(CTX_QUERY_VALUE), _) => "missing value".to_owned(),
+                (Some(CTX_QUERY_QUOTE), _) => "unterminated quote".to_owned(),
+                (_, Some((input, _))) => format!("unexpected `{input}`"),
+                (_, None) => "failed to parse query".to_owned(),
+            };
+            ParseQueryError::Syntax(msg)
+        })?;
+        tokens
+            .into_iter()
+            .map(|token| token.into_term(categories))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|terms| Query { terms })
+    }
+
+    /// Parse the first line which is neither empty nor a comment as the filter name
+    pub(super) fn parse_filter_name(&self) -> Option<String> {
+        self.input
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(ToOwned::to_owned)
+    }
+
+    pub(super) fn edit_feeds_prompt<'f>(
+        feeds: impl IntoIterator<Item = &'f types::Feed>,
+    ) -> String {
+        let feeds = feeds.into_iter().map(|feed| {
+            format!(
+                "{requirement} {category} {feed_url}",
+                requirement = feed.requirement(),
+                category = feed.category(),
+                feed_url = feed.url,
+            )
+        });
+        std::iter::once(Self::SUSBSCRIBE_FEED_PROMPT.to_owned())
+            .chain(feeds)
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_multiple_feed_subscriptions() {
+        let input = format!(
+            "{}\nMUST rust https://example.ymgyt.io/atom.xml\n\nmay go https://example.ymgyt.io/rss.xml\n",
+            InputParser::SUSBSCRIBE_FEED_PROMPT,
+        );
+        let inputs = InputParser::new(&input)
+            .parse_feed_subscriptions(&Categories::default_toml())
+            .unwrap();
+
+        assert_eq!(
+            inputs
+                .iter()
+                .map(|input| input.url.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "https://example.ymgyt.io/atom.xml",
+                "https://example.ymgyt.io/rss.xml"
+            ],
+        );
+
+        let err = InputParser::new("MUST rust https://example.ymgyt.io/atom.xml\nfoo")
+            .parse_feed_subscriptions(&Categories::default_toml());
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn parse_filter_name() {
+        let input = format!("{}\n\n  rust-weekly \n", InputParser::SAVE_FILTER_PROMPT);
+        assert_eq!(
+            InputParser::new(&input).parse_filter_name().as_deref(),
+            Some("rust-weekly")
+        );
+        assert_eq!(
+            InputParser::new(InputParser::SAVE_FILTER_PROMPT).parse_filter_name(),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_query() {
+        use std::time::Duration;
+
+        use synd_feed::types::{Category, Requirement};
+
+        use crate::ui::components::filter::{Condition, Since, Term};
+
+        let categories = Categories::default_toml();
+        let parse = |input| InputParser::new(input).parse_query(&categories);
+        let query = parse(
+            r#"feed:"this week" cat:Rust req:must after:7d before:2024-07-01 -title:release"#,
         )
+        .unwrap();
+        let term = |negated, condition| Term { negated, condition };
+        assert_eq!(
+            query.terms,
+            vec![
+                term(false, Condition::Feed("this week".into())),
+                term(false, Condition::Category(Category::new("rust").unwrap())),
+                term(false, Condition::Requirement(Requirement::Must)),
+                term(
+                    false,
+                    Condition::After(Since::Ago(Duration::from_secs(7 * 24 * 60 * 60)))
+                ),
+                term(
+                    false,
+                    Condition::Before(Since::Date("2024-07-01".parse().unwrap()))
+                ),
+                term(true, Condition::Title("release".into())),
+            ]
+        );
+
+        // Only the known fields are parsed as fields, and aliases of the category are normalized
+        assert_eq!(
+            parse("https://blog.rust-lang.org foo:bar cat:golang")
+                .unwrap()
+                .terms,
+            vec![
+                term(
+                    false,
+                    Condition::Keyword("https://blog.rust-lang.org".into())
+                ),
+                term(false, Condition::Keyword("foo:bar".into())),
+                term(false, Condition::Category(Category::new("go").unwrap())),
+            ]
+        );
+
+        let err = |input| parse(input).unwrap_err().to_string();
+        assert_eq!(err("req:always"), "invalid requirement `always`");
+        assert_eq!(err("after:someday"), "invalid date `someday`");
+        assert_eq!(err("after:1000000y"), "invalid date `1000000y`");
+        assert_eq!(err(r#"title:"rust"#), "unterminated quote");
+        assert_eq!(err("cat:"), "missing value");
+        assert_eq!(err("rust -"), "unexpected `-`");
     }
 }
 
@@ -253,6 +426,180 @@ mod feed {
     }
 }
 
+mod query {
+    use chrono::{NaiveDate, Utc};
+    use nom::{
+        Finish, IResult, Parser,
+        branch::alt,
+        bytes::complete::{take_till, take_till1},
+        character::complete::{alpha1, char, multispace0, multispace1},
+        combinator::{all_consuming, cut, map_opt, opt},
+        error::context,
+        multi::separated_list0,
+        sequence::{delimited, preceded, terminated},
+    };
+    use synd_feed::types::Category;
+    use synd_stdx::time::humantime::parse_duration;
+
+    use super::{CTX_QUERY_QUOTE, CTX_QUERY_VALUE, NomError, ParseQueryError};
+    use crate::{
+        config::Categories,
+        ui::components::filter::{Condition, Since, Term},
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Field {
+        Title,
+        Feed,
+        Category,
+        Requirement,
+        After,
+        Before,
+    }
+
+    impl Field {
+        /// Other prefixes like the scheme of the url are not fields but a part of the keyword
+        fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "title" => Some(Field::Title),
+                "feed" | "repo" => Some(Field::Feed),
+                "cat" | "category" => Some(Field::Category),
+                "req" | "requirement" => Some(Field::Requirement),
+                "after" => Some(Field::After),
+                "before" => Some(Field::Before),
+                _ => None,
+            }
+        }
+    }
+
+    /// `[-][field:]value` where the value can be quoted to include spaces
+    #[derive(Debug, PartialEq, Eq)]
+    pub(super) struct Token<'s> {
+        negated: bool,
+        field: Option<Field>,
+        value: &'s str,
+    }
+
+    impl Token<'_> {
+        pub(super) fn into_term(self, categories: &Categories) -> Result<Term, ParseQueryError> {
+            let invalid = |field| ParseQueryError::InvalidValue {
+                field,
+                value: self.value.to_owned(),
+            };
+            let value = self.value;
+            let condition = match self.field {
+                None => Condition::Keyword(value.to_owned()),
+                Some(Field::Title) => Condition::Title(value.to_owned()),
+                Some(Field::Feed) => Condition::Feed(value.to_owned()),
+                Some(Field::Category) => Category::new(value.to_owned())
+                    .map(|category| Condition::Category(categories.normalize(category)))
+                    .map_err(|_| invalid("category"))?,
+                Some(Field::Requirement) => value
+                    .parse()
+                    .map(Condition::Requirement)
+                    .map_err(|_| invalid("requirement"))?,
+                Some(Field::After) => {
+                    Condition::After(since(value).ok_or_else(|| invalid("date"))?)
+                }
+                Some(Field::Before) => {
+                    Condition::Before(since(value).ok_or_else(|| invalid("date"))?)
+                }
+            };
+            Ok(Term {
+                negated: self.negated,
+                condition,
+            })
+        }
+    }
+
+    /// Accept the date like `2024-07-01` or the duration like `7d` which does not overflow the date
+    fn since(value: &str) -> Option<Since> {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(Since::Date)
+            .ok()
+            .or_else(|| parse_duration(value).ok().map(Since::Ago))
+            .filter(|since| since.is_in_range(Utc::now()))
+    }
+
+    pub(super) fn parse(s: &str) -> Result<Vec<Token<'_>>, NomError<'_>> {
+        all_consuming(delimited(
+            multispace0,
+            separated_list0(multispace1, token),
+            multispace0,
+        ))
+        .parse(s)
+        .finish()
+        .map(|(_, tokens)| tokens)
+    }
+
+    fn token(s: &str) -> IResult<&str, Token<'_>, NomError<'_>> {
+        let (s, negated) = opt(char('-')).parse(s)?;
+        let (s, field) = opt(terminated(map_opt(alpha1, Field::from_name), char(':'))).parse(s)?;
+        let (s, value) = if field.is_some() {
+            cut(value).parse(s)?
+        } else {
+            value(s)?
+        };
+        Ok((
+            s,
+            Token {
+                negated: negated.is_some(),
+                field,
+                value,
+            },
+        ))
+    }
+
+    fn value(s: &str) -> IResult<&str, &str, NomError<'_>> {
+        alt((
+            quoted,
+            context(CTX_QUERY_VALUE, take_till1(char::is_whitespace)),
+        ))
+        .parse(s)
+    }
+
+    fn quoted(s: &str) -> IResult<&str, &str, NomError<'_>> {
+        preceded(
+            char('"'),
+            cut(terminated(
+                take_till(|c| c == '"'),
+                context(CTX_QUERY_QUOTE, char('"')),
+            )),
+        )
+        .parse(s)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn parse_tokens() {
+            assert_eq!(
+                parse(r#" feed:"this week" -title:release  rust "#),
+                Ok(vec![
+                    Token {
+                        negated: false,
+                        field: Some(Field::Feed),
+                        value: "this week"
+                    },
+                    Token {
+                        negated: true,
+                        field: Some(Field::Title),
+                        value: "release"
+                    },
+                    Token {
+                        negated: false,
+                        field: None,
+                        value: "rust"
+                    },
+                ])
+            );
+            assert_eq!(parse(""), Ok(vec![]));
+        }
+    }
+}
+
 mod comment {
     use nom::{
         IResult, Parser,
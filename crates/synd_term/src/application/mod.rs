@@ -25,7 +25,10 @@ use crate::{
     auth::{self, AuthenticationProvider, Credential, CredentialError, Verified},
     client::{
         github::{FetchNotificationsParams, GithubClient},
-        synd_api::{Client, SyndApiError, mutation::subscribe_feed::SubscribeFeedInput},
+        synd_api::{
+            Client, SyndApiError,
+            mutation::{subscribe_feed::SubscribeFeedInput, update_feeds::UpdateFeedInput},
+        },
     },
     command::{ApiResponse, Command},
     config::{self, Categories},
@@ -37,11 +40,18 @@ use crate::{
     ui::{
         self,
         components::{
-            Components, authentication::AuthenticateState, filter::Filterer,
-            gh_notifications::GhNotifications, root::Root, subscription::UnsubscribeSelection,
-            tabs::Tab,
+            Components,
+            authentication::AuthenticateState,
+            collections::Marking,
+            command_palette::PaletteItem,
+            entries::Entries,
+            filter::{Filter, Filterer, SavedFilters},
+            gh_notifications::GhNotifications,
+            root::Root,
+            subscription::UnsubscribeSelection,
+            tabs::{Connection, Tab},
         },
-        theme::{Palette, Theme},
+        theme::Theme,
     },
 };
 
@@ -52,7 +62,7 @@ mod in_flight;
 pub(crate) use in_flight::{InFlight, RequestId, RequestSequence};
 
 mod input_parser;
-use input_parser::InputParser;
+pub(crate) use input_parser::{InputParser, ParseQueryError};
 
 pub use auth::authenticator::{Authenticator, DeviceFlows, JwtService};
 
@@ -60,6 +70,7 @@ mod clock;
 pub use clock::{Clock, SystemClock};
 
 mod cache;
+use cache::Snapshot;
 pub use cache::{Cache, LoadCacheError, PersistCacheError};
 
 mod builder;
@@ -136,7 +147,7 @@ impl Application {
         } = builder;
 
         let key_handlers = {
-            let mut keymaps = Keymaps::default();
+            let mut keymaps = Keymaps::new(config.keymaps.clone());
             keymaps.enable(KeymapId::Global);
             keymaps.enable(KeymapId::Login);
 
@@ -144,6 +155,7 @@ impl Application {
             key_handlers.push(event::KeyHandler::Keymaps(keymaps));
             key_handlers
         };
+        let authenticator = authenticator.unwrap_or_else(Authenticator::new);
         let mut state = State::new();
         if dry_run {
             state.flags = Should::Quit;
@@ -157,9 +169,9 @@ impl Application {
             // The secondary rate limit of the GitHub API is 100 concurrent requests, so we have set it to 90.
             jobs: Jobs::new(NonZero::new(90).unwrap()),
             background_jobs: Jobs::new(NonZero::new(10).unwrap()),
-            components: Components::new(&config.features),
+            components: Components::new(&config.features, authenticator.providers()),
             interactor,
-            authenticator: authenticator.unwrap_or_else(Authenticator::new),
+            authenticator,
             in_flight: InFlight::new().with_throbber_timer_interval(config.throbber_timer_interval),
             cache,
             theme,
@@ -212,6 +224,24 @@ impl Application {
             }
         }?;
 
+        // Restore previous sort and grouping of the entries
+        match self.cache.load_entries_view_options() {
+            Ok(options) => {
+                self.components.entries = Entries::with_view_options(options);
+            }
+            Err(err) => {
+                tracing::warn!("Load entries view options: {err}");
+            }
+        }
+
+        // Filters saved in the previous sessions follow the configured ones
+        let saved = self.cache.load_saved_filters().unwrap_or_else(|err| {
+            tracing::warn!("Load saved filters: {err}");
+            Vec::new()
+        });
+        self.components.filter =
+            Filter::with_saved_filters(SavedFilters::new(self.config.saved_filters.clone(), saved));
+
         if self.config.features.enable_github_notification {
             // Restore previous filter options
             match self.cache.load_gh_notification_filter_options() {
@@ -245,6 +275,7 @@ impl Application {
 
     fn handle_initial_credential(&mut self, cred: Verified<Credential>) {
         self.set_credential(cred);
+        self.restore_snapshot();
         self.initial_fetch();
         self.check_latest_release();
         self.components.auth.authenticated();
@@ -266,15 +297,66 @@ impl Application {
         self.client.set_credential(cred);
     }
 
+    /// Show the subscription and entries fetched in the previous session until they are refreshed
+    fn restore_snapshot(&mut self) {
+        match self.cache.load_entries() {
+            Ok(Snapshot { fetched_at, items }) => {
+                self.components.filter.update_categories(
+                    &self.categories,
+                    Populate::Replace,
+                    items.as_slice(),
+                );
+                self.components.entries.restore_entries(items);
+                self.state.entries_fetched_at = Some(fetched_at);
+            }
+            Err(err) => tracing::info!("Restore entries: {err}"),
+        }
+        match self.cache.load_subscription() {
+            Ok(Snapshot { fetched_at, items }) => {
+                self.components.subscription.restore_feeds(items);
+                self.state.subscription_fetched_at = Some(fetched_at);
+                // Subscription is otherwise fetched when the tab is first selected
+                self.jobs
+                    .push(future::ready(Ok(Command::ReloadSubscription)).boxed());
+            }
+            Err(err) => tracing::info!("Restore subscription: {err}"),
+        }
+    }
+
+    fn persist_snapshot(&self) {
+        self.persist_entries_snapshot();
+        self.persist_subscription_snapshot();
+    }
+
+    fn persist_entries_snapshot(&self) {
+        if let Some(fetched_at) = self.state.entries_fetched_at {
+            let snapshot = Snapshot {
+                fetched_at,
+                items: self.components.entries.entries(),
+            };
+            if let Err(err) = self.cache.persist_entries(&snapshot) {
+                tracing::warn!("Failed to persist entries: {err}");
+            }
+        }
+    }
+
+    fn persist_subscription_snapshot(&self) {
+        if let Some(fetched_at) = self.state.subscription_fetched_at {
+            let snapshot = Snapshot {
+                fetched_at,
+                items: self.components.subscription.feeds(),
+            };
+            if let Err(err) = self.cache.persist_subscription(&snapshot) {
+                tracing::warn!("Failed to persist subscription: {err}");
+            }
+        }
+    }
+
     fn initial_fetch(&mut self) {
         tracing::info!("Initial fetch");
-        self.jobs.push(
-            future::ready(Ok(Command::FetchEntries {
-                after: None,
-                first: self.config.entries_per_pagination,
-            }))
-            .boxed(),
-        );
+        // Replace the entries restored from the cache
+        self.jobs
+            .push(future::ready(Ok(Command::ReloadEntries)).boxed());
         if self.config.features.enable_github_notification {
             if let Some(fetch) = self.components.gh_notifications.fetch_next_if_needed() {
                 self.jobs.push(future::ready(Ok(fetch)).boxed());
@@ -284,6 +366,18 @@ impl Application {
 
     /// Restore terminal state and print something to console if necesseary
     fn cleanup(&mut self) -> anyhow::Result<()> {
+        self.persist_snapshot();
+
+        let options = self.components.entries.view_options();
+        if let Err(err) = self.cache.persist_entries_view_options(options) {
+            tracing::warn!("Failed to persist entries view options: {err}");
+        }
+
+        let saved = self.components.filter.saved_filters().saved();
+        if let Err(err) = self.cache.persist_saved_filters(saved) {
+            tracing::warn!("Failed to persist saved filters: {err}");
+        }
+
         if self.config.features.enable_github_notification {
             let options = self.components.gh_notifications.filter_options();
             match self.cache.persist_gh_notification_filter_options(options) {
@@ -367,7 +461,8 @@ impl Application {
             match command {
                 Command::Nop => {}
                 Command::Quit => self.state.flags.insert(Should::Quit),
-                Command::ResizeTerminal { .. } => {
+                Command::ResizeTerminal { columns, rows } => {
+                    self.components.reader.resize(columns, rows);
                     self.should_render();
                 }
                 Command::RenderThrobber => {
@@ -417,6 +512,20 @@ impl Application {
                             );
                             self.should_render();
                         }
+                        ApiResponse::UpdateFeeds => {
+                            // Reflect the annotations, which are cheap to query as the feeds are cached
+                            self.fetch_subscription(
+                                Populate::Replace,
+                                None,
+                                self.config.feeds_per_pagination,
+                            );
+                            self.fetch_entries(
+                                Populate::Replace,
+                                None,
+                                self.config.entries_per_pagination,
+                            );
+                            self.should_render();
+                        }
                         ApiResponse::UnsubscribeFeed { url } => {
                             self.components.subscription.remove_unsubscribed_feed(&url);
                             self.components.entries.remove_unsubscribed_entries(&url);
@@ -448,6 +557,10 @@ impl Application {
                             self.components
                                 .subscription
                                 .update_subscription(populate, subscription);
+                            self.state.subscription_fetched_at = Some(self.now());
+                            self.components.tabs.set_connection(Connection::Online);
+                            // Keep the snapshot even if the application is not closed normally
+                            self.persist_subscription_snapshot();
                             self.should_render();
                         }
                         ApiResponse::FetchEntries { populate, payload } => {
@@ -472,6 +585,19 @@ impl Application {
                                 });
                             });
                             self.components.entries.update_entries(populate, payload);
+                            self.state.entries_fetched_at = Some(self.now());
+                            self.components.tabs.set_connection(Connection::Online);
+                            self.persist_entries_snapshot();
+                            self.should_render();
+                        }
+                        ApiResponse::FetchEntryContent {
+                            feed_url,
+                            id,
+                            content_html,
+                        } => {
+                            self.components
+                                .reader
+                                .set_content(&feed_url, &id, content_html);
                             self.should_render();
                         }
                         ApiResponse::FetchGithubNotifications {
@@ -604,10 +730,12 @@ impl Application {
                     self.should_render();
                 }
                 Command::SelectFeedUnsubscriptionPopup => {
-                    if let (UnsubscribeSelection::Yes, Some(feed)) =
+                    if let (UnsubscribeSelection::Yes, feeds) =
                         self.components.subscription.unsubscribe_popup_selection()
                     {
-                        self.unsubscribe_feed(feed.url.clone());
+                        let urls = feeds.iter().map(|feed| feed.url.clone()).collect_vec();
+                        urls.into_iter().for_each(|url| self.unsubscribe_feed(url));
+                        self.components.subscription.update_marks(Marking::Clear);
                     }
                     queue.push_back(Command::CancelFeedUnsubscriptionPopup);
                     self.should_render();
@@ -654,12 +782,132 @@ impl Application {
                     self.components.entries.move_last();
                     self.should_render();
                 }
+                Command::RotateEntrySort => {
+                    self.components.entries.rotate_sort();
+                    self.should_render();
+                }
+                Command::RotateEntryGrouping => {
+                    self.components.entries.rotate_grouping();
+                    self.should_render();
+                }
+                Command::ToggleEntryGroup => {
+                    self.components.entries.toggle_group();
+                    self.should_render();
+                }
+                Command::ToggleAllEntryGroups => {
+                    self.components.entries.toggle_all_groups();
+                    self.should_render();
+                }
                 Command::OpenEntry => {
                     self.open_entry();
                 }
                 Command::BrowseEntry => {
                     self.browse_entry();
                 }
+                Command::PlayEntryMedia => {
+                    self.play_entry_media();
+                }
+                Command::OpenCommandPalette => {
+                    if self.components.auth.state() != &AuthenticateState::Authenticated {
+                        continue;
+                    }
+                    self.open_command_palette();
+                    self.should_render();
+                }
+                Command::CloseCommandPalette => {
+                    self.close_command_palette();
+                    self.should_render();
+                }
+                Command::MoveCommandPaletteSelection(direction) => {
+                    self.components.command_palette.move_selection(direction);
+                    self.should_render();
+                }
+                Command::ExecuteCommandPalette => {
+                    let command = self.components.command_palette.selected_command();
+                    self.close_command_palette();
+                    queue.extend(command);
+                    self.should_render();
+                }
+                Command::OpenHelp => {
+                    if self.components.auth.state() != &AuthenticateState::Authenticated {
+                        continue;
+                    }
+                    let help = self.keymaps().help();
+                    self.components.help.open(help);
+                    self.keymaps().focus(KeymapId::Help);
+                    self.should_render();
+                }
+                Command::CloseHelp => {
+                    self.components.help.close();
+                    self.keymaps().unfocus();
+                    self.should_render();
+                }
+                Command::ScrollHelp(direction) => {
+                    self.components.help.scroll(direction);
+                    self.should_render();
+                }
+                Command::OpenReader => {
+                    let Some(entry) = self.components.entries.selected_entry() else {
+                        continue;
+                    };
+                    let size = self.terminal.size().unwrap_or_default();
+                    self.components
+                        .reader
+                        .open(entry, (size.width, size.height));
+                    // Entries do not include the content to keep them small
+                    let content =
+                        (!entry.id.is_empty()).then(|| (entry.feed_url.clone(), entry.id.clone()));
+                    if let Some((feed_url, id)) = content {
+                        self.fetch_entry_content(feed_url, id);
+                    }
+                    self.keymaps()
+                        .enable(KeymapId::Reader)
+                        .disable(KeymapId::Tabs)
+                        .disable(KeymapId::Filter)
+                        .disable(KeymapId::Entries);
+                    self.should_render();
+                }
+                Command::CloseReader => {
+                    self.components.reader.close();
+                    self.keymaps()
+                        .disable(KeymapId::Reader)
+                        .enable(KeymapId::Tabs)
+                        .enable(KeymapId::Filter)
+                        .enable(KeymapId::Entries);
+                    self.should_render();
+                }
+                Command::ScrollReader(direction) => {
+                    self.components.reader.scroll(direction);
+                    self.should_render();
+                }
+                Command::ScrollReaderPage(direction) => {
+                    self.components.reader.scroll_page(direction);
+                    self.should_render();
+                }
+                Command::ScrollReaderTop => {
+                    self.components.reader.scroll_top();
+                    self.should_render();
+                }
+                Command::ScrollReaderBottom => {
+                    self.components.reader.scroll_bottom();
+                    self.should_render();
+                }
+                Command::InputReaderLinkNumber { digit } => {
+                    self.components.reader.input_link_number(digit);
+                    self.should_render();
+                }
+                Command::FollowReaderLink => {
+                    self.follow_reader_link();
+                    self.should_render();
+                }
+                Command::UpdateMarks(marking) => {
+                    match self.components.tabs.current() {
+                        Tab::Entries => self.components.entries.update_marks(marking),
+                        Tab::Feeds => self.components.subscription.update_marks(marking),
+                        Tab::GitHub => self.components.gh_notifications.update_marks(marking),
+                    }
+                    self.should_render();
+                }
                 Command::MoveFilterRequirement(direction) => {
                     let filterer = self.components.filter.move_requirement(direction);
                     self.apply_filterer(filterer)
@@ -681,7 +929,11 @@ impl Application {
                     self.should_render();
                 }
                 Command::PromptChanged => {
-                    if self.components.filter.is_search_active() {
+                    if self.components.command_palette.is_open() {
+                        self.components.command_palette.update_needle();
+                        self.should_render();
+                    } else if self.components.filter.is_search_active() {
+                        self.components.filter.update_query(&self.categories);
                         let filterer = self
                             .components
                             .filter
@@ -722,6 +974,22 @@ impl Application {
                         .for_each(|command| queue.push_back(command));
                     self.should_render();
                 }
+                Command::MoveSavedFilter(direction) => {
+                    for filterer in self
+                        .components
+                        .filter
+                        .rotate_saved_filter(direction, &self.categories)
+                    {
+                        self.apply_filterer(filterer)
+                            .into_iter()
+                            .for_each(|command| queue.push_back(command));
+                    }
+                    self.should_render();
+                }
+                Command::SaveFilter => {
+                    self.prompt_filter_name();
+                    self.should_render();
+                }
                 Command::FetchGhNotifications { populate, params } => {
                     self.fetch_gh_notifications(populate, params);
                 }
@@ -740,6 +1008,9 @@ impl Application {
                 Command::OpenGhNotification { with_mark_as_done } => {
                     self.open_notification();
                     with_mark_as_done.then(|| self.mark_gh_notification_as_done(false));
+                    self.components
+                        .gh_notifications
+                        .update_marks(Marking::Clear);
                 }
                 Command::ReloadGhNotifications => {
                     let params = self.components.gh_notifications.reload();
@@ -750,6 +1021,9 @@ impl Application {
                 }
                 Command::MarkGhNotificationAsDone { all } => {
                     self.mark_gh_notification_as_done(all);
+                    self.components
+                        .gh_notifications
+                        .update_marks(Marking::Clear);
                 }
                 Command::UnsubscribeGhThread => {
                     // Unlike the web UI, simply unsubscribing does not mark it as done
@@ -758,6 +1032,9 @@ impl Application {
                     // To address this, we will implicitly mark it as done when unsubscribing.
                     self.unsubscribe_gh_thread();
                     self.mark_gh_notification_as_done(false);
+                    self.components
+                        .gh_notifications
+                        .update_marks(Marking::Clear);
                 }
                 Command::OpenGhNotificationFilterPopup => {
                     self.components.gh_notifications.open_filter_popup();
@@ -798,6 +1075,12 @@ impl Application {
                     self.handle_error_message(message, None);
                 }
                 Command::HandleApiError { error, request_seq } => {
+                    let unreachable = error.is_unreachable();
+                    if unreachable {
+                        self.components.tabs.set_connection(Connection::Offline {
+                            stale_since: self.state.entries_fetched_at,
+                        });
+                    }
                     let message = match Arc::into_inner(error).expect("error never cloned") {
                         SyndApiError::Unauthorized { url } => {
                             tracing::warn!(
@@ -810,6 +1093,9 @@ impl Application {
                                 url.map(|url| url.to_string()).unwrap_or_default(),
                             )
                         }
+                        SyndApiError::BuildRequest(err) if unreachable => {
+                            format!("api unreachable: {err}")
+                        }
                         SyndApiError::BuildRequest(err) => {
                             format!("build request failed: {err} this is a BUG")
                         }
@@ -871,10 +1157,9 @@ impl Application {
 
     fn handle_terminal_event(&mut self, event: std::io::Result<CrosstermEvent>) -> Option<Command> {
         match event.unwrap() {
-            CrosstermEvent::Resize(columns, rows) => Some(Command::ResizeTerminal {
-                _columns: columns,
-                _rows: rows,
-            }),
+            CrosstermEvent::Resize(columns, rows) => {
+                Some(Command::ResizeTerminal { columns, rows })
+            }
             CrosstermEvent::FocusGained => {
                 self.should_render();
                 self.state.focus_gained()
@@ -952,13 +1237,15 @@ impl Application {
     }
 
     fn prompt_feed_edition(&mut self) {
-        let Some(feed) = self.components.subscription.selected_feed() else {
+        let feeds = self.components.subscription.marked_feeds();
+        if feeds.is_empty() {
             return;
-        };
+        }
 
+        let urls = feeds.iter().map(|feed| feed.url.clone()).collect_vec();
         let input = match self
             .interactor
-            .open_editor(InputParser::edit_feed_prompt(feed).as_str())
+            .open_editor(InputParser::edit_feeds_prompt(feeds).as_str())
         {
             Ok(input) => input,
             Err(err) => {
@@ -970,20 +1257,46 @@ impl Application {
         // the terminal state becomes strange after editing in the editor
         self.terminal.force_redraw();
 
-        let fut = match InputParser::new(input.as_str()).parse_feed_subscription(&self.categories) {
-            // Strictly, if the URL of the feed changed before and after an update
-            // it is not considered an edit, so it could be considered an error
-            // but currently we are allowing it
-            Ok(input) => async move { Ok(Command::SubscribeFeed { input }) }.boxed(),
-            Err(err) => async move {
-                Ok(Command::HandleError {
-                    message: err.to_string(),
-                })
+        match InputParser::new(input.as_str()).parse_feed_subscriptions(&self.categories) {
+            // Only the annotations of the unchanged feeds are updated without fetching them,
+            // and the feeds whose URL changed are subscribed as new ones
+            Ok(inputs) => {
+                let (updates, inputs): (Vec<_>, Vec<_>) = inputs
+                    .into_iter()
+                    .partition(|input| urls.contains(&input.url));
+                if !updates.is_empty() {
+                    self.update_feeds(updates.into_iter().map(UpdateFeedInput::from).collect());
+                }
+                for input in inputs {
+                    self.jobs
+                        .push(async move { Ok(Command::SubscribeFeed { input }) }.boxed());
+                }
+                self.components.subscription.update_marks(Marking::Clear);
+            }
+            Err(err) => {
+                let message = err.to_string();
+                self.jobs
+                    .push(async move { Ok(Command::HandleError { message }) }.boxed());
+            }
+        }
+    }
+
+    fn prompt_filter_name(&mut self) {
+        let input = match self.interactor.open_editor(InputParser::SAVE_FILTER_PROMPT) {
+            Ok(input) => input,
+            Err(err) => {
+                tracing::warn!("{err}");
+                return;
             }
-            .boxed(),
         };
+        // the terminal state becomes strange after editing in the editor
+        self.terminal.force_redraw();
 
-        self.jobs.push(fut);
+        if let Some(name) = InputParser::new(input.as_str()).parse_filter_name() {
+            self.components
+                .filter
+                .save_current(name, self.components.tabs.current().into());
+        }
     }
 
     fn subscribe_feed(&mut self, input: SubscribeFeedInput) {
@@ -1004,6 +1317,22 @@ impl Application {
         self.jobs.push(fut);
     }
 
+    fn update_feeds(&mut self, feeds: Vec<UpdateFeedInput>) {
+        let client = self.client.clone();
+        let request_seq = self.in_flight.add(RequestId::UpdateFeeds);
+        let fut = async move {
+            match client.update_feeds(feeds).await {
+                Ok(()) => Ok(Command::HandleApiResponse {
+                    request_seq,
+                    response: ApiResponse::UpdateFeeds,
+                }),
+                Err(err) => Ok(Command::api_error(err, request_seq)),
+            }
+        }
+        .boxed();
+        self.jobs.push(fut);
+    }
+
     fn unsubscribe_feed(&mut self, url: FeedUrl) {
         let client = self.client.clone();
         let request_seq = self.in_flight.add(RequestId::UnsubscribeFeed);
@@ -1022,17 +1351,9 @@ impl Application {
 
     fn mark_gh_notification_as_done(&mut self, all: bool) {
         let ids = if all {
-            Either::Left(
-                self.components
-                    .gh_notifications
-                    .marking_as_done_all()
-                    .into_iter(),
-            )
+            self.components.gh_notifications.marking_as_done_all()
         } else {
-            let Some(id) = self.components.gh_notifications.marking_as_done() else {
-                return;
-            };
-            Either::Right(std::iter::once(id))
+            self.components.gh_notifications.marking_as_done()
         };
 
         for id in ids {
@@ -1062,59 +1383,72 @@ impl Application {
     }
 
     fn unsubscribe_gh_thread(&mut self) {
-        let Some(id) = self
+        let ids = self
             .components
             .gh_notifications
-            .selected_notification()
-            .and_then(|n| n.thread_id)
-        else {
-            return;
-        };
-        let client = self.github_client.as_ref().unwrap().clone();
-        let request_seq = self.in_flight.add(RequestId::UnsubscribeGithubThread);
-        let fut = async move {
-            match client.unsubscribe_thread(id).await {
-                Ok(()) => Ok(Command::HandleApiResponse {
-                    request_seq,
-                    response: ApiResponse::UnsubscribeGithubThread {},
-                }),
-                Err(error) => Ok(Command::HandleGithubApiError {
-                    error: Arc::new(error),
-                    request_seq,
-                }),
+            .marked_notifications()
+            .into_iter()
+            .filter_map(|n| n.thread_id)
+            .collect_vec();
+        for id in ids {
+            let client = self.github_client.as_ref().unwrap().clone();
+            let request_seq = self.in_flight.add(RequestId::UnsubscribeGithubThread);
+            let fut = async move {
+                match client.unsubscribe_thread(id).await {
+                    Ok(()) => Ok(Command::HandleApiResponse {
+                        request_seq,
+                        response: ApiResponse::UnsubscribeGithubThread {},
+                    }),
+                    Err(error) => Ok(Command::HandleGithubApiError {
+                        error: Arc::new(error),
+                        request_seq,
+                    }),
+                }
             }
+            .boxed();
+            self.jobs.push(fut);
         }
-        .boxed();
-        self.jobs.push(fut);
     }
 }
 
 impl Application {
     fn open_feed(&mut self) {
-        let Some(feed_website_url) = self
+        let feed_website_urls = self
             .components
             .subscription
-            .selected_feed()
-            .and_then(|feed| feed.website_url.clone())
-        else {
-            return;
-        };
-        match Url::parse(&feed_website_url) {
-            Ok(url) => {
-                self.interactor.open_browser(url).ok();
-            }
-            Err(err) => {
-                tracing::warn!("Try to open invalid feed url: {feed_website_url} {err}");
+            .marked_feeds()
+            .into_iter()
+            .filter_map(|feed| feed.website_url.clone())
+            .collect_vec();
+        for feed_website_url in feed_website_urls {
+            match Url::parse(&feed_website_url) {
+                Ok(url) => {
+                    self.interactor.open_browser(url).ok();
+                }
+                Err(err) => {
+                    tracing::warn!("Try to open invalid feed url: {feed_website_url} {err}");
+                }
             }
         }
+        self.components.subscription.update_marks(Marking::Clear);
     }
 
     fn open_entry(&mut self) {
-        if let Some(url) = self.selected_entry_url() {
+        let urls = self
+            .components
+            .entries
+            .marked_entries()
+            .into_iter()
+            .filter_map(|entry| entry.website_url.as_deref())
+            .filter_map(parse_entry_url)
+            .collect_vec();
+        for url in urls {
             if let Err(err) = self.interactor.open_browser(url) {
                 self.handle_error_message(format!("open browser: {err}"), None);
+                break;
             }
         }
+        self.components.entries.update_marks(Marking::Clear);
     }
 
     fn browse_entry(&mut self) {
@@ -1126,27 +1460,98 @@ impl Application {
         }
     }
 
-    fn selected_entry_url(&self) -> Option<Url> {
-        let entry_website_url = self.components.entries.selected_entry_website_url()?;
-        match Url::parse(entry_website_url) {
-            Ok(url) => Some(url),
+    fn play_entry_media(&mut self) {
+        let Some(enclosure_url) = self.components.entries.selected_entry_enclosure_url() else {
+            return;
+        };
+        match Url::parse(enclosure_url) {
+            // Other schemes like `file` would let the feed open arbitrary resources with the player
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {
+                if let Err(err) = self.interactor.open_media_player(url) {
+                    self.handle_error_message(format!("open media player: {err}"), None);
+                }
+                self.terminal.force_redraw();
+            }
+            Ok(url) => {
+                self.handle_error_message(
+                    format!("unsupported enclosure url scheme: {}", url.scheme()),
+                    None,
+                );
+            }
             Err(err) => {
-                tracing::warn!("Try to open/browse invalid entry url: {entry_website_url} {err}");
-                None
+                tracing::warn!("Try to play invalid enclosure url: {enclosure_url} {err}");
             }
         }
     }
 
+    fn open_command_palette(&mut self) {
+        let keymaps = self.keymaps();
+        // Only the commands bound in the enabled keymaps are valid in the current state
+        let items = Command::names()
+            .filter(|name| *name != "open_command_palette")
+            .filter_map(|name| {
+                keymaps
+                    .binding(name)
+                    .map(|binding| PaletteItem::new(name, binding))
+            })
+            .collect();
+        let prompt = self.components.command_palette.open(items);
+        self.keymaps().focus(KeymapId::CommandPalette);
+        self.key_handlers.push(event::KeyHandler::Prompt(prompt));
+    }
+
+    fn close_command_palette(&mut self) {
+        self.components.command_palette.close();
+        self.keymaps().unfocus();
+        self.key_handlers.remove_prompt();
+    }
+
+    fn follow_reader_link(&mut self) {
+        let Some(link) = self.components.reader.take_link() else {
+            return;
+        };
+        match link {
+            // Other schemes like `file` would let the content open arbitrary resources
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {
+                if let Err(err) = self.interactor.open_browser(url) {
+                    self.handle_error_message(format!("open browser: {err}"), None);
+                }
+            }
+            Ok(url) => {
+                self.handle_error_message(
+                    format!("unsupported link url scheme: {}", url.scheme()),
+                    None,
+                );
+            }
+            Err(message) => self.handle_error_message(message, None),
+        }
+    }
+
+    fn selected_entry_url(&self) -> Option<Url> {
+        parse_entry_url(self.components.entries.selected_entry_website_url()?)
+    }
+
     fn open_notification(&mut self) {
-        let Some(notification_url) = self
+        let notification_urls = self
             .components
             .gh_notifications
-            .selected_notification()
-            .and_then(Notification::browser_url)
-        else {
-            return;
-        };
-        self.interactor.open_browser(notification_url).ok();
+            .marked_notifications()
+            .into_iter()
+            .filter_map(Notification::browser_url)
+            .collect_vec();
+        for notification_url in notification_urls {
+            self.interactor.open_browser(notification_url).ok();
+        }
+    }
+}
+
+fn parse_entry_url(entry_website_url: &str) -> Option<Url> {
+    match Url::parse(entry_website_url) {
+        Ok(url) => Some(url),
+        Err(err) => {
+            tracing::warn!("Try to open/browse invalid entry url: {entry_website_url} {err}");
+            None
+        }
     }
 }
 
@@ -1197,6 +1602,33 @@ impl Application {
         self.jobs.push(fut);
     }
 
+    #[tracing::instrument(skip(self))]
+    fn fetch_entry_content(&mut self, feed_url: FeedUrl, id: String) {
+        let client = self.client.clone();
+        let request_seq = self.in_flight.add(RequestId::FetchEntryContent);
+        let fut = async move {
+            match client
+                .fetch_entry_content(feed_url.clone(), id.clone())
+                .await
+            {
+                Ok(content_html) => Ok(Command::HandleApiResponse {
+                    request_seq,
+                    response: ApiResponse::FetchEntryContent {
+                        feed_url,
+                        id,
+                        content_html,
+                    },
+                }),
+                Err(error) => Ok(Command::HandleApiError {
+                    error: Arc::new(error),
+                    request_seq,
+                }),
+            }
+        }
+        .boxed();
+        self.jobs.push(fut);
+    }
+
     #[tracing::instrument(skip(self))]
     fn fetch_gh_notifications(&mut self, populate: Populate, params: FetchNotificationsParams) {
         let client = self
@@ -1381,6 +1813,32 @@ impl Application {
                 .boxed();
                 self.background_jobs.push(fut);
             }
+            Credential::Oidc {
+                refresh_token,
+                expired_at,
+                ..
+            } => {
+                let until_expire = expired_at
+                    .sub(config::credential::EXPIRE_MARGIN)
+                    .sub(self.now())
+                    .to_std()
+                    .unwrap_or(config::credential::FALLBACK_EXPIRE);
+                let jwt_service = self.jwt_service().clone();
+                let refresh_token = refresh_token.clone();
+                let fut = async move {
+                    tokio::time::sleep(until_expire).await;
+
+                    tracing::debug!("Refresh oidc credential");
+                    match jwt_service.refresh_oidc_id_token(&refresh_token).await {
+                        Ok(credential) => Ok(Command::RefreshCredential { credential }),
+                        Err(err) => Ok(Command::HandleError {
+                            message: err.to_string(),
+                        }),
+                    }
+                }
+                .boxed();
+                self.background_jobs.push(fut);
+            }
         }
     }
 }
@@ -1402,14 +1860,22 @@ impl Application {
     }
 
     fn rotate_theme(&mut self) {
-        let p = match self.theme.name {
-            "ferra" => Palette::solarized_dark(),
-            "solarized_dark" => Palette::helix(),
-            "helix" => Palette::dracula(),
-            "dracula" => Palette::eldritch(),
-            _ => Palette::ferra(),
-        };
-        self.theme = Theme::with_palette(p);
+        // User defined themes replace the builtin ones with the same name
+        let mut themes = Theme::builtins();
+        for user_theme in &self.config.themes {
+            match themes
+                .iter_mut()
+                .find(|theme| theme.name == user_theme.name)
+            {
+                Some(theme) => *theme = user_theme.clone(),
+                None => themes.push(user_theme.clone()),
+            }
+        }
+        let next = themes
+            .iter()
+            .position(|theme| theme.name == self.theme.name)
+            .map_or(0, |current| (current + 1) % themes.len());
+        self.theme = themes.swap_remove(next);
     }
 }
 
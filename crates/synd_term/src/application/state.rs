@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+
 use crate::command::Command;
 
 use bitflags::bitflags;
@@ -19,6 +21,10 @@ pub(crate) enum TerminalFocus {
 pub(super) struct State {
     pub(super) flags: Should,
     focus: TerminalFocus,
+    /// When the shown entries were fetched from the api
+    pub(super) entries_fetched_at: Option<DateTime<Utc>>,
+    /// When the shown subscription was fetched from the api
+    pub(super) subscription_fetched_at: Option<DateTime<Utc>>,
 }
 
 impl State {
@@ -26,6 +32,8 @@ impl State {
         Self {
             flags: Should::empty(),
             focus: TerminalFocus::Gained,
+            entries_fetched_at: None,
+            subscription_fetched_at: None,
         }
     }
 
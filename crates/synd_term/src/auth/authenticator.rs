@@ -14,23 +14,34 @@ use crate::{
 pub struct DeviceFlows {
     pub github: DeviceFlow<provider::Github>,
     pub google: DeviceFlow<provider::Google>,
+    pub oidc: Option<DeviceFlow<provider::Oidc>>,
 }
 
 #[derive(Clone)]
 pub struct JwtService {
     pub google: jwt::google::JwtService,
+    pub oidc: Option<jwt::oidc::JwtService>,
 }
 
 impl JwtService {
     pub fn new() -> Self {
         Self {
             google: jwt::google::JwtService::default(),
+            oidc: None,
         }
     }
 
     #[must_use]
     pub fn with_google_jwt_service(self, google: jwt::google::JwtService) -> Self {
-        Self { google }
+        Self { google, ..self }
+    }
+
+    #[must_use]
+    pub fn with_oidc_jwt_service(self, oidc: jwt::oidc::JwtService) -> Self {
+        Self {
+            oidc: Some(oidc),
+            ..self
+        }
     }
 
     pub(crate) async fn refresh_google_id_token(
@@ -54,6 +65,30 @@ impl JwtService {
         };
         Ok(Verified(credential))
     }
+
+    pub(crate) async fn refresh_oidc_id_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<Verified<Credential>, CredentialError> {
+        let oidc = self
+            .oidc
+            .as_ref()
+            .ok_or(CredentialError::OidcNotConfigured)?;
+        let id_token = oidc
+            .refresh_id_token(refresh_token)
+            .await
+            .map_err(CredentialError::RefreshJwt)?;
+        let expired_at = oidc
+            .decode_id_token_insecure(&id_token, false)
+            .map_err(CredentialError::DecodeJwt)?
+            .expired_at();
+        let credential = Credential::Oidc {
+            id_token,
+            refresh_token: refresh_token.to_owned(),
+            expired_at,
+        };
+        Ok(Verified(credential))
+    }
 }
 
 #[derive(Clone)]
@@ -68,6 +103,7 @@ impl Authenticator {
             device_flows: DeviceFlows {
                 github: DeviceFlow::new(provider::Github::default()),
                 google: DeviceFlow::new(provider::Google::default()),
+                oidc: None,
             },
             jwt_service: JwtService::new(),
         }
@@ -89,6 +125,18 @@ impl Authenticator {
         }
     }
 
+    /// Return the authentication providers available for login
+    pub(crate) fn providers(&self) -> Vec<AuthenticationProvider> {
+        let mut providers = vec![
+            AuthenticationProvider::Github,
+            AuthenticationProvider::Google,
+        ];
+        if self.device_flows.oidc.is_some() && self.jwt_service.oidc.is_some() {
+            providers.push(AuthenticationProvider::Oidc);
+        }
+        providers
+    }
+
     pub(crate) async fn init_device_flow(
         &self,
         provider: AuthenticationProvider,
@@ -101,6 +149,10 @@ impl Authenticator {
             AuthenticationProvider::Google => {
                 self.device_flows.google.device_authorize_request().await
             }
+
+            AuthenticationProvider::Oidc => {
+                self.oidc_device_flow()?.device_authorize_request().await
+            }
         }
     }
 
@@ -146,6 +198,39 @@ impl Authenticator {
                     expired_at,
                 }))
             }
+            AuthenticationProvider::Oidc => {
+                let token_response = self
+                    .oidc_device_flow()?
+                    .poll_device_access_token(response.device_code, response.interval)
+                    .await?;
+
+                let id_token = token_response
+                    .id_token
+                    .ok_or(CredentialError::OidcIdTokenNotFound)?;
+                let refresh_token = token_response
+                    .refresh_token
+                    .ok_or(CredentialError::OidcRefreshTokenNotFound)?;
+                let expired_at = self
+                    .jwt_service
+                    .oidc
+                    .as_ref()
+                    .and_then(|oidc| oidc.decode_id_token_insecure(&id_token, false).ok())
+                    .map_or(now.add(config::credential::FALLBACK_EXPIRE), |claims| {
+                        claims.expired_at()
+                    });
+                Ok(Verified(Credential::Oidc {
+                    id_token,
+                    refresh_token,
+                    expired_at,
+                }))
+            }
         }
     }
+
+    fn oidc_device_flow(&self) -> Result<&DeviceFlow<provider::Oidc>, CredentialError> {
+        self.device_flows
+            .oidc
+            .as_ref()
+            .ok_or(CredentialError::OidcNotConfigured)
+    }
 }
@@ -23,6 +23,7 @@ pub mod authenticator;
 pub enum AuthenticationProvider {
     Github,
     Google,
+    Oidc,
 }
 
 #[derive(Debug, Error)]
@@ -31,6 +32,16 @@ pub enum CredentialError {
     GoogleJwtExpired { refresh_token: String },
     #[error("google jwt email not verified")]
     GoogleJwtEmailNotVerified,
+    #[error("oidc jwt email not verified")]
+    OidcJwtEmailNotVerified,
+    #[error("oidc provider is not configured")]
+    OidcNotConfigured,
+    #[error("oidc token response does not contain id token. make sure to request `openid` scope")]
+    OidcIdTokenNotFound,
+    #[error(
+        "oidc token response does not contain refresh token. make sure to request `offline_access` scope"
+    )]
+    OidcRefreshTokenNotFound,
     #[error("decode jwt: {0}")]
     DecodeJwt(JwtError),
     #[error("refresh jwt id token: {0}")]
@@ -51,6 +62,11 @@ pub enum Credential {
         refresh_token: String,
         expired_at: DateTime<Utc>,
     },
+    Oidc {
+        id_token: String,
+        refresh_token: String,
+        expired_at: DateTime<Utc>,
+    },
 }
 
 impl fmt::Debug for Credential {
@@ -119,25 +135,43 @@ impl Unverified<Credential> {
                 if !claims.email_verified {
                     return Err(CredentialError::GoogleJwtEmailNotVerified);
                 }
-                match claims
-                    .expired_at()
-                    .sub(config::credential::EXPIRE_MARGIN)
-                    .cmp(&now)
-                {
-                    // expired
-                    Ordering::Less | Ordering::Equal => {
-                        debug!("Google jwt expired, trying to refresh");
-
-                        Ok(VerifyResult::Expired(Expired(credential)))
-                    }
-                    // not expired
-                    Ordering::Greater => Ok(VerifyResult::Verified(Verified(credential))),
+                if is_expired(claims.expired_at(), now) {
+                    debug!("Google jwt expired, trying to refresh");
+
+                    Ok(VerifyResult::Expired(Expired(credential)))
+                } else {
+                    Ok(VerifyResult::Verified(Verified(credential)))
+                }
+            }
+            Credential::Oidc { id_token, .. } => {
+                let claims = jwt_service
+                    .oidc
+                    .as_ref()
+                    .ok_or(CredentialError::OidcNotConfigured)?
+                    .decode_id_token_insecure(id_token, false)
+                    .map_err(CredentialError::DecodeJwt)?;
+                if claims.verified_email().is_none() {
+                    return Err(CredentialError::OidcJwtEmailNotVerified);
+                }
+                if is_expired(claims.expired_at(), now) {
+                    debug!("Oidc jwt expired, trying to refresh");
+
+                    Ok(VerifyResult::Expired(Expired(credential)))
+                } else {
+                    Ok(VerifyResult::Verified(Verified(credential)))
                 }
             }
         }
     }
 }
 
+fn is_expired(expired_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    match expired_at.sub(config::credential::EXPIRE_MARGIN).cmp(&now) {
+        Ordering::Less | Ordering::Equal => true,
+        Ordering::Greater => false,
+    }
+}
+
 /// Process for restoring credential from cache
 pub(crate) struct Restore<'a> {
     pub(crate) jwt_service: &'a JwtService,
@@ -167,6 +201,15 @@ impl Restore<'_> {
 
                 Ok(cred)
             }
+            VerifyResult::Expired(Expired(Credential::Oidc { refresh_token, .. })) => {
+                let cred = jwt_service.refresh_oidc_id_token(&refresh_token).await?;
+
+                if persist_when_refreshed {
+                    cache.persist_credential(&cred)?;
+                }
+
+                Ok(cred)
+            }
             VerifyResult::Expired(_) => panic!("Unexpected verify result. this is bug"),
         }
     }
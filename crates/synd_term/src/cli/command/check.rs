@@ -1,6 +1,6 @@
 use std::{io, path::Path, process::ExitCode, time::Duration};
 
-use anyhow::Context;
+use anyhow::{Context, bail};
 use clap::Args;
 use synd_o11y::health_check::Health;
 
@@ -44,6 +44,11 @@ impl CheckCommand {
         let cache_dir = config.cache_dir();
         let log_path = config.log_file();
         let config_path = config.config_file();
+        let keymap_errors = config
+            .keymap_errors()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
 
         match format {
             CheckFormat::Human => {
@@ -53,6 +58,7 @@ impl CheckCommand {
                     &config_path,
                     &cache_dir,
                     log_path.as_path(),
+                    &keymap_errors,
                 )?;
             }
             CheckFormat::Json => {
@@ -67,11 +73,19 @@ impl CheckCommand {
                         "config": config_path.display().to_string(),
                         "cache": cache_dir.display().to_string(),
                         "log": log_path.display().to_string(),
+                        "keymap": if keymap_errors.is_empty() {
+                            serde_json::json!("ok")
+                        } else {
+                            serde_json::json!(&keymap_errors)
+                        },
                     })
                 );
             }
         }
 
+        if !keymap_errors.is_empty() {
+            bail!("invalid keymap configuration");
+        }
         Ok(())
     }
 
@@ -81,6 +95,7 @@ impl CheckCommand {
         config_path: &Path,
         cache_dir: &Path,
         log_path: &Path,
+        keymap_errors: &[String],
     ) -> io::Result<()> {
         let w = &mut writer;
 
@@ -100,6 +115,14 @@ impl CheckCommand {
         writeln!(w, "     Config: {}", config_path.display())?;
         writeln!(w, "      Cache: {}", cache_dir.display())?;
         writeln!(w, "        Log: {}", log_path.display())?;
+        if keymap_errors.is_empty() {
+            writeln!(w, "     Keymap: ok")?;
+        } else {
+            writeln!(w, "     Keymap: {} errors", keymap_errors.len())?;
+            for err in keymap_errors {
+                writeln!(w, "             {err}")?;
+            }
+        }
         Ok(())
     }
 }
@@ -54,6 +54,8 @@ pub struct Args {
     pub feed: FeedOptions,
     #[command(flatten)]
     pub github: GithubOptions,
+    #[command(flatten)]
+    pub oidc: OidcOptions,
     #[arg(hide = true, long = "dry-run", hide_long_help = true)]
     pub dry_run: bool,
 }
@@ -81,6 +83,12 @@ pub struct FeedOptions {
     /// Args for launching the browser command
     #[arg(long, env = config::env::FEED_BROWSER_ARGS)]
     pub browser_args: Option<Vec<String>>,
+    /// Media player command to open entry enclosure
+    #[arg(long, env = config::env::FEED_PLAYER)]
+    pub player: Option<PathBuf>,
+    /// Args for launching the media player command
+    #[arg(long, env = config::env::FEED_PLAYER_ARGS)]
+    pub player_args: Option<Vec<String>>,
 }
 
 #[derive(clap::Args, Debug)]
@@ -103,6 +111,24 @@ pub struct GithubOptions {
     pub github_pat: Option<String>,
 }
 
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "OIDC options")]
+pub struct OidcOptions {
+    /// `OpenID` Connect issuer url to login with
+    #[arg(long, env = config::env::OIDC_ISSUER)]
+    pub oidc_issuer: Option<Url>,
+    /// `OpenID` Connect client id
+    #[arg(long, env = config::env::OIDC_CLIENT_ID)]
+    pub oidc_client_id: Option<String>,
+    /// `OpenID` Connect client secret if the provider requires it
+    #[arg(
+        long,
+        env = config::env::OIDC_CLIENT_SECRET,
+        hide_env_values = true,
+    )]
+    pub oidc_client_secret: Option<String>,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Command {
     #[command(alias = "clear")]
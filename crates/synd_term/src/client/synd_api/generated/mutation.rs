@@ -4,7 +4,7 @@ pub mod subscribe_feed {
     #![allow(dead_code)]
     use std::result::Result;
     pub const OPERATION_NAME: &str = "SubscribeFeed";
-    pub const QUERY: &str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n";
+    pub const QUERY: &str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation UpdateFeeds($updateInput: UpdateFeedsInput!) {\n  updateFeeds(input: $updateInput) {\n    __typename\n    ... on UpdateFeedsSuccess {\n      status {\n        code\n      }\n    }\n    ... on UpdateFeedsError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summaryText\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n";
     use super::*;
     use serde::{Deserialize, Serialize};
     #[allow(dead_code)]
@@ -163,7 +163,8 @@ pub mod subscribe_feed {
         pub title: Option<String>,
         pub published: Option<Rfc3339Time>,
         pub updated: Option<Rfc3339Time>,
-        pub summary: Option<String>,
+        #[serde(rename = "summaryText")]
+        pub summary_text: Option<String>,
     }
     #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
     pub struct Link {
@@ -220,7 +221,7 @@ pub mod unsubscribe_feed {
     #![allow(dead_code)]
     use std::result::Result;
     pub const OPERATION_NAME: &str = "UnsubscribeFeed";
-    pub const QUERY: &str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n";
+    pub const QUERY: &str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation UpdateFeeds($updateInput: UpdateFeedsInput!) {\n  updateFeeds(input: $updateInput) {\n    __typename\n    ... on UpdateFeedsSuccess {\n      status {\n        code\n      }\n    }\n    ... on UpdateFeedsError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summaryText\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n";
     use super::*;
     use serde::{Deserialize, Serialize};
     #[allow(dead_code)]
@@ -315,3 +316,139 @@ impl graphql_client::GraphQLQuery for UnsubscribeFeed {
         }
     }
 }
+pub struct UpdateFeeds;
+pub mod update_feeds {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "UpdateFeeds";
+    pub const QUERY: &str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation UpdateFeeds($updateInput: UpdateFeedsInput!) {\n  updateFeeds(input: $updateInput) {\n    __typename\n    ... on UpdateFeedsSuccess {\n      status {\n        code\n      }\n    }\n    ... on UpdateFeedsError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summaryText\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n";
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    type Category = crate::client::synd_api::scalar::Category;
+    type FeedUrl = crate::client::synd_api::scalar::FeedUrl;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum Requirement {
+        MUST,
+        SHOULD,
+        MAY,
+        Other(String),
+    }
+    impl ::serde::Serialize for Requirement {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                Requirement::MUST => "MUST",
+                Requirement::SHOULD => "SHOULD",
+                Requirement::MAY => "MAY",
+                Requirement::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for Requirement {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "MUST" => Ok(Requirement::MUST),
+                "SHOULD" => Ok(Requirement::SHOULD),
+                "MAY" => Ok(Requirement::MAY),
+                _ => Ok(Requirement::Other(s)),
+            }
+        }
+    }
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        FEED_UNAVAILABLE,
+        INTERNAL_ERROR,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UpdateFeedInput {
+        pub url: FeedUrl,
+        pub requirement: Option<Requirement>,
+        pub category: Option<Category>,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UpdateFeedsInput {
+        pub feeds: Vec<UpdateFeedInput>,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        #[serde(rename = "updateInput")]
+        pub update_input: UpdateFeedsInput,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "updateFeeds")]
+        pub update_feeds: UpdateFeedsUpdateFeeds,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum UpdateFeedsUpdateFeeds {
+        UpdateFeedsSuccess(UpdateFeedsUpdateFeedsOnUpdateFeedsSuccess),
+        UpdateFeedsError(UpdateFeedsUpdateFeedsOnUpdateFeedsError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UpdateFeedsUpdateFeedsOnUpdateFeedsSuccess {
+        pub status: UpdateFeedsUpdateFeedsOnUpdateFeedsSuccessStatus,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UpdateFeedsUpdateFeedsOnUpdateFeedsSuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UpdateFeedsUpdateFeedsOnUpdateFeedsError {
+        pub status: UpdateFeedsUpdateFeedsOnUpdateFeedsErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UpdateFeedsUpdateFeedsOnUpdateFeedsErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for UpdateFeeds {
+    type Variables = update_feeds::Variables;
+    type ResponseData = update_feeds::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: update_feeds::QUERY,
+            operation_name: update_feeds::OPERATION_NAME,
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use std::{fmt::Debug, time::Duration};
 
 use anyhow::anyhow;
-use graphql_client::{GraphQLQuery, Response};
+use graphql_client::{GraphQLQuery, QueryBody, Response};
 use reqwest::header::{self, HeaderValue};
 use serde::{Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
 use synd_o11y::{health_check::Health, opentelemetry::extension::*};
 use thiserror::Error;
 use tracing::{Span, error};
@@ -47,6 +48,71 @@ pub enum SyndApiError {
     Internal(#[from] anyhow::Error),
 }
 
+impl SyndApiError {
+    /// Whether the request failed before reaching the api
+    pub(crate) fn is_unreachable(&self) -> bool {
+        let SyndApiError::BuildRequest(err) = self else {
+            return false;
+        };
+        // Name resolution and tls handshake failures are io errors which are not always classified as connect errors
+        err.is_connect()
+            || err.is_timeout()
+            || std::iter::successors(std::error::Error::source(err), |err| err.source())
+                .any(<dyn std::error::Error>::is::<std::io::Error>)
+    }
+}
+
+/// Request body of automatic persisted queries
+/// <https://www.apollographql.com/docs/apollo-server/performance/apq>
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQueryBody<'a, Variables> {
+    variables: &'a Variables,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<&'static str>,
+    operation_name: &'static str,
+    extensions: PersistedQueryExtensions,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQueryExtensions {
+    persisted_query: PersistedQuery,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQuery {
+    version: u8,
+    sha256_hash: String,
+}
+
+impl<'a, Variables> PersistedQueryBody<'a, Variables> {
+    /// Error message returned when the server does not know the hash
+    const NOT_FOUND: &'static str = "PersistedQueryNotFound";
+
+    fn hash_only(body: &'a QueryBody<Variables>) -> Self {
+        Self {
+            variables: &body.variables,
+            query: None,
+            operation_name: body.operation_name,
+            extensions: PersistedQueryExtensions {
+                persisted_query: PersistedQuery {
+                    version: 1,
+                    sha256_hash: format!("{:x}", Sha256::digest(body.query.as_bytes())),
+                },
+            },
+        }
+    }
+
+    fn with_query(self, query: &'static str) -> Self {
+        Self {
+            query: Some(query),
+            ..self
+        }
+    }
+}
+
 /// synd-api client
 #[derive(Clone)]
 pub struct Client {
@@ -79,6 +145,7 @@ impl Client {
         let mut token = HeaderValue::try_from(match cred.into_inner() {
             Credential::Github { access_token } => format!("github {access_token}"),
             Credential::Google { id_token, .. } => format!("google {id_token}"),
+            Credential::Oidc { id_token, .. } => format!("oidc {id_token}"),
         })
         .unwrap();
         token.set_sensitive(true);
@@ -157,6 +224,64 @@ impl Client {
         }
     }
 
+    /// Update the requirement and category of the subscribed feeds without fetching them.
+    /// Feeds are sent in chunks which fit the body limit, so the chunks sent before an error are applied
+    #[tracing::instrument(skip(self))]
+    pub async fn update_feeds(
+        &self,
+        feeds: Vec<mutation::update_feeds::UpdateFeedInput>,
+    ) -> Result<(), SyndApiError> {
+        for feeds in Self::chunk_update_feeds(feeds, config::client::BODY_LIMIT_BYTES) {
+            let request = Self::update_feeds_query(feeds);
+            let response: mutation::update_feeds::ResponseData = self.request(&request).await?;
+
+            if let mutation::update_feeds::UpdateFeedsUpdateFeeds::UpdateFeedsError(err) =
+                response.update_feeds
+            {
+                return Err(SyndApiError::Internal(anyhow!(
+                    "Failed to mutate update_feeds: {}",
+                    err.message
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn update_feeds_query(
+        feeds: Vec<mutation::update_feeds::UpdateFeedInput>,
+    ) -> QueryBody<mutation::update_feeds::Variables> {
+        mutation::UpdateFeeds::build_query(mutation::update_feeds::Variables {
+            update_input: mutation::update_feeds::UpdateFeedsInput { feeds },
+        })
+    }
+
+    /// Split feeds so that the request registering the persisted query does not exceed the body limit.
+    /// A feed which alone exceeds the limit is sent by itself
+    fn chunk_update_feeds(
+        feeds: Vec<mutation::update_feeds::UpdateFeedInput>,
+        body_limit: usize,
+    ) -> Vec<Vec<mutation::update_feeds::UpdateFeedInput>> {
+        let body_len = |feeds: &[mutation::update_feeds::UpdateFeedInput]| {
+            let request = Self::update_feeds_query(feeds.to_vec());
+            let body = PersistedQueryBody::hash_only(&request).with_query(request.query);
+            serde_json::to_vec(&body).map_or(usize::MAX, |body| body.len())
+        };
+
+        let mut chunks = Vec::new();
+        let mut chunk = Vec::new();
+        for feed in feeds {
+            chunk.push(feed);
+            if chunk.len() > 1 && body_len(&chunk) > body_limit {
+                let feed = chunk.pop().unwrap();
+                chunks.push(std::mem::replace(&mut chunk, vec![feed]));
+            }
+        }
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+        chunks
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn fetch_entries(
         &self,
@@ -174,6 +299,20 @@ impl Client {
         Ok(response.output.into())
     }
 
+    /// Fetch the content of the entry which is not included in the entries to keep them small
+    #[tracing::instrument(skip(self))]
+    pub async fn fetch_entry_content(
+        &self,
+        feed_url: FeedUrl,
+        id: String,
+    ) -> Result<Option<String>, SyndApiError> {
+        let var = query::entry_content::Variables { feed_url, id };
+        let request = query::EntryContent::build_query(var);
+        let response: query::entry_content::ResponseData = self.request(&request).await?;
+
+        Ok(response.output.entry.and_then(|entry| entry.content_html))
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn export_subscription(
         &self,
@@ -187,8 +326,31 @@ impl Client {
         Ok(response.output.into())
     }
 
+    /// Send the query by its hash first, then send the query itself if the server does not know the hash
     #[tracing::instrument(skip_all, err(Display))]
-    async fn request<Body, ResponseData>(&self, body: &Body) -> Result<ResponseData, SyndApiError>
+    async fn request<Variables, ResponseData>(
+        &self,
+        body: &QueryBody<Variables>,
+    ) -> Result<ResponseData, SyndApiError>
+    where
+        Variables: Serialize + Debug,
+        ResponseData: DeserializeOwned + Debug,
+    {
+        let persisted = PersistedQueryBody::hash_only(body);
+        match self.send(&persisted).await {
+            Err(SyndApiError::Graphql { errors })
+                if errors
+                    .iter()
+                    .any(|err| err.message == PersistedQueryBody::<Variables>::NOT_FOUND) =>
+            {
+                tracing::debug!(operation = body.operation_name, "Register persisted query");
+                self.send(&persisted.with_query(body.query)).await
+            }
+            result => result,
+        }
+    }
+
+    async fn send<Body, ResponseData>(&self, body: &Body) -> Result<ResponseData, SyndApiError>
     where
         Body: Serialize + Debug + ?Sized,
         ResponseData: DeserializeOwned + Debug,
@@ -250,3 +412,79 @@ impl Client {
             .map_err(anyhow::Error::from)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::{io::AsyncWriteExt as _, net::TcpListener};
+
+    use super::*;
+
+    async fn health_error(endpoint: &str) -> SyndApiError {
+        let client = Client::new(endpoint.parse().unwrap(), Duration::from_secs(5)).unwrap();
+        let err = client
+            .client
+            .get(client.endpoint.join(Client::HEALTH_CHECK).unwrap())
+            .send()
+            .await
+            .unwrap_err();
+        SyndApiError::BuildRequest(err)
+    }
+
+    #[tokio::test]
+    async fn unreachable_errors() {
+        // Name resolution failure
+        assert!(health_error("https://synd.invalid").await.is_unreachable());
+
+        // Connection refused
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        assert!(
+            health_error(&format!("https://{addr}"))
+                .await
+                .is_unreachable()
+        );
+
+        // Tls handshake failure
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n")
+                .await
+                .ok();
+        });
+        assert!(
+            health_error(&format!("https://{addr}"))
+                .await
+                .is_unreachable()
+        );
+
+        assert!(!SyndApiError::Unauthorized { url: None }.is_unreachable());
+    }
+
+    #[test]
+    fn chunk_update_feeds_within_body_limit() {
+        let feeds = (0..100)
+            .map(|i| mutation::update_feeds::UpdateFeedInput {
+                url: FeedUrl::parse(&format!("https://example.com/{i}/feed.xml")).unwrap(),
+                requirement: Some(mutation::update_feeds::Requirement::MUST),
+                category: Some(Category::new(format!("category-{i}")).unwrap()),
+            })
+            .collect::<Vec<_>>();
+
+        let chunks = Client::chunk_update_feeds(feeds.clone(), config::client::BODY_LIMIT_BYTES);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            let request = Client::update_feeds_query(chunk.clone());
+            let body = PersistedQueryBody::hash_only(&request).with_query(request.query);
+            assert!(serde_json::to_vec(&body).unwrap().len() <= config::client::BODY_LIMIT_BYTES);
+        }
+        assert_eq!(chunks.concat(), feeds);
+
+        assert!(
+            Client::chunk_update_feeds(Vec::new(), config::client::BODY_LIMIT_BYTES).is_empty()
+        );
+    }
+}
@@ -19,7 +19,9 @@ use crate::{
             PullRequestState, Reason,
         },
     },
-    ui::components::{filter::FilterLane, gh_notifications::GhNotificationFilterUpdater},
+    ui::components::{
+        collections::Marking, filter::FilterLane, gh_notifications::GhNotificationFilterUpdater,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -37,6 +39,7 @@ pub(crate) enum ApiResponse {
     UnsubscribeFeed {
         url: FeedUrl,
     },
+    UpdateFeeds,
     FetchSubscription {
         populate: Populate,
         subscription: SubscriptionOutput,
@@ -45,6 +48,11 @@ pub(crate) enum ApiResponse {
         populate: Populate,
         payload: payload::FetchEntriesPayload,
     },
+    FetchEntryContent {
+        feed_url: FeedUrl,
+        id: String,
+        content_html: Option<String>,
+    },
     FetchGithubNotifications {
         populate: Populate,
         notifications: Vec<Notification>,
@@ -69,6 +77,7 @@ impl Display for ApiResponse {
             ApiResponse::DeviceFlowCredential { .. } => f.write_str("DeviceFlowCredential"),
             ApiResponse::FetchSubscription { .. } => f.write_str("FetchSubscription"),
             ApiResponse::FetchEntries { .. } => f.write_str("FetchEntries"),
+            ApiResponse::FetchEntryContent { .. } => f.write_str("FetchEntryContent"),
             ApiResponse::FetchGithubNotifications { .. } => f.write_str("FetchGithubNotifications"),
             ApiResponse::FetchGithubIssue { .. } => f.write_str("FetchGithubIssue"),
             ApiResponse::FetchGithubPullRequest { .. } => f.write_str("FetchGithubPullRequest"),
@@ -82,8 +91,8 @@ pub(crate) enum Command {
     Nop,
     Quit,
     ResizeTerminal {
-        _columns: u16,
-        _rows: u16,
+        columns: u16,
+        rows: u16,
     },
     RenderThrobber,
     Idle,
@@ -131,8 +140,39 @@ pub(crate) enum Command {
     MoveEntry(Direction),
     MoveEntryFirst,
     MoveEntryLast,
+    RotateEntrySort,
+    RotateEntryGrouping,
+    ToggleEntryGroup,
+    ToggleAllEntryGroups,
     OpenEntry,
     BrowseEntry,
+    PlayEntryMedia,
+
+    // Reader
+    OpenReader,
+    CloseReader,
+    ScrollReader(Direction),
+    ScrollReaderPage(Direction),
+    ScrollReaderTop,
+    ScrollReaderBottom,
+    InputReaderLinkNumber {
+        digit: u8,
+    },
+    FollowReaderLink,
+
+    // Command palette
+    OpenCommandPalette,
+    CloseCommandPalette,
+    MoveCommandPaletteSelection(Direction),
+    ExecuteCommandPalette,
+
+    // Help
+    OpenHelp,
+    CloseHelp,
+    ScrollHelp(Direction),
+
+    // Marks for the bulk operations on the current tab
+    UpdateMarks(Marking),
 
     // Filter
     MoveFilterRequirement(Direction),
@@ -150,6 +190,8 @@ pub(crate) enum Command {
     DeactivateAllFilterCategories {
         lane: FilterLane,
     },
+    MoveSavedFilter(Direction),
+    SaveFilter,
 
     // Theme
     RotateTheme,
@@ -259,9 +301,78 @@ impl Command {
     pub fn open_entry() -> Self {
         Command::OpenEntry
     }
+    pub fn rotate_entry_sort() -> Self {
+        Command::RotateEntrySort
+    }
+    pub fn rotate_entry_grouping() -> Self {
+        Command::RotateEntryGrouping
+    }
+    pub fn toggle_entry_group() -> Self {
+        Command::ToggleEntryGroup
+    }
+    pub fn toggle_all_entry_groups() -> Self {
+        Command::ToggleAllEntryGroups
+    }
     pub fn browse_entry() -> Self {
         Command::BrowseEntry
     }
+    pub fn play_entry_media() -> Self {
+        Command::PlayEntryMedia
+    }
+    pub fn open_reader() -> Self {
+        Command::OpenReader
+    }
+    pub fn close_reader() -> Self {
+        Command::CloseReader
+    }
+    pub fn scroll_up_reader() -> Self {
+        Command::ScrollReader(Direction::Up)
+    }
+    pub fn scroll_down_reader() -> Self {
+        Command::ScrollReader(Direction::Down)
+    }
+    pub fn scroll_up_reader_page() -> Self {
+        Command::ScrollReaderPage(Direction::Up)
+    }
+    pub fn scroll_down_reader_page() -> Self {
+        Command::ScrollReaderPage(Direction::Down)
+    }
+    pub fn scroll_reader_top() -> Self {
+        Command::ScrollReaderTop
+    }
+    pub fn scroll_reader_bottom() -> Self {
+        Command::ScrollReaderBottom
+    }
+    pub fn follow_reader_link() -> Self {
+        Command::FollowReaderLink
+    }
+    pub fn open_command_palette() -> Self {
+        Command::OpenCommandPalette
+    }
+    pub fn close_command_palette() -> Self {
+        Command::CloseCommandPalette
+    }
+    pub fn move_up_command_palette_selection() -> Self {
+        Command::MoveCommandPaletteSelection(Direction::Up)
+    }
+    pub fn move_down_command_palette_selection() -> Self {
+        Command::MoveCommandPaletteSelection(Direction::Down)
+    }
+    pub fn execute_command_palette() -> Self {
+        Command::ExecuteCommandPalette
+    }
+    pub fn open_help() -> Self {
+        Command::OpenHelp
+    }
+    pub fn close_help() -> Self {
+        Command::CloseHelp
+    }
+    pub fn scroll_up_help() -> Self {
+        Command::ScrollHelp(Direction::Up)
+    }
+    pub fn scroll_down_help() -> Self {
+        Command::ScrollHelp(Direction::Down)
+    }
     pub fn move_entry_first() -> Self {
         Command::MoveEntryFirst
     }
@@ -307,6 +418,18 @@ impl Command {
     pub fn move_subscribed_feed_last() -> Self {
         Command::MoveSubscribedFeedLast
     }
+    pub fn toggle_mark() -> Self {
+        Command::UpdateMarks(Marking::Toggle)
+    }
+    pub fn toggle_visual_mark() -> Self {
+        Command::UpdateMarks(Marking::Visual)
+    }
+    pub fn mark_all() -> Self {
+        Command::UpdateMarks(Marking::All)
+    }
+    pub fn clear_marks() -> Self {
+        Command::UpdateMarks(Marking::Clear)
+    }
     pub fn move_filter_requirement_left() -> Self {
         Command::MoveFilterRequirement(Direction::Left)
     }
@@ -322,6 +445,15 @@ impl Command {
     pub fn deactivate_filtering() -> Self {
         Command::DeactivateFiltering
     }
+    pub fn move_saved_filter_left() -> Self {
+        Command::MoveSavedFilter(Direction::Left)
+    }
+    pub fn move_saved_filter_right() -> Self {
+        Command::MoveSavedFilter(Direction::Right)
+    }
+    pub fn save_filter() -> Self {
+        Command::SaveFilter
+    }
     pub fn rotate_theme() -> Self {
         Command::RotateTheme
     }
@@ -420,3 +552,221 @@ impl Command {
         })
     }
 }
+
+impl Command {
+    /// Resolve the command from the name used in the keymap configuration
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        NAMED_COMMANDS
+            .iter()
+            .find(|(command_name, _)| *command_name == name)
+            .map(|(_, command)| command())
+    }
+
+    /// Names of the commands which can be bound to keys
+    pub(crate) fn names() -> impl Iterator<Item = &'static str> {
+        NAMED_COMMANDS.iter().map(|(name, _)| *name)
+    }
+
+    /// Human readable description of the command which is not bound by name
+    pub(crate) fn description(&self) -> String {
+        match self {
+            Command::ToggleFilterCategory { category, .. } => {
+                format!("Toggle category {category}")
+            }
+            Command::ActivateAllFilterCategories { .. } => "Activate all categories".to_owned(),
+            Command::DeactivateAllFilterCategories { .. } => "Deactivate all categories".to_owned(),
+            Command::InputReaderLinkNumber { .. } => "Input link number".to_owned(),
+            command => command.to_string(),
+        }
+    }
+}
+
+type Constructor = fn() -> Command;
+
+/// Commands which can be bound to keys by name
+const NAMED_COMMANDS: &[(&str, Constructor)] = &[
+    ("quit", Command::quit),
+    ("authenticate", Command::authenticate),
+    (
+        "move_right_tab_selection",
+        Command::move_right_tab_selection,
+    ),
+    ("move_left_tab_selection", Command::move_left_tab_selection),
+    (
+        "move_up_authentication_provider",
+        Command::move_up_authentication_provider,
+    ),
+    (
+        "move_down_authentication_provider",
+        Command::move_down_authentication_provider,
+    ),
+    ("move_up_entry", Command::move_up_entry),
+    ("move_down_entry", Command::move_down_entry),
+    ("reload_entries", Command::reload_entries),
+    ("open_entry", Command::open_entry),
+    ("rotate_entry_sort", Command::rotate_entry_sort),
+    ("rotate_entry_grouping", Command::rotate_entry_grouping),
+    ("toggle_entry_group", Command::toggle_entry_group),
+    ("toggle_all_entry_groups", Command::toggle_all_entry_groups),
+    ("browse_entry", Command::browse_entry),
+    ("play_entry_media", Command::play_entry_media),
+    ("open_reader", Command::open_reader),
+    ("close_reader", Command::close_reader),
+    ("scroll_up_reader", Command::scroll_up_reader),
+    ("scroll_down_reader", Command::scroll_down_reader),
+    ("scroll_up_reader_page", Command::scroll_up_reader_page),
+    ("scroll_down_reader_page", Command::scroll_down_reader_page),
+    ("scroll_reader_top", Command::scroll_reader_top),
+    ("scroll_reader_bottom", Command::scroll_reader_bottom),
+    ("follow_reader_link", Command::follow_reader_link),
+    ("move_entry_first", Command::move_entry_first),
+    ("move_entry_last", Command::move_entry_last),
+    (
+        "prompt_feed_subscription",
+        Command::prompt_feed_subscription,
+    ),
+    ("prompt_feed_edition", Command::prompt_feed_edition),
+    (
+        "prompt_feed_unsubscription",
+        Command::prompt_feed_unsubscription,
+    ),
+    (
+        "move_feed_unsubscription_popup_selection_left",
+        Command::move_feed_unsubscription_popup_selection_left,
+    ),
+    (
+        "move_feed_unsubscription_popup_selection_right",
+        Command::move_feed_unsubscription_popup_selection_right,
+    ),
+    (
+        "select_feed_unsubscription_popup",
+        Command::select_feed_unsubscription_popup,
+    ),
+    (
+        "cancel_feed_unsubscription_popup",
+        Command::cancel_feed_unsubscription_popup,
+    ),
+    ("move_up_subscribed_feed", Command::move_up_subscribed_feed),
+    (
+        "move_down_subscribed_feed",
+        Command::move_down_subscribed_feed,
+    ),
+    ("reload_subscription", Command::reload_subscription),
+    ("open_feed", Command::open_feed),
+    (
+        "move_subscribed_feed_first",
+        Command::move_subscribed_feed_first,
+    ),
+    (
+        "move_subscribed_feed_last",
+        Command::move_subscribed_feed_last,
+    ),
+    ("toggle_mark", Command::toggle_mark),
+    ("toggle_visual_mark", Command::toggle_visual_mark),
+    ("mark_all", Command::mark_all),
+    ("clear_marks", Command::clear_marks),
+    (
+        "move_filter_requirement_left",
+        Command::move_filter_requirement_left,
+    ),
+    (
+        "move_filter_requirement_right",
+        Command::move_filter_requirement_right,
+    ),
+    (
+        "activate_category_filtering",
+        Command::activate_category_filtering,
+    ),
+    (
+        "activate_search_filtering",
+        Command::activate_search_filtering,
+    ),
+    ("deactivate_filtering", Command::deactivate_filtering),
+    ("move_saved_filter_left", Command::move_saved_filter_left),
+    ("move_saved_filter_right", Command::move_saved_filter_right),
+    ("save_filter", Command::save_filter),
+    ("rotate_theme", Command::rotate_theme),
+    ("open_command_palette", Command::open_command_palette),
+    ("open_help", Command::open_help),
+    ("move_up_gh_notification", Command::move_up_gh_notification),
+    (
+        "move_down_gh_notification",
+        Command::move_down_gh_notification,
+    ),
+    (
+        "move_gh_notification_first",
+        Command::move_gh_notification_first,
+    ),
+    (
+        "move_gh_notification_last",
+        Command::move_gh_notification_last,
+    ),
+    ("open_gh_notification", Command::open_gh_notification),
+    (
+        "open_gh_notification_with_done",
+        Command::open_gh_notification_with_done,
+    ),
+    ("reload_gh_notifications", Command::reload_gh_notifications),
+    (
+        "mark_gh_notification_as_done",
+        Command::mark_gh_notification_as_done,
+    ),
+    (
+        "mark_gh_notification_as_done_all",
+        Command::mark_gh_notification_as_done_all,
+    ),
+    ("unsubscribe_gh_thread", Command::unsubscribe_gh_thread),
+    (
+        "open_gh_notification_filter_popup",
+        Command::open_gh_notification_filter_popup,
+    ),
+    (
+        "close_gh_notification_filter_popup",
+        Command::close_gh_notification_filter_popup,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_include_unread",
+        Command::toggle_gh_notification_filter_popup_include_unread,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_participating",
+        Command::toggle_gh_notification_filter_popup_participating,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_visibility_public",
+        Command::toggle_gh_notification_filter_popup_visibility_public,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_visibility_private",
+        Command::toggle_gh_notification_filter_popup_visibility_private,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_pr_open",
+        Command::toggle_gh_notification_filter_popup_pr_open,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_pr_closed",
+        Command::toggle_gh_notification_filter_popup_pr_closed,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_pr_merged",
+        Command::toggle_gh_notification_filter_popup_pr_merged,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_reason_mentioned",
+        Command::toggle_gh_notification_filter_popup_reason_mentioned,
+    ),
+    (
+        "toggle_gh_notification_filter_popup_reason_review",
+        Command::toggle_gh_notification_filter_popup_reason_review,
+    ),
+];
+
+/// `move_up_entry` => `Move up entry`
+pub(crate) fn label(name: &str) -> String {
+    let mut label = name.replace('_', " ");
+    if let Some(first) = label.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    label
+}
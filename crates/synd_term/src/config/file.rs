@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use url::Url;
 
-use crate::{cli::Palette, config::categories};
+use crate::{
+    config::{categories, filters::UserFilters, themes::UserThemes},
+    keymap::UserKeymaps,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CacheEntry {
@@ -18,13 +21,15 @@ pub struct LogEntry {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ThemeEntry {
-    pub(super) name: Option<Palette>,
+    pub(super) name: Option<String>,
+    pub(super) file: Option<PathBuf>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FeedEntry {
     pub(super) entries_limit: Option<usize>,
     pub(super) browser: Option<FeedBrowserEntry>,
+    pub(super) player: Option<FeedPlayerEntry>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,6 +38,12 @@ pub struct FeedBrowserEntry {
     pub(super) args: Option<Vec<String>>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedPlayerEntry {
+    pub(super) command: Option<PathBuf>,
+    pub(super) args: Option<Vec<String>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiEntry {
     pub(super) endpoint: Option<Url>,
@@ -49,6 +60,14 @@ pub struct GithubEntry {
     pub(super) pat: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OidcEntry {
+    pub(super) issuer: Option<Url>,
+    pub(super) client_id: Option<String>,
+    pub(super) client_secret: Option<String>,
+    pub(super) scopes: Option<Vec<String>>,
+}
+
 #[derive(Error, Debug)]
 pub enum ConfigFileError {
     #[error(transparent)]
@@ -65,7 +84,11 @@ pub struct ConfigFile {
     pub(super) api: Option<ApiEntry>,
     pub(super) feed: Option<FeedEntry>,
     pub(super) github: Option<GithubEntry>,
+    pub(super) oidc: Option<OidcEntry>,
     pub(super) categories: Option<HashMap<String, categories::Entry>>,
+    pub(super) keymap: Option<UserKeymaps>,
+    pub(super) themes: Option<UserThemes>,
+    pub(super) filters: Option<UserFilters>,
 }
 
 impl ConfigFile {
@@ -76,7 +99,7 @@ impl ConfigFile {
     }
 }
 
-pub static INIT_CONFIG: &str = r#"
+pub static INIT_CONFIG: &str = r##"
 [cache]
 # Cache directory
 # directory = "path/to/dir"
@@ -87,9 +110,12 @@ pub static INIT_CONFIG: &str = r#"
 
 [theme]
 # Theme name 
-# The available themes can be found by `synd --help`
+# The available themes can be found by `synd --help` in addition to the user defined themes
 # name = "ferra"
 
+# File which defines themes as `[<name>]` tables in the same format as `[themes.<name>]`
+# file = "path/to/themes.toml"
+
 [api]
 # Backend api endpoint
 # endpoint = "https://api.syndicationd.ymgyt.io"
@@ -104,13 +130,54 @@ pub static INIT_CONFIG: &str = r#"
 # Command to browse feed
 # browser = { command = "", args = [] }
 
+# Command to play entry media such as podcast episode
+# player = { command = "mpv", args = ["--no-video"] }
+
 [github]
 # Enable github notification feature
 # enable = true
 
 # Github Personal access token(PAT) to browse notifications
 # pat = "ghp_xxxx"
-"#;
+
+[oidc]
+# OpenID Connect issuer to login with in addition to GitHub and Google
+# issuer = "https://accounts.example.com"
+
+# Client id registered in the issuer
+# client_id = ""
+
+# Client secret if the issuer requires it
+# client_secret = ""
+
+# Scopes to request. "openid" is always requested
+# scopes = ["openid", "email", "offline_access"]
+
+# Key bindings merged over the defaults per keymap
+# Keymaps: global, login, tabs, entries, subscription, filter,
+#          unsubscribe_popup, gh_notification, gh_notification_filter_popup,
+#          reader, command_palette, help
+# [keymap.entries]
+# "C-n" = "move_down_entry"
+# "g t" = "move_entry_first"
+
+# User defined theme based on the builtin one
+# [themes.nord]
+# base = "ferra"
+# bg = "#2e3440"
+# fg = "#d8dee9"
+# fg_inactive = "#4c566a"
+# fg_focus = "#88c0d0"
+# error = "#bf616a"
+# styles = { "entries.selected_entry" = { fg = "#ebcb8b", modifiers = ["bold"] } }
+# colors = { "requirement.must" = "#bf616a" }
+
+# Saved filter to switch with `[` and `]`
+# [filters.work-must]
+# requirement = "must"
+# categories = ["work"]
+# search = ""
+"##;
 
 #[cfg(test)]
 mod tests {
@@ -118,7 +185,7 @@ mod tests {
 
     #[test]
     fn deserialize() {
-        let src = r#"
+        let src = r##"
 [cache]
 directory = "/tmp/synd/cache"
 
@@ -135,15 +202,35 @@ timeout = "30s"
 [feed]
 entries_limit = 100
 browser = { command = "w3m", args = ["--foo", "--bar"] }
+player = { command = "mpv", args = ["--no-video"] }
 
 [github]
 enable = true
 pat = "ghp_xxxx"
 
+[oidc]
+issuer = "https://accounts.example.com"
+client_id = "synd"
+scopes = ["openid", "email"]
+
 [categories.rust]
 icon = { symbol = "S", color = { rgb = 0xF74C00 }}
 aliases = ["rs"]
-"#;
+
+[keymap.entries]
+"C-n" = "move_down_entry"
+"g t" = "move_entry_first"
+
+[themes.nord]
+base = "dracula"
+bg = "#2e3440"
+styles = { "tabs_selected" = { fg = "#88c0d0", modifiers = ["bold"] } }
+
+[filters.rust-weekly]
+requirement = "should"
+categories = ["rust"]
+search = "this week"
+"##;
 
         let config = ConfigFile::new(src.as_bytes()).unwrap();
 
@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use synd_feed::types::{Category, Requirement};
+
+use crate::{config::Categories, ui::components::filter::SavedFilter};
+
+/// User defined filters keyed by the filter name
+pub(super) type UserFilters = BTreeMap<String, FilterDefinition>;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct FilterDefinition {
+    /// One of "must", "should" and "may". defaults to "may"
+    requirement: Option<String>,
+    /// Categories to show. all categories when omitted
+    #[serde(default)]
+    categories: Vec<String>,
+    #[serde(default)]
+    search: String,
+}
+
+impl FilterDefinition {
+    fn build(&self, name: &str, config: &Categories) -> Result<SavedFilter, String> {
+        let err = |message: String| format!("filter `{name}`: {message}");
+
+        let requirement = match self.requirement.as_deref() {
+            Some(requirement) => requirement.parse().map_err(|e: &str| err(e.to_owned()))?,
+            None => Requirement::May,
+        };
+        let categories = self
+            .categories
+            .iter()
+            .map(|category| {
+                Category::new(category.clone())
+                    .map(|category| config.normalize(category))
+                    .map_err(|_| err(format!("invalid category `{category}`")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SavedFilter {
+            name: name.to_owned(),
+            requirement,
+            categories,
+            search: self.search.clone(),
+        })
+    }
+}
+
+/// Build the user defined filters sorted by name
+pub(super) fn build(
+    filters: &UserFilters,
+    config: &Categories,
+) -> Result<Vec<SavedFilter>, String> {
+    filters
+        .iter()
+        .map(|(name, definition)| definition.build(name, config))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_user_filter() {
+        let src = r#"
+[work-must]
+requirement = "MUST"
+categories = ["rust", "golang"]
+
+[rust-weekly]
+search = "this week"
+"#;
+        let filters: UserFilters = toml::from_str(src).unwrap();
+        let filters = build(&filters, &Categories::default_toml()).unwrap();
+
+        assert_eq!(filters[0].name, "rust-weekly");
+        assert_eq!(filters[0].requirement, Requirement::May);
+        assert!(filters[0].categories.is_empty());
+        assert_eq!(filters[0].search, "this week");
+        assert_eq!(filters[1].requirement, Requirement::Must);
+        assert_eq!(
+            filters[1].categories,
+            vec![Category::new("rust").unwrap(), Category::new("go").unwrap()]
+        );
+
+        let invalid: UserFilters = toml::from_str("[x]\nrequirement = \"always\"").unwrap();
+        assert!(build(&invalid, &Categories::default_toml()).is_err());
+    }
+}
@@ -6,7 +6,9 @@ mod categories;
 pub use categories::{Categories, Icon, IconColor};
 mod file;
 pub use file::INIT_CONFIG;
+mod filters;
 pub(crate) mod parse;
+mod themes;
 
 mod resolver;
 pub use resolver::ConfigResolver;
@@ -33,8 +35,13 @@ pub mod env {
     pub const FEED_ENTRIES_LIMIT: &str = env_key!("ENTRIES_LIMIT");
     pub const FEED_BROWSER: &str = env_key!("BROWSER");
     pub const FEED_BROWSER_ARGS: &str = env_key!("BROWSER_ARGS");
+    pub const FEED_PLAYER: &str = env_key!("PLAYER");
+    pub const FEED_PLAYER_ARGS: &str = env_key!("PLAYER_ARGS");
     pub const ENABLE_GITHUB: &str = env_key!("ENABLE_GH");
     pub const GITHUB_PAT: &str = env_key!("GH_PAT");
+    pub const OIDC_ISSUER: &str = env_key!("OIDC_ISSUER");
+    pub const OIDC_CLIENT_ID: &str = env_key!("OIDC_CLIENT_ID");
+    pub const OIDC_CLIENT_SECRET: &str = env_key!("OIDC_CLIENT_SECRET");
 }
 
 pub mod client {
@@ -47,6 +54,8 @@ pub mod client {
     pub const DEFAULT_ENTRIES_PER_PAGINATION: i64 = 200;
     /// Number of feeds to fetch in one request
     pub const DEFAULT_FEEDS_PER_PAGINATION: i64 = 50;
+    /// Max bytes of the request body which the api accepts by default
+    pub const BODY_LIMIT_BYTES: usize = 2 * 1024;
 }
 
 pub mod credential {
@@ -64,6 +73,10 @@ pub mod feed {
     pub fn default_brower_command() -> PathBuf {
         PathBuf::new()
     }
+    /// Empty command means the media is opened by the application associated with it
+    pub fn default_player_command() -> PathBuf {
+        PathBuf::new()
+    }
 }
 
 pub mod cache {
@@ -74,6 +87,17 @@ pub mod cache {
 
     pub const GH_NOTIFICATION_FILTER_OPTION_FILE: &str = "gh_notification_filter_options.json";
 
+    /// Sort and grouping of the entries
+    pub const ENTRIES_VIEW_OPTION_FILE: &str = "entries_view_options.json";
+
+    /// Filters saved from the filter state
+    pub const SAVED_FILTER_FILE: &str = "saved_filters.json";
+
+    /// Last fetched subscription to show while offline
+    pub const SUBSCRIPTION_FILE: &str = "subscription.json";
+    /// Last fetched entries to show while offline
+    pub const ENTRIES_FILE: &str = "entries.json";
+
     pub fn dir() -> &'static Path {
         super::project_dirs().cache_dir()
     }
@@ -1,9 +1,11 @@
 use std::{
-    io::{self, ErrorKind},
+    io::{self, ErrorKind, Read as _},
     path::PathBuf,
     time::Duration,
 };
 
+use clap::ValueEnum as _;
+
 use synd_stdx::{
     conf::Entry,
     fs::{FileSystem, fsimpl},
@@ -12,12 +14,15 @@ use thiserror::Error;
 use url::Url;
 
 use crate::{
-    cli::{self, ApiOptions, FeedOptions, GithubOptions},
+    cli::{self, ApiOptions, FeedOptions, GithubOptions, OidcOptions},
     config::{
         self, Categories,
         file::{ConfigFile, ConfigFileError},
+        filters,
+        themes::{self, UserThemes},
     },
-    ui::theme::Palette,
+    keymap::{KeymapError, KeymapsConfig, UserKeymaps},
+    ui::{components::filter::SavedFilter, theme::Theme},
 };
 
 /// `ConfigResolver` is responsible for resolving the application's configration
@@ -38,10 +43,19 @@ pub struct ConfigResolver {
     feed_entries_limit: Entry<usize>,
     feed_browser_command: Entry<PathBuf>,
     feed_browser_args: Entry<Vec<String>>,
+    feed_player_command: Entry<PathBuf>,
+    feed_player_args: Entry<Vec<String>>,
     github_enable: Entry<bool>,
     github_pat: Entry<String>,
-    palette: Entry<Palette>,
+    oidc_issuer: Entry<Option<Url>>,
+    oidc_client_id: Entry<Option<String>>,
+    oidc_client_secret: Entry<Option<String>>,
+    oidc_scopes: Entry<Vec<String>>,
+    theme: Entry<Theme>,
+    themes: Vec<Theme>,
+    filters: Vec<SavedFilter>,
     categories: Categories,
+    keymap: UserKeymaps,
 }
 
 impl ConfigResolver {
@@ -81,6 +95,14 @@ impl ConfigResolver {
         self.feed_browser_args.resolve_ref().clone()
     }
 
+    pub fn feed_player_command(&self) -> PathBuf {
+        self.feed_player_command.resolve_ref().clone()
+    }
+
+    pub fn feed_player_args(&self) -> Vec<String> {
+        self.feed_player_args.resolve_ref().clone()
+    }
+
     pub fn is_github_enable(&self) -> bool {
         self.github_enable.resolve()
     }
@@ -89,13 +111,54 @@ impl ConfigResolver {
         self.github_pat.resolve_ref().clone()
     }
 
-    pub fn palette(&self) -> Palette {
-        self.palette.resolve_ref().clone()
+    pub fn oidc_issuer(&self) -> Option<Url> {
+        self.oidc_issuer.resolve_ref().clone()
+    }
+
+    pub fn oidc_client_id(&self) -> Option<String> {
+        self.oidc_client_id.resolve_ref().clone()
+    }
+
+    pub fn oidc_client_secret(&self) -> Option<String> {
+        self.oidc_client_secret.resolve_ref().clone()
+    }
+
+    pub fn oidc_scopes(&self) -> Vec<String> {
+        self.oidc_scopes.resolve_ref().clone()
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme.resolve_ref().clone()
+    }
+
+    /// User defined themes
+    pub fn themes(&self) -> Vec<Theme> {
+        self.themes.clone()
+    }
+
+    /// User defined filters
+    pub fn filters(&self) -> Vec<SavedFilter> {
+        self.filters.clone()
     }
 
     pub fn categories(&self) -> Categories {
         self.categories.clone()
     }
+
+    /// Default keymaps merged with the user defined bindings
+    pub fn keymaps(&self) -> Result<KeymapsConfig, Vec<KeymapError>> {
+        let mut keymaps = KeymapsConfig::default();
+        keymaps.merge(&self.keymap)?;
+        Ok(keymaps)
+    }
+
+    /// Invalid user defined bindings and the bindings conflicting across keymaps
+    pub fn keymap_errors(&self) -> Vec<KeymapError> {
+        match self.keymaps() {
+            Ok(keymaps) => keymaps.conflicts(&self.keymap),
+            Err(errors) => errors,
+        }
+    }
 }
 
 impl ConfigResolver {
@@ -106,6 +169,11 @@ impl ConfigResolver {
                 "github pat is required for github feature".into(),
             ));
         }
+        if self.oidc_issuer.resolve_ref().is_some() && self.oidc_client_id.resolve_ref().is_none() {
+            return Err(ConfigResolverBuildError::ValidateConfigFile(
+                "oidc client id is required for oidc issuer".into(),
+            ));
+        }
         Ok(self)
     }
 }
@@ -128,6 +196,7 @@ pub struct ConfigResolverBuilder<FS = fsimpl::FileSystem> {
     api_flags: Option<ApiOptions>,
     feed_flags: Option<FeedOptions>,
     github_flags: Option<GithubOptions>,
+    oidc_flags: Option<OidcOptions>,
     palette_flag: Option<cli::Palette>,
     fs: FS,
 }
@@ -181,6 +250,14 @@ impl ConfigResolverBuilder {
         }
     }
 
+    #[must_use]
+    pub fn oidc_options(self, oidc_options: OidcOptions) -> Self {
+        Self {
+            oidc_flags: Some(oidc_options),
+            ..self
+        }
+    }
+
     #[must_use]
     pub fn palette(self, palette: Option<cli::Palette>) -> Self {
         Self {
@@ -193,6 +270,7 @@ impl ConfigResolverBuilder {
         self.try_build().expect("failed to build config resolver")
     }
 
+    #[allow(clippy::too_many_lines)]
     pub fn try_build(self) -> Result<ConfigResolver, ConfigResolverBuildError> {
         let (mut config_file, config_path) = if let Some(path) = self.config_file {
             // If a configuration file path is explicitly specified, search for that file
@@ -232,6 +310,63 @@ impl ConfigResolverBuilder {
         if let Some(user_defined) = config_file.as_mut().and_then(|c| c.categories.take()) {
             categories.merge(user_defined);
         }
+        let keymap = config_file
+            .as_mut()
+            .and_then(|c| c.keymap.take())
+            .unwrap_or_default();
+
+        // construct user themes. the definitions in the config file take precedence over the theme file
+        let themes = {
+            let mut definitions = UserThemes::new();
+            if let Some(path) = config_file
+                .as_mut()
+                .and_then(|c| c.theme.as_mut())
+                .and_then(|theme| theme.file.take())
+            {
+                let mut buf = String::new();
+                self.fs
+                    .open_file(&path)
+                    .and_then(|mut f| f.read_to_string(&mut buf))
+                    .map_err(|err| ConfigResolverBuildError::ConfigFileOpen {
+                        path: path.display().to_string(),
+                        err,
+                    })?;
+                definitions
+                    .extend(toml::from_str::<UserThemes>(&buf).map_err(ConfigFileError::from)?);
+            }
+            if let Some(user_defined) = config_file.as_mut().and_then(|c| c.themes.take()) {
+                definitions.extend(user_defined);
+            }
+            themes::build(&definitions).map_err(ConfigResolverBuildError::ValidateConfigFile)?
+        };
+        let filters = match config_file.as_mut().and_then(|c| c.filters.take()) {
+            Some(definitions) => filters::build(&definitions, &categories)
+                .map_err(ConfigResolverBuildError::ValidateConfigFile)?,
+            None => Vec::new(),
+        };
+        let file_theme = match config_file
+            .as_mut()
+            .and_then(|c| c.theme.as_mut())
+            .and_then(|theme| theme.name.take())
+        {
+            Some(name) => Some(
+                themes
+                    .iter()
+                    .find(|theme| theme.name == name)
+                    .cloned()
+                    .or_else(|| {
+                        cli::Palette::from_str(&name, true)
+                            .ok()
+                            .map(|palette| Theme::with_palette(palette.into()))
+                    })
+                    .ok_or_else(|| {
+                        ConfigResolverBuildError::ValidateConfigFile(format!(
+                            "unknown theme `{name}`"
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
 
         let ConfigResolverBuilder {
             api_flags:
@@ -244,12 +379,20 @@ impl ConfigResolverBuilder {
                     entries_limit,
                     browser,
                     browser_args,
+                    player,
+                    player_args,
                 }),
             github_flags:
                 Some(GithubOptions {
                     enable_github_notification,
                     github_pat,
                 }),
+            oidc_flags:
+                Some(OidcOptions {
+                    oidc_issuer,
+                    oidc_client_id,
+                    oidc_client_secret,
+                }),
             log_file_flag,
             cache_dir_flag,
             palette_flag,
@@ -322,6 +465,26 @@ impl ConfigResolverBuilder {
                 )
                 .with_flag(browser_args),
 
+            feed_player_command: Entry::with_default(config::feed::default_player_command())
+                .with_file(
+                    config_file
+                        .as_mut()
+                        .and_then(|c| c.feed.as_mut())
+                        .and_then(|feed| feed.player.as_mut())
+                        .and_then(|player| player.command.take()),
+                )
+                .with_flag(player),
+
+            feed_player_args: Entry::with_default(Vec::new())
+                .with_file(
+                    config_file
+                        .as_mut()
+                        .and_then(|c| c.feed.as_mut())
+                        .and_then(|feed| feed.player.as_mut())
+                        .and_then(|player| player.args.take()),
+                )
+                .with_flag(player_args),
+
             github_enable: Entry::with_default(false)
                 .with_file(
                     config_file
@@ -338,16 +501,46 @@ impl ConfigResolverBuilder {
                         .and_then(|gh| gh.pat.take()),
                 )
                 .with_flag(github_pat),
-            palette: Entry::with_default(config::theme::DEFAULT_PALETTE.into())
+            oidc_issuer: Entry::with_default(None)
+                .with_file(
+                    config_file
+                        .as_mut()
+                        .and_then(|c| c.oidc.as_mut())
+                        .and_then(|oidc| oidc.issuer.take())
+                        .map(Some),
+                )
+                .with_flag(oidc_issuer.map(Some)),
+            oidc_client_id: Entry::with_default(None)
+                .with_file(
+                    config_file
+                        .as_mut()
+                        .and_then(|c| c.oidc.as_mut())
+                        .and_then(|oidc| oidc.client_id.take())
+                        .map(Some),
+                )
+                .with_flag(oidc_client_id.map(Some)),
+            oidc_client_secret: Entry::with_default(None)
                 .with_file(
                     config_file
                         .as_mut()
-                        .and_then(|c| c.theme.as_mut())
-                        .and_then(|theme| theme.name.take())
-                        .map(Into::into),
+                        .and_then(|c| c.oidc.as_mut())
+                        .and_then(|oidc| oidc.client_secret.take())
+                        .map(Some),
                 )
-                .with_flag(palette_flag.map(Into::into)),
+                .with_flag(oidc_client_secret.map(Some)),
+            oidc_scopes: Entry::with_default(Vec::new()).with_file(
+                config_file
+                    .as_mut()
+                    .and_then(|c| c.oidc.as_mut())
+                    .and_then(|oidc| oidc.scopes.take()),
+            ),
+            theme: Entry::with_default(Theme::with_palette(config::theme::DEFAULT_PALETTE.into()))
+                .with_file(file_theme)
+                .with_flag(palette_flag.map(|palette| Theme::with_palette(palette.into()))),
+            themes,
+            filters,
             categories,
+            keymap,
         };
 
         resolver.validate()
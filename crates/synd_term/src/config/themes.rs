@@ -0,0 +1,177 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli,
+    ui::theme::{Palette, Theme},
+};
+
+/// User defined themes keyed by the theme name
+pub(super) type UserThemes = BTreeMap<String, ThemeDefinition>;
+
+/// Theme defined on top of a builtin palette
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct ThemeDefinition {
+    /// Builtin palette to override. defaults to ferra
+    base: Option<cli::Palette>,
+    bg: Option<String>,
+    fg: Option<String>,
+    fg_inactive: Option<String>,
+    fg_focus: Option<String>,
+    error: Option<String>,
+    /// Styles keyed by the component such as `entries.selected_entry`
+    #[serde(default)]
+    styles: BTreeMap<String, StyleDefinition>,
+    /// Colors keyed by the component such as `requirement.must`
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct StyleDefinition {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    modifiers: Vec<String>,
+}
+
+impl ThemeDefinition {
+    pub(super) fn build(&self, name: &str) -> Result<Theme, String> {
+        let err = |message: String| format!("theme `{name}`: {message}");
+
+        let mut palette: Palette = self.base.unwrap_or(super::theme::DEFAULT_PALETTE).into();
+        name.clone_into(&mut palette.name);
+        for (color, value) in [
+            (&mut palette.bg, &self.bg),
+            (&mut palette.fg, &self.fg),
+            (&mut palette.fg_inactive, &self.fg_inactive),
+            (&mut palette.fg_focus, &self.fg_focus),
+            (&mut palette.error, &self.error),
+        ] {
+            if let Some(value) = value {
+                *color = parse_color(value).map_err(err)?;
+            }
+        }
+
+        let mut theme = Theme::with_palette(palette);
+        for (key, definition) in &self.styles {
+            let style = definition.style().map_err(err)?;
+            let target = theme
+                .style_mut(key)
+                .ok_or_else(|| err(format!("unknown style `{key}`")))?;
+            *target = target.patch(style);
+        }
+        for (key, value) in &self.colors {
+            let color = parse_color(value).map_err(err)?;
+            let target = theme
+                .color_mut(key)
+                .ok_or_else(|| err(format!("unknown color `{key}`")))?;
+            *target = color;
+        }
+        Ok(theme)
+    }
+}
+
+impl StyleDefinition {
+    fn style(&self) -> Result<Style, String> {
+        let mut style = Style::new();
+        if let Some(fg) = &self.fg {
+            style = style.fg(parse_color(fg)?);
+        }
+        if let Some(bg) = &self.bg {
+            style = style.bg(parse_color(bg)?);
+        }
+        for modifier in &self.modifiers {
+            let modifier = Modifier::from_name(&modifier.to_uppercase())
+                .ok_or_else(|| format!("unknown modifier `{modifier}`"))?;
+            style = style.add_modifier(modifier);
+        }
+        Ok(style)
+    }
+}
+
+/// Accept the color names, indexes and hex codes like `#282a36`
+fn parse_color(value: &str) -> Result<Color, String> {
+    Color::from_str(value).map_err(|_| format!("invalid color `{value}`"))
+}
+
+/// Build the user defined themes sorted by name
+pub(super) fn build(themes: &UserThemes) -> Result<Vec<Theme>, String> {
+    themes
+        .iter()
+        .map(|(name, definition)| definition.build(name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_user_theme() {
+        let src = r##"
+[nord]
+base = "dracula"
+bg = "#2e3440"
+fg_focus = "#88c0d0"
+
+[nord.styles]
+tabs_selected = { fg = "yellow", modifiers = ["italic"] }
+
+[nord.colors]
+"requirement.must" = "#bf616a"
+"##;
+        let themes: UserThemes = toml::from_str(src).unwrap();
+        let theme = build(&themes).unwrap().pop().unwrap();
+
+        assert_eq!(theme.name, "nord");
+        assert_eq!(theme.base.bg, Some(Color::Rgb(0x2e, 0x34, 0x40)));
+        assert_eq!(
+            theme.base.fg,
+            Theme::with_palette(Palette::dracula()).base.fg
+        );
+        assert_eq!(
+            theme.subscription.selected_feed.fg,
+            Some(Color::Rgb(0x88, 0xc0, 0xd0))
+        );
+        assert_eq!(theme.tabs_selected.fg, Some(Color::Yellow));
+        assert!(
+            theme
+                .tabs_selected
+                .add_modifier
+                .contains(Modifier::BOLD | Modifier::ITALIC)
+        );
+        assert_eq!(theme.requirement.must, Color::Rgb(0xbf, 0x61, 0x6a));
+    }
+
+    #[test]
+    fn reject_invalid_definition() {
+        for (src, message) in [
+            (
+                r#"[x]
+fg = "nocolor""#,
+                "theme `x`: invalid color `nocolor`",
+            ),
+            (
+                r#"[x.styles]
+unknown = { fg = "red" }"#,
+                "theme `x`: unknown style `unknown`",
+            ),
+            (
+                r#"[x.styles]
+tabs = { modifiers = ["shiny"] }"#,
+                "theme `x`: unknown modifier `shiny`",
+            ),
+            (
+                r#"[x.colors]
+"requirement.could" = "red""#,
+                "theme `x`: unknown color `requirement.could`",
+            ),
+        ] {
+            let themes: UserThemes = toml::from_str(src).unwrap();
+            assert_eq!(build(&themes).unwrap_err(), message);
+        }
+    }
+}
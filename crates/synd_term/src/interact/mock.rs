@@ -3,7 +3,8 @@ use std::cell::RefCell;
 use url::Url;
 
 use crate::interact::{
-    Interact, OpenBrowserError, OpenEditor, OpenEditorError, OpenTextBrowser, OpenWebBrowser,
+    Interact, OpenBrowserError, OpenEditor, OpenEditorError, OpenMediaPlayer, OpenTextBrowser,
+    OpenWebBrowser,
 };
 
 pub struct MockInteractor {
@@ -40,6 +41,13 @@ impl OpenTextBrowser for MockInteractor {
     }
 }
 
+impl OpenMediaPlayer for MockInteractor {
+    fn open_media_player(&self, url: Url) -> Result<(), OpenBrowserError> {
+        self.browser_urls.borrow_mut().push(url.to_string());
+        Ok(())
+    }
+}
+
 impl OpenEditor for MockInteractor {
     fn open_editor(&self, _initial_content: &str) -> Result<String, OpenEditorError> {
         Ok(self.editor_buffer.borrow_mut().remove(0))
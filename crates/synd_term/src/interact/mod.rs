@@ -3,12 +3,12 @@ use std::{io, path::PathBuf};
 #[cfg(feature = "integration")]
 pub mod mock;
 mod process;
-pub use process::{ProcessInteractor, TextBrowserInteractor};
+pub use process::{MediaPlayerInteractor, ProcessInteractor, TextBrowserInteractor};
 
 use thiserror::Error;
 use url::Url;
 
-pub trait Interact: OpenWebBrowser + OpenTextBrowser + OpenEditor {}
+pub trait Interact: OpenWebBrowser + OpenTextBrowser + OpenMediaPlayer + OpenEditor {}
 
 #[derive(Debug, Error)]
 pub enum OpenBrowserError {
@@ -26,6 +26,10 @@ pub trait OpenTextBrowser {
     fn open_text_browser(&self, url: Url) -> Result<(), OpenBrowserError>;
 }
 
+pub trait OpenMediaPlayer {
+    fn open_media_player(&self, url: Url) -> Result<(), OpenBrowserError>;
+}
+
 #[derive(Debug, Error)]
 #[error("failed to open editor: {message}")]
 pub struct OpenEditorError {
@@ -1,21 +1,27 @@
 use std::{
     io::{self, ErrorKind},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
 };
 
 use itertools::Itertools;
 use url::Url;
 
-use crate::interact::{Interact, OpenBrowserError, OpenEditor, OpenTextBrowser, OpenWebBrowser};
+use crate::interact::{
+    Interact, OpenBrowserError, OpenEditor, OpenMediaPlayer, OpenTextBrowser, OpenWebBrowser,
+};
 
 pub struct ProcessInteractor {
     text_browser: TextBrowserInteractor,
+    media_player: MediaPlayerInteractor,
 }
 
 impl ProcessInteractor {
-    pub fn new(text_browser: TextBrowserInteractor) -> Self {
-        Self { text_browser }
+    pub fn new(text_browser: TextBrowserInteractor, media_player: MediaPlayerInteractor) -> Self {
+        Self {
+            text_browser,
+            media_player,
+        }
     }
 }
 
@@ -31,6 +37,12 @@ impl OpenTextBrowser for ProcessInteractor {
     }
 }
 
+impl OpenMediaPlayer for ProcessInteractor {
+    fn open_media_player(&self, url: Url) -> Result<(), super::OpenBrowserError> {
+        self.media_player.open_media_player(url)
+    }
+}
+
 impl OpenEditor for ProcessInteractor {
     fn open_editor(&self, initial_content: &str) -> Result<String, super::OpenEditorError> {
         edit::edit(initial_content).map_err(|err| super::OpenEditorError {
@@ -55,38 +67,61 @@ impl TextBrowserInteractor {
 impl OpenTextBrowser for TextBrowserInteractor {
     #[tracing::instrument(skip(self))]
     fn open_text_browser(&self, url: Url) -> Result<(), OpenBrowserError> {
-        let status = Command::new(self.command.as_os_str())
-            .args(self.args.iter())
-            .arg(url.as_str())
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map_err(|err| {
-                if err.kind() == ErrorKind::NotFound {
-                    OpenBrowserError::CommandNotFound {
-                        command: self.command.clone(),
-                    }
-                } else {
-                    err.into()
-                }
-            })?
-            .status;
+        run_command(&self.command, &self.args, &url)
+    }
+}
+
+pub struct MediaPlayerInteractor {
+    command: PathBuf,
+    args: Vec<String>,
+}
+
+impl MediaPlayerInteractor {
+    pub fn new(command: PathBuf, args: Vec<String>) -> Self {
+        Self { command, args }
+    }
+}
 
-        if status.success() {
-            Ok(())
+impl OpenMediaPlayer for MediaPlayerInteractor {
+    #[tracing::instrument(skip(self))]
+    fn open_media_player(&self, url: Url) -> Result<(), OpenBrowserError> {
+        // Delegate to the application associated with the media type if the player is not configured
+        if self.command.as_os_str().is_empty() {
+            open::that(url.as_str()).map_err(OpenBrowserError::from)
         } else {
-            let full_command = if self.args.is_empty() {
-                format!("{} {}", self.command.display(), url,)
-            } else {
-                format!(
-                    "{} {} {}",
-                    self.command.display(),
-                    self.args.iter().join(" "),
-                    url,
-                )
-            };
-            Err(io::Error::new(io::ErrorKind::Other, full_command).into())
+            run_command(&self.command, &self.args, &url)
         }
     }
 }
+
+/// Run the command with the url in the foreground and wait for it to exit
+fn run_command(command: &Path, args: &[String], url: &Url) -> Result<(), OpenBrowserError> {
+    let status = Command::new(command.as_os_str())
+        .args(args.iter())
+        .arg(url.as_str())
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| {
+            if err.kind() == ErrorKind::NotFound {
+                OpenBrowserError::CommandNotFound {
+                    command: command.to_owned(),
+                }
+            } else {
+                err.into()
+            }
+        })?
+        .status;
+
+    if status.success() {
+        Ok(())
+    } else {
+        let full_command = if args.is_empty() {
+            format!("{} {}", command.display(), url,)
+        } else {
+            format!("{} {} {}", command.display(), args.iter().join(" "), url,)
+        };
+        Err(io::Error::new(io::ErrorKind::Other, full_command).into())
+    }
+}
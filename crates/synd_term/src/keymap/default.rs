@@ -1,5 +1,9 @@
-use crate::keymap::{KeymapsConfig, macros::keymap};
+use crate::{
+    command::Command,
+    keymap::{KeyTrie, KeymapsConfig, macros::keymap, parse},
+};
 
+#[allow(clippy::too_many_lines)]
 pub fn default() -> KeymapsConfig {
     let login = keymap!({
         "enter" => authenticate,
@@ -16,6 +20,16 @@ pub fn default() -> KeymapsConfig {
         "r" => reload_entries,
         "enter" => open_entry,
         "space" => browse_entry,
+        "p" => play_entry_media,
+        "v" => open_reader,
+        "s" => rotate_entry_sort,
+        "S-g" => rotate_entry_grouping,
+        "z" => toggle_entry_group,
+        "S-z" => toggle_all_entry_groups,
+        "x" => toggle_mark,
+        "S-v" => toggle_visual_mark,
+        "C-a" => mark_all,
+        "S-x" => clear_marks,
         "g" => {
            "g" => move_entry_first,
            "e" => move_entry_last,
@@ -29,6 +43,10 @@ pub fn default() -> KeymapsConfig {
         "j" | "down" => move_down_subscribed_feed,
         "r" => reload_subscription,
         "enter" => open_feed,
+        "x" => toggle_mark,
+        "S-v" => toggle_visual_mark,
+        "C-a" => mark_all,
+        "S-x" => clear_marks,
         "g" => {
             "g" => move_subscribed_feed_first,
             "e" => move_subscribed_feed_last,
@@ -43,6 +61,10 @@ pub fn default() -> KeymapsConfig {
         "d" => mark_gh_notification_as_done,
         "S-d" => mark_gh_notification_as_done_all,
         "u" => unsubscribe_gh_thread,
+        "x" => toggle_mark,
+        "S-v" => toggle_visual_mark,
+        "C-a" => mark_all,
+        "S-x" => clear_marks,
         "g" => {
             "g" => move_gh_notification_first,
             "e" => move_gh_notification_last,
@@ -80,6 +102,9 @@ pub fn default() -> KeymapsConfig {
        "c" => activate_category_filtering,
        "/" => activate_search_filtering,
        "esc" => deactivate_filtering,
+       "[" => move_saved_filter_left,
+       "]" => move_saved_filter_right,
+       "S-s" => save_filter,
     });
     let unsubscribe_popup = keymap!({
         "h" | "left" => move_feed_unsubscription_popup_selection_left,
@@ -87,9 +112,43 @@ pub fn default() -> KeymapsConfig {
         "enter" => select_feed_unsubscription_popup,
         "esc" => cancel_feed_unsubscription_popup,
     });
+    let mut reader = keymap!({
+        "k" | "up" => scroll_up_reader,
+        "j" | "down" => scroll_down_reader,
+        "space" | "C-d" => scroll_down_reader_page,
+        "b" | "C-u" => scroll_up_reader_page,
+        "g" => {
+            "g" => scroll_reader_top,
+            "e" => scroll_reader_bottom,
+        },
+        "enter" => follow_reader_link,
+        "o" => open_entry,
+        "esc" | "q" => close_reader,
+    });
+    // Link numbers are typed before enter
+    for digit in 0..=9_u8 {
+        let key = parse(&digit.to_string()).unwrap();
+        reader.insert(
+            &[key],
+            KeyTrie::command(Command::InputReaderLinkNumber { digit }),
+        );
+    }
+    let command_palette = keymap!({
+        "up" => move_up_command_palette_selection,
+        "down" => move_down_command_palette_selection,
+        "enter" => execute_command_palette,
+        "esc" => close_command_palette,
+    });
+    let help = keymap!({
+        "k" | "up" => scroll_up_help,
+        "j" | "down" => scroll_down_help,
+        "?" | "esc" | "q" => close_help,
+    });
     let global = keymap!({
         "q" | "C-c" =>  quit ,
         "S-t" => rotate_theme,
+        ":" | "C-p" => open_command_palette,
+        "?" => open_help,
     });
 
     KeymapsConfig {
@@ -101,6 +160,9 @@ pub fn default() -> KeymapsConfig {
         gh_notification_filter_popup,
         filter,
         unsubscribe_popup,
+        reader,
+        command_palette,
+        help,
         global,
     }
 }
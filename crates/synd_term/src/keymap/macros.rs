@@ -1,7 +1,12 @@
 macro_rules! keymap {
     ( @count $token:tt ) => { () };
 
-    ( @trie $cmd:ident ) =>  { $crate::keymap::KeyTrie::Command($crate::command::Command::$cmd()) };
+    ( @trie $cmd:ident ) =>  {
+        $crate::keymap::KeyTrie::Command {
+            command: $crate::command::Command::$cmd(),
+            name: Some(stringify!($cmd)),
+        }
+    };
 
     (@trie
         { $( $($key:literal)|+ => $value:tt, )+ }
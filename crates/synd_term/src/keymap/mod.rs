@@ -1,13 +1,21 @@
-use std::{collections::HashMap, ops::ControlFlow};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::ControlFlow,
+};
 
 use anyhow::{anyhow, bail};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use itertools::Itertools;
+use thiserror::Error;
 
 mod default;
 
 pub mod macros;
 
-use crate::{application::event::KeyEventResult, command::Command};
+use crate::{
+    application::event::KeyEventResult,
+    command::{Command, label},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum KeymapId {
@@ -21,11 +29,41 @@ pub(crate) enum KeymapId {
     CategoryFiltering = 7,
     UnsubscribePopupSelection = 8,
     GhNotificationFilterPopup = 9,
+    Reader = 10,
+    CommandPalette = 11,
+    Help = 12,
+}
+
+impl KeymapId {
+    fn title(self) -> &'static str {
+        match self {
+            KeymapId::Global => "Global",
+            KeymapId::Login => "Login",
+            KeymapId::Tabs => "Tabs",
+            KeymapId::Entries => "Entries",
+            KeymapId::Subscription => "Feeds",
+            KeymapId::GhNotification => "GitHub",
+            KeymapId::Filter => "Filter",
+            KeymapId::CategoryFiltering => "Category filter",
+            KeymapId::UnsubscribePopupSelection => "Unsubscribe",
+            KeymapId::GhNotificationFilterPopup => "GitHub filter",
+            KeymapId::Reader => "Reader",
+            KeymapId::CommandPalette => "Command palette",
+            KeymapId::Help => "Help",
+        }
+    }
+}
+
+/// Bindings of a keymap shown in the help
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KeymapHelp {
+    pub(crate) title: &'static str,
+    /// Key sequences bound to the same description like `k/up`, and the description
+    pub(crate) bindings: Vec<(String, String)>,
 }
 
 #[derive(Debug)]
 pub(crate) struct Keymap {
-    #[allow(dead_code)]
     id: KeymapId,
     enable: bool,
     trie: KeyTrie,
@@ -50,16 +88,16 @@ impl Keymap {
     fn search(&mut self, event: &KeyEvent) -> Option<Command> {
         let first = self.pending_keys.first().unwrap_or(event);
         let trie = match self.trie.search(&[*first]) {
-            Some(KeyTrie::Command(cmd)) => return Some(cmd),
+            Some(KeyTrie::Command { command, .. }) => return Some(command),
             Some(KeyTrie::Node(node)) => KeyTrie::Node(node),
             None => return None,
         };
 
         self.pending_keys.push(*event);
         match trie.search(&self.pending_keys[1..]) {
-            Some(KeyTrie::Command(cmd)) => {
+            Some(KeyTrie::Command { command, .. }) => {
                 self.pending_keys.drain(..);
-                Some(cmd)
+                Some(command)
             }
             Some(KeyTrie::Node(_)) => None,
             _ => {
@@ -70,16 +108,84 @@ impl Keymap {
     }
 }
 
-pub(crate) struct KeymapsConfig {
-    pub login: KeyTrie,
-    pub tabs: KeyTrie,
-    pub entries: KeyTrie,
-    pub subscription: KeyTrie,
-    pub gh_notification: KeyTrie,
-    pub gh_notification_filter_popup: KeyTrie,
-    pub filter: KeyTrie,
-    pub unsubscribe_popup: KeyTrie,
-    pub global: KeyTrie,
+/// User defined bindings per keymap. key sequence like `g g` to command name
+pub type UserKeymaps = BTreeMap<String, BTreeMap<String, String>>;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum KeymapError {
+    #[error("unknown keymap `{keymap}`")]
+    UnknownKeymap { keymap: String },
+    #[error("[keymap.{keymap}] invalid key `{keys}`: {message}")]
+    InvalidKey {
+        keymap: String,
+        keys: String,
+        message: String,
+    },
+    #[error("[keymap.{keymap}] unknown command `{command}`")]
+    UnknownCommand { keymap: String, command: String },
+    #[error("[keymap.{keymap}] `{keys}` conflicts with `{other}`")]
+    Conflict {
+        keymap: String,
+        keys: String,
+        other: String,
+    },
+    /// The user binding would replace the default sequences sharing the prefix
+    #[error("[keymap.{keymap}] `{keys}` conflicts with the default `{default}`")]
+    ConflictDefault {
+        keymap: String,
+        keys: String,
+        default: String,
+    },
+    /// The user binding and the binding of the keymap enabled at the same time shadow each other
+    #[error("[keymap.{keymap}] `{keys}` conflicts with `{other}` of keymap.{other_keymap}")]
+    ConflictKeymap {
+        keymap: String,
+        keys: String,
+        other_keymap: String,
+        other: String,
+    },
+}
+
+/// Keymaps enabled at the same time on each screen in addition to `global`
+const SCREENS: &[&[&str]] = &[
+    &["login"],
+    &["tabs", "filter", "entries"],
+    &["tabs", "filter", "subscription", "unsubscribe_popup"],
+    &["tabs", "filter", "gh_notification"],
+    &["gh_notification_filter_popup"],
+    &["reader"],
+];
+
+fn enabled_together(a: &str, b: &str) -> bool {
+    // Focused keymaps are enabled alone
+    let focused = |keymap| matches!(keymap, "command_palette" | "help");
+    a != b
+        && ((a == "global" && !focused(b))
+            || (b == "global" && !focused(a))
+            || SCREENS
+                .iter()
+                .any(|screen| screen.contains(&a) && screen.contains(&b)))
+}
+
+/// Return true if one of the sequences is a prefix of the other
+fn overlaps(a: &[KeyEvent], b: &[KeyEvent]) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
+#[derive(Clone, Debug)]
+pub struct KeymapsConfig {
+    pub(crate) login: KeyTrie,
+    pub(crate) tabs: KeyTrie,
+    pub(crate) entries: KeyTrie,
+    pub(crate) subscription: KeyTrie,
+    pub(crate) gh_notification: KeyTrie,
+    pub(crate) gh_notification_filter_popup: KeyTrie,
+    pub(crate) filter: KeyTrie,
+    pub(crate) unsubscribe_popup: KeyTrie,
+    pub(crate) reader: KeyTrie,
+    pub(crate) command_palette: KeyTrie,
+    pub(crate) help: KeyTrie,
+    pub(crate) global: KeyTrie,
 }
 
 impl Default for KeymapsConfig {
@@ -88,9 +194,189 @@ impl Default for KeymapsConfig {
     }
 }
 
+impl KeymapsConfig {
+    /// Merge user defined bindings over the current ones.
+    /// All the invalid bindings are reported and none of them are applied
+    pub fn merge(&mut self, user: &UserKeymaps) -> Result<(), Vec<KeymapError>> {
+        let mut errors = Vec::new();
+        let mut bindings = Vec::new();
+
+        for (keymap, table) in user {
+            if self.trie_mut(keymap).is_none() {
+                errors.push(KeymapError::UnknownKeymap {
+                    keymap: keymap.clone(),
+                });
+                continue;
+            }
+            let mut parsed: Vec<(&str, Vec<KeyEvent>)> = Vec::with_capacity(table.len());
+            for (keys, command) in table {
+                let sequence = match parse_sequence(keys) {
+                    Ok(sequence) => sequence,
+                    Err(err) => {
+                        errors.push(KeymapError::InvalidKey {
+                            keymap: keymap.clone(),
+                            keys: keys.clone(),
+                            message: err.to_string(),
+                        });
+                        continue;
+                    }
+                };
+                let Some(name) = Command::names().find(|name| name == command) else {
+                    errors.push(KeymapError::UnknownCommand {
+                        keymap: keymap.clone(),
+                        command: command.clone(),
+                    });
+                    continue;
+                };
+                // A sequence which is a prefix of another could never reach the longer one
+                if let Some((other, _)) =
+                    parsed.iter().find(|(_, other)| overlaps(other, &sequence))
+                {
+                    errors.push(KeymapError::Conflict {
+                        keymap: keymap.clone(),
+                        keys: keys.clone(),
+                        other: (*other).to_owned(),
+                    });
+                    continue;
+                }
+                // Overriding the same sequence is allowed, but inserting the sequence would
+                // silently drop the defaults sharing the prefix like `g g` and `g e` for `g`
+                // The first one in order is reported as the bindings are not ordered
+                if let Some(default) = self.trie(keymap).and_then(|trie| {
+                    trie.bindings()
+                        .into_iter()
+                        .filter(|(default, ..)| {
+                            *default != sequence && overlaps(default, &sequence)
+                        })
+                        .map(|(default, ..)| default.iter().map(format).join(" "))
+                        .min()
+                }) {
+                    errors.push(KeymapError::ConflictDefault {
+                        keymap: keymap.clone(),
+                        keys: keys.clone(),
+                        default,
+                    });
+                    continue;
+                }
+                parsed.push((keys, sequence.clone()));
+                bindings.push((keymap, sequence, name));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        for (keymap, sequence, name) in bindings {
+            if let (Some(trie), Some(command)) = (self.trie_mut(keymap), KeyTrie::named(name)) {
+                trie.insert(&sequence, command);
+            }
+        }
+        Ok(())
+    }
+
+    /// User bindings which conflict with the bindings of the keymaps enabled at the same time.
+    /// They are valid, but one of them is never reached depending on the priority of the keymaps
+    pub fn conflicts(&self, user: &UserKeymaps) -> Vec<KeymapError> {
+        let defaults = Self::default();
+        let mut conflicts = Vec::new();
+        for (keymap, table) in user {
+            for keys in table.keys() {
+                let Ok(sequence) = parse_sequence(keys) else {
+                    continue;
+                };
+                // Rebinding a default sequence keeps the precedence of the defaults
+                if defaults.trie(keymap).is_some_and(|trie| {
+                    trie.bindings()
+                        .iter()
+                        .any(|(default, ..)| *default == sequence)
+                }) {
+                    continue;
+                }
+                for other_keymap in Self::KEYMAPS
+                    .iter()
+                    .filter(|other| enabled_together(keymap, other))
+                {
+                    let Some(trie) = self.trie(other_keymap) else {
+                        continue;
+                    };
+                    conflicts.extend(
+                        trie.bindings()
+                            .into_iter()
+                            .filter(|(other, ..)| overlaps(other, &sequence))
+                            .map(|(other, ..)| other.iter().map(format).join(" "))
+                            .sorted()
+                            .map(|other| KeymapError::ConflictKeymap {
+                                keymap: keymap.clone(),
+                                keys: keys.clone(),
+                                other_keymap: (*other_keymap).to_owned(),
+                                other,
+                            }),
+                    );
+                }
+            }
+        }
+        conflicts
+    }
+
+    const KEYMAPS: [&str; 12] = [
+        "global",
+        "login",
+        "tabs",
+        "entries",
+        "subscription",
+        "gh_notification",
+        "gh_notification_filter_popup",
+        "filter",
+        "unsubscribe_popup",
+        "reader",
+        "command_palette",
+        "help",
+    ];
+
+    fn trie(&self, keymap: &str) -> Option<&KeyTrie> {
+        let trie = match keymap {
+            "global" => &self.global,
+            "login" => &self.login,
+            "tabs" => &self.tabs,
+            "entries" => &self.entries,
+            "subscription" => &self.subscription,
+            "gh_notification" => &self.gh_notification,
+            "gh_notification_filter_popup" => &self.gh_notification_filter_popup,
+            "filter" => &self.filter,
+            "unsubscribe_popup" => &self.unsubscribe_popup,
+            "reader" => &self.reader,
+            "command_palette" => &self.command_palette,
+            "help" => &self.help,
+            _ => return None,
+        };
+        Some(trie)
+    }
+
+    fn trie_mut(&mut self, keymap: &str) -> Option<&mut KeyTrie> {
+        let trie = match keymap {
+            "global" => &mut self.global,
+            "login" => &mut self.login,
+            "tabs" => &mut self.tabs,
+            "entries" => &mut self.entries,
+            "subscription" => &mut self.subscription,
+            "gh_notification" => &mut self.gh_notification,
+            "gh_notification_filter_popup" => &mut self.gh_notification_filter_popup,
+            "filter" => &mut self.filter,
+            "unsubscribe_popup" => &mut self.unsubscribe_popup,
+            "reader" => &mut self.reader,
+            "command_palette" => &mut self.command_palette,
+            "help" => &mut self.help,
+            _ => return None,
+        };
+        Some(trie)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct Keymaps {
     keymaps: Vec<Keymap>,
+    /// Enabled state of the keymaps before `focus`
+    suspended: Option<Vec<bool>>,
 }
 
 impl Keymaps {
@@ -113,9 +399,15 @@ impl Keymaps {
                 KeymapId::GhNotificationFilterPopup,
                 config.gh_notification_filter_popup,
             ),
+            Keymap::new(KeymapId::Reader, config.reader),
+            Keymap::new(KeymapId::CommandPalette, config.command_palette),
+            Keymap::new(KeymapId::Help, config.help),
         ];
 
-        Self { keymaps }
+        Self {
+            keymaps,
+            suspended: None,
+        }
     }
 
     pub fn enable(&mut self, id: KeymapId) -> &mut Self {
@@ -128,6 +420,87 @@ impl Keymaps {
         self
     }
 
+    /// Enable only the keymap until `unfocus` is called.
+    /// Focusing again keeps the keymaps enabled before the first `focus`
+    pub fn focus(&mut self, id: KeymapId) {
+        if self.suspended.is_none() {
+            self.suspended = Some(self.keymaps.iter().map(|keymap| keymap.enable).collect());
+        }
+        for keymap in &mut self.keymaps {
+            keymap.enable = false;
+        }
+        self.enable(id);
+    }
+
+    /// Restore the keymaps enabled before `focus`
+    pub fn unfocus(&mut self) {
+        if let Some(suspended) = self.suspended.take() {
+            for (keymap, enable) in self.keymaps.iter_mut().zip(suspended) {
+                keymap.enable = enable;
+            }
+        }
+    }
+
+    /// Key sequence like `g g` bound to the command of the name in the enabled keymaps
+    pub fn binding(&self, name: &str) -> Option<String> {
+        self.keymaps
+            .iter()
+            .rev()
+            .filter(|keymap| keymap.enable)
+            .find_map(|keymap| {
+                keymap
+                    .trie
+                    .bindings()
+                    .into_iter()
+                    .filter(|(.., bound)| *bound == Some(name))
+                    .map(|(keys, ..)| keys.iter().map(format).join(" "))
+                    .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+            })
+    }
+
+    /// Bindings of the enabled keymaps in order of priority.
+    /// Bindings shadowed by a keymap with higher priority are omitted
+    pub fn help(&self) -> Vec<KeymapHelp> {
+        let mut shadowed = HashSet::new();
+        let mut helps = Vec::new();
+        for keymap in self.keymaps.iter().rev().filter(|keymap| keymap.enable) {
+            let bindings = keymap.trie.bindings();
+            let mut grouped: Vec<(Vec<String>, String)> = Vec::new();
+            for (keys, command, name) in &bindings {
+                if keys.first().is_none_or(|first| shadowed.contains(first)) {
+                    continue;
+                }
+                let keys = keys.iter().map(format).join(" ");
+                let description = name.map_or_else(|| command.description(), label);
+                match grouped.iter_mut().find(|(_, d)| *d == description) {
+                    Some((group, _)) => group.push(keys),
+                    None => grouped.push((vec![keys], description)),
+                }
+            }
+            shadowed.extend(
+                bindings
+                    .iter()
+                    .filter_map(|(keys, ..)| keys.first().copied()),
+            );
+
+            if grouped.is_empty() {
+                continue;
+            }
+            for (keys, _) in &mut grouped {
+                keys.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+            }
+            grouped.sort();
+            helps.push(KeymapHelp {
+                title: keymap.id.title(),
+                bindings: grouped
+                    .into_iter()
+                    .map(|(keys, description)| (keys.join("/"), description))
+                    .collect(),
+            });
+        }
+        helps
+    }
+
     pub fn update(&mut self, id: KeymapId, keymap: Keymap) {
         let mut keymap = keymap;
         keymap.enable = true;
@@ -160,23 +533,88 @@ impl Default for Keymaps {
 
 #[derive(Clone, Debug)]
 pub(crate) enum KeyTrie {
-    Command(Command),
+    Command {
+        command: Command,
+        /// Name of the constructor which is also used in the keymap configuration
+        name: Option<&'static str>,
+    },
     Node(KeyTrieNode),
 }
 
 impl KeyTrie {
+    pub fn command(command: Command) -> Self {
+        KeyTrie::Command {
+            command,
+            name: None,
+        }
+    }
+
+    /// Resolve the command from the name
+    pub fn named(name: &'static str) -> Option<Self> {
+        Some(KeyTrie::Command {
+            command: Command::from_name(name)?,
+            name: Some(name),
+        })
+    }
+
     pub fn search(&self, keys: &[KeyEvent]) -> Option<KeyTrie> {
         let mut trie = self;
         for key in keys {
             trie = match trie {
-                KeyTrie::Command(_) => return Some(trie.clone()),
+                KeyTrie::Command { .. } => return Some(trie.clone()),
                 KeyTrie::Node(trie) => trie.map.get(key)?,
             }
         }
         Some(trie.clone())
     }
+
+    /// Key sequences bound to the commands with their names
+    fn bindings(&self) -> Vec<Binding<'_>> {
+        fn walk<'a>(trie: &'a KeyTrie, keys: &mut Vec<KeyEvent>, bindings: &mut Vec<Binding<'a>>) {
+            match trie {
+                KeyTrie::Command { command, name } => bindings.push((keys.clone(), command, *name)),
+                KeyTrie::Node(node) => {
+                    for (key, trie) in &node.map {
+                        keys.push(*key);
+                        walk(trie, keys, bindings);
+                        keys.pop();
+                    }
+                }
+            }
+        }
+        let mut bindings = Vec::new();
+        walk(self, &mut Vec::new(), &mut bindings);
+        bindings
+    }
+
+    /// Bind the key sequence to the command.
+    /// Existing bindings which share the prefix with the sequence are replaced
+    fn insert(&mut self, keys: &[KeyEvent], command: KeyTrie) {
+        let Some((last, prefix)) = keys.split_last() else {
+            return;
+        };
+        let mut trie = self;
+        for key in prefix {
+            if let KeyTrie::Command { .. } = trie {
+                *trie = KeyTrie::default();
+            }
+            let KeyTrie::Node(node) = trie else {
+                unreachable!()
+            };
+            trie = node.map.entry(*key).or_default();
+        }
+        if let KeyTrie::Command { .. } = trie {
+            *trie = KeyTrie::default();
+        }
+        if let KeyTrie::Node(node) = trie {
+            node.map.insert(*last, command);
+        }
+    }
 }
 
+/// Key sequence, command and its name
+type Binding<'a> = (Vec<KeyEvent>, &'a Command, Option<&'static str>);
+
 impl Default for KeyTrie {
     fn default() -> Self {
         KeyTrie::Node(KeyTrieNode {
@@ -190,6 +628,18 @@ pub struct KeyTrieNode {
     map: HashMap<KeyEvent, KeyTrie>,
 }
 
+/// Parse whitespace separated keys such as `g g`
+fn parse_sequence(s: &str) -> anyhow::Result<Vec<KeyEvent>> {
+    let sequence = s
+        .split_whitespace()
+        .map(parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    if sequence.is_empty() {
+        bail!("empty key sequence");
+    }
+    Ok(sequence)
+}
+
 fn parse(s: &str) -> anyhow::Result<KeyEvent> {
     let mut tokens: Vec<_> = s.split('-').collect();
     let code = match tokens.pop().ok_or_else(|| anyhow!("no token"))? {
@@ -222,3 +672,243 @@ fn parse(s: &str) -> anyhow::Result<KeyEvent> {
     }
     Ok(KeyEvent::new(code, modifiers))
 }
+
+/// Format the key in the notation accepted by `parse`
+fn format(key: &KeyEvent) -> String {
+    let code = match key.code {
+        KeyCode::Enter => "enter".to_owned(),
+        KeyCode::Tab => "tab".to_owned(),
+        KeyCode::BackTab => return "backtab".to_owned(),
+        KeyCode::Left => "left".to_owned(),
+        KeyCode::Right => "right".to_owned(),
+        KeyCode::Up => "up".to_owned(),
+        KeyCode::Down => "down".to_owned(),
+        KeyCode::Esc => "esc".to_owned(),
+        KeyCode::Char(' ') => "space".to_owned(),
+        KeyCode::Char(c) => c.to_string(),
+        code => code.to_string(),
+    };
+    [
+        (KeyModifiers::CONTROL, "C-"),
+        (KeyModifiers::SHIFT, "S-"),
+        (KeyModifiers::ALT, "A-"),
+    ]
+    .into_iter()
+    .filter(|(modifier, _)| key.modifiers.contains(*modifier))
+    .map(|(_, prefix)| prefix)
+    .chain([code.as_str()])
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_config_lists_keymaps() {
+        let listed = crate::config::INIT_CONFIG
+            .lines()
+            .skip_while(|line| !line.starts_with("# Keymaps:"))
+            .take_while(|line| !line.starts_with("# ["))
+            .flat_map(|line| {
+                line.trim_start_matches("# Keymaps:")
+                    .trim_start_matches('#')
+                    .split(',')
+            })
+            .map(str::trim)
+            .filter(|keymap| !keymap.is_empty())
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listed,
+            KeymapsConfig::KEYMAPS
+                .into_iter()
+                .sorted()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    fn user(keymap: &str, bindings: &[(&str, &str)]) -> UserKeymaps {
+        let table = bindings
+            .iter()
+            .map(|(keys, command)| ((*keys).to_owned(), (*command).to_owned()))
+            .collect();
+        BTreeMap::from([(keymap.to_owned(), table)])
+    }
+
+    /// Name of the command bound to the keys
+    fn search(trie: &KeyTrie, keys: &str) -> Option<&'static str> {
+        match trie.search(&parse_sequence(keys).unwrap()) {
+            Some(KeyTrie::Command { name, .. }) => name,
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn merge_over_defaults() {
+        let mut config = KeymapsConfig::default();
+        config
+            .merge(&user(
+                "entries",
+                &[
+                    ("C-n", "move_down_entry"),
+                    ("g t", "move_entry_last"),
+                    ("r", "browse_entry"),
+                    ("y y", "reload_entries"),
+                ],
+            ))
+            .unwrap();
+
+        let entries = &config.entries;
+        assert_eq!(search(entries, "C-n"), Some("move_down_entry"));
+        assert_eq!(search(entries, "r"), Some("browse_entry"));
+        assert_eq!(search(entries, "y y"), Some("reload_entries"));
+        // Defaults sharing the prefix are kept
+        assert_eq!(search(entries, "g t"), Some("move_entry_last"));
+        assert_eq!(search(entries, "g g"), Some("move_entry_first"));
+        assert_eq!(search(entries, "j"), Some("move_down_entry"));
+
+        // Rebinding the prefix would drop the sequences under it
+        let mut config = KeymapsConfig::default();
+        let errors = config
+            .merge(&user("entries", &[("g", "reload_entries")]))
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![KeymapError::ConflictDefault {
+                keymap: "entries".into(),
+                keys: "g".into(),
+                default: "g e".into(),
+            }]
+        );
+        assert_eq!(search(&config.entries, "g g"), Some("move_entry_first"));
+        assert_eq!(search(&config.entries, "g e"), Some("move_entry_last"));
+    }
+
+    #[test]
+    fn conflicts_across_keymaps() {
+        let keymaps = user("entries", &[("q", "reload_entries"), ("y y", "quit")]);
+        let mut config = KeymapsConfig::default();
+        config.merge(&keymaps).unwrap();
+
+        assert_eq!(
+            config.conflicts(&keymaps),
+            vec![KeymapError::ConflictKeymap {
+                keymap: "entries".into(),
+                keys: "q".into(),
+                other_keymap: "global".into(),
+                other: "q".into(),
+            }]
+        );
+        // Keymaps never enabled at the same time do not conflict
+        let keymaps = user("reader", &[("r", "quit")]);
+        assert_eq!(config.conflicts(&keymaps), vec![]);
+    }
+
+    #[test]
+    fn validate_user_keymaps() {
+        let mut keymaps = user(
+            "entries",
+            &[("y", "move_entry_first"), ("y x", "quit"), ("g", "quit")],
+        );
+        keymaps.extend(user("unknown", &[("a", "quit")]));
+        keymaps.extend(user(
+            "global",
+            &[("C-z", "suspend"), ("X-a", "quit"), ("S-q", "quit")],
+        ));
+
+        let mut config = KeymapsConfig::default();
+        let errors = config.merge(&keymaps).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                KeymapError::ConflictDefault {
+                    keymap: "entries".into(),
+                    keys: "g".into(),
+                    default: "g e".into(),
+                },
+                KeymapError::Conflict {
+                    keymap: "entries".into(),
+                    keys: "y x".into(),
+                    other: "y".into(),
+                },
+                KeymapError::UnknownCommand {
+                    keymap: "global".into(),
+                    command: "suspend".into(),
+                },
+                KeymapError::InvalidKey {
+                    keymap: "global".into(),
+                    keys: "X-a".into(),
+                    message: "`X` modifier is not implemented yet".into(),
+                },
+                KeymapError::UnknownKeymap {
+                    keymap: "unknown".into(),
+                },
+            ]
+        );
+        // Nothing is applied when the configuration is invalid
+        assert_eq!(search(&config.global, "S-q"), None);
+    }
+
+    #[test]
+    fn binding_of_enabled_keymaps() {
+        let mut keymaps = Keymaps::default();
+        keymaps.enable(KeymapId::Global).enable(KeymapId::Entries);
+
+        assert_eq!(keymaps.binding("move_entry_first").as_deref(), Some("g g"));
+        assert_eq!(keymaps.binding("quit").as_deref(), Some("q"));
+        assert_eq!(
+            keymaps.binding("open_command_palette").as_deref(),
+            Some(":")
+        );
+        assert_eq!(keymaps.binding("rotate_theme").as_deref(), Some("S-t"));
+        // Subscription keymap is disabled
+        assert_eq!(keymaps.binding("open_feed"), None);
+
+        keymaps.focus(KeymapId::CommandPalette);
+        assert_eq!(keymaps.binding("quit"), None);
+        keymaps.unfocus();
+        assert_eq!(keymaps.binding("quit").as_deref(), Some("q"));
+        assert_eq!(keymaps.binding("execute_command_palette"), None);
+    }
+
+    #[test]
+    fn help_of_enabled_keymaps() {
+        let mut keymaps = Keymaps::default();
+        keymaps
+            .enable(KeymapId::Global)
+            .enable(KeymapId::Entries)
+            .enable(KeymapId::Reader);
+
+        let help = keymaps.help();
+        assert_eq!(
+            help.iter().map(|keymap| keymap.title).collect::<Vec<_>>(),
+            vec!["Reader", "Entries", "Global"]
+        );
+
+        let binding = |title: &str, description: &str| {
+            help.iter()
+                .find(|keymap| keymap.title == title)
+                .and_then(|keymap| {
+                    keymap
+                        .bindings
+                        .iter()
+                        .find(|(_, d)| d == description)
+                        .map(|(keys, _)| keys.clone())
+                })
+        };
+        assert_eq!(
+            binding("Reader", "Scroll up reader").as_deref(),
+            Some("k/up")
+        );
+        assert_eq!(
+            binding("Reader", "Input link number").as_deref(),
+            Some("0/1/2/3/4/5/6/7/8/9")
+        );
+        assert_eq!(binding("Global", "Open help").as_deref(), Some("?"));
+        // Shadowed by the reader
+        assert_eq!(binding("Entries", "Move up entry"), None);
+        assert_eq!(binding("Global", "Quit").as_deref(), Some("C-c"));
+    }
+}
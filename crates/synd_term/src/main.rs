@@ -2,15 +2,19 @@ use std::{future, path::PathBuf, process::ExitCode};
 
 use anyhow::Context as _;
 use futures_util::TryFutureExt as _;
+use synd_auth::{
+    device_flow::{DeviceFlow, provider},
+    jwt,
+    oidc::ProviderMetadata,
+};
 use synd_stdx::fs::fsimpl::FileSystem;
 use synd_term::{
-    application::{Application, Cache, Config, Features},
+    application::{Application, Authenticator, Cache, Config, Features},
     cli::{self, Args},
     client::{github::GithubClient, synd_api::Client},
     config::{self, ConfigResolver},
-    interact::{ProcessInteractor, TextBrowserInteractor},
+    interact::{MediaPlayerInteractor, ProcessInteractor, TextBrowserInteractor},
     terminal::{self, Terminal},
-    ui::theme::Theme,
 };
 use tracing::error;
 use tracing_appender::non_blocking::WorkerGuard;
@@ -62,8 +66,50 @@ fn init_tracing(log_path: Option<PathBuf>) -> anyhow::Result<Option<WorkerGuard>
     Ok(guard)
 }
 
+/// Configure the authenticator. If an oidc issuer is configured, its provider metadata is discovered
+async fn build_authenticator(config: &ConfigResolver) -> anyhow::Result<Authenticator> {
+    let authenticator = Authenticator::new();
+    let (Some(issuer), Some(client_id)) = (config.oidc_issuer(), config.oidc_client_id()) else {
+        return Ok(authenticator);
+    };
+
+    let metadata = ProviderMetadata::discover(&issuer)
+        .await
+        .with_context(|| format!("Failed to discover oidc provider: {issuer}"))?;
+    let mut device_flow = provider::Oidc::from_metadata(client_id.clone(), &metadata)
+        .context("Oidc provider does not support device authorization flow")?;
+    let mut jwt_service = jwt::oidc::JwtService::from_metadata(client_id, &metadata);
+    if let Some(client_secret) = config.oidc_client_secret() {
+        device_flow = device_flow.with_client_secret(client_secret.clone());
+        jwt_service = jwt_service.with_client_secret(client_secret);
+    }
+    let scopes = config.oidc_scopes();
+    if !scopes.is_empty() {
+        device_flow = device_flow.with_scopes(scopes);
+    }
+
+    let mut device_flows = authenticator.device_flows.clone();
+    device_flows.oidc = Some(DeviceFlow::new(device_flow));
+    let jwt_service = authenticator
+        .jwt_service
+        .clone()
+        .with_oidc_jwt_service(jwt_service);
+
+    Ok(authenticator
+        .with_device_flows(device_flows)
+        .with_jwt_service(jwt_service))
+}
+
 #[allow(clippy::needless_pass_by_value)]
-fn build_app(config: ConfigResolver, dry_run: bool) -> anyhow::Result<Application> {
+fn build_app(
+    config: ConfigResolver,
+    authenticator: Authenticator,
+    dry_run: bool,
+) -> anyhow::Result<Application> {
+    let keymaps = config.keymaps().map_err(|errors| {
+        let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+        anyhow::anyhow!("invalid keymap configuration\n{}", errors.join("\n"))
+    })?;
     let mut builder = Application::builder()
         .terminal(Terminal::new().context("Failed to construct terminal")?)
         .client(
@@ -76,13 +122,18 @@ fn build_app(config: ConfigResolver, dry_run: bool) -> anyhow::Result<Applicatio
             features: Features {
                 enable_github_notification: config.is_github_enable(),
             },
+            keymaps,
+            themes: config.themes(),
+            saved_filters: config.filters(),
             ..Default::default()
         })
         .cache(Cache::new(config.cache_dir()))
-        .theme(Theme::with_palette(config.palette()))
+        .theme(config.theme())
         .interactor(Box::new(ProcessInteractor::new(
             TextBrowserInteractor::new(config.feed_browser_command(), config.feed_browser_args()),
+            MediaPlayerInteractor::new(config.feed_player_command(), config.feed_player_args()),
         )))
+        .authenticator(authenticator)
         .dry_run(dry_run);
 
     if config.is_github_enable() {
@@ -105,6 +156,7 @@ async fn main() -> ExitCode {
             api,
             feed,
             github,
+            oidc,
             command,
             palette,
             dry_run,
@@ -117,6 +169,7 @@ async fn main() -> ExitCode {
             .api_options(api)
             .feed_options(feed)
             .github_options(github)
+            .oidc_options(oidc)
             .palette(palette)
             .try_build()
         {
@@ -154,12 +207,21 @@ async fn main() -> ExitCode {
 
     let mut event_stream = terminal::event_stream();
 
-    if let Err(err) = future::ready(build_app(config, dry_run))
-        .and_then(|app| {
+    let authenticator = match build_authenticator(&config).await {
+        Ok(authenticator) => authenticator,
+        Err(err) => {
+            error!("{err:?}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(err) = Box::pin(
+        future::ready(build_app(config, authenticator, dry_run)).and_then(|app| {
             tracing::info!("Running...");
             app.run(&mut event_stream)
-        })
-        .await
+        }),
+    )
+    .await
     {
         error!("{err:?}");
         ExitCode::FAILURE
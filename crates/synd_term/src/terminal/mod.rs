@@ -4,7 +4,7 @@ use crossterm::{
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures_util::{Stream, future::Either, stream};
-use ratatui::Frame;
+use ratatui::{Frame, layout::Size};
 use std::io::{self, IsTerminal};
 
 #[cfg(not(feature = "integration"))]
@@ -75,6 +75,10 @@ impl Terminal {
         self.backend.clear().unwrap();
     }
 
+    pub fn size(&self) -> io::Result<Size> {
+        self.backend.size()
+    }
+
     #[cfg(feature = "integration")]
     pub fn buffer(&self) -> &Buffer {
         self.backend.backend().buffer()
@@ -1,8 +1,9 @@
+use std::time::Duration;
+
 use chrono::DateTime;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use synd_feed::types::{Category, FeedType, FeedUrl, Requirement};
-use tracing::warn;
 
 use crate::{
     client::synd_api::{
@@ -23,7 +24,7 @@ pub use requirement_ext::RequirementExt;
 
 pub(crate) mod github;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(test, derive(fake::Dummy))]
 pub struct Link {
     pub href: String,
@@ -54,13 +55,14 @@ impl From<mutation::subscribe_feed::Link> for Link {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(test, derive(fake::Dummy))]
 pub struct EntryMeta {
     pub title: Option<String>,
     pub published: Option<Time>,
     pub updated: Option<Time>,
-    pub summary: Option<String>,
+    /// Summary rendered as plain text by the api
+    pub summary_text: Option<String>,
 }
 
 impl From<query::subscription::EntryMeta> for EntryMeta {
@@ -69,7 +71,7 @@ impl From<query::subscription::EntryMeta> for EntryMeta {
             title: e.title,
             published: e.published.map(parse_time),
             updated: e.updated.map(parse_time),
-            summary: e.summary,
+            summary_text: e.summary_text,
         }
     }
 }
@@ -80,26 +82,12 @@ impl From<mutation::subscribe_feed::EntryMeta> for EntryMeta {
             title: e.title,
             published: e.published.map(parse_time),
             updated: e.updated.map(parse_time),
-            summary: e.summary,
+            summary_text: e.summary_text,
         }
     }
 }
 
-impl EntryMeta {
-    pub fn summary_text(&self, width: usize) -> Option<String> {
-        self.summary.as_deref().and_then(|summary| {
-            match html2text::from_read(summary.as_bytes(), width) {
-                Ok(text) => Some(text),
-                Err(err) => {
-                    warn!("convert summary html to text: {err}");
-                    None
-                }
-            }
-        })
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(test, derive(fake::Dummy))]
 pub struct Feed {
     pub feed_type: Option<FeedType>,
@@ -209,13 +197,41 @@ impl From<mutation::subscribe_feed::Feed> for Feed {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Media file attached to the entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(fake::Dummy))]
+pub struct Enclosure {
+    pub url: String,
+    pub mime_type: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+impl From<query::entries::EntryEnclosures> for Enclosure {
+    fn from(v: query::entries::EntryEnclosures) -> Self {
+        Self {
+            url: v.url,
+            mime_type: v.mime_type,
+            duration: v
+                .duration_seconds
+                .and_then(|seconds| u64::try_from(seconds).ok())
+                .map(Duration::from_secs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(fake::Dummy))]
 pub struct Entry {
+    /// Entry id which is unique within the feed
+    #[serde(default)]
+    pub id: String,
     pub title: Option<String>,
     pub published: Option<Time>,
     pub updated: Option<Time>,
     pub website_url: Option<String>,
-    pub summary: Option<String>,
+    /// Summary rendered as plain text by the api
+    pub summary_text: Option<String>,
+    pub enclosures: Vec<Enclosure>,
     pub feed_title: Option<String>,
     pub feed_url: FeedUrl,
     requirement: Option<Requirement>,
@@ -223,14 +239,6 @@ pub struct Entry {
 }
 
 impl Entry {
-    pub fn summary_text(&self, width: usize) -> Option<String> {
-        self.summary.as_deref().map(|summary| {
-            html2text::config::plain()
-                .string_from_read(summary.as_bytes(), width)
-                .unwrap_or_default()
-        })
-    }
-
     pub fn requirement(&self) -> Requirement {
         self.requirement.unwrap_or(ui::DEFAULT_REQUIREMNET)
     }
@@ -245,13 +253,15 @@ impl Entry {
 impl From<query::entries::Entry> for Entry {
     fn from(v: query::entries::Entry) -> Self {
         Self {
+            id: v.id,
             title: v.title,
             published: v.published.map(parse_time),
             updated: v.updated.map(parse_time),
             website_url: v.website_url,
             feed_title: v.feed.title,
             feed_url: v.feed.url,
-            summary: v.summary,
+            summary_text: v.summary_text,
+            enclosures: v.enclosures.into_iter().map(From::from).collect(),
             requirement: match v.feed.requirement {
                 Some(query::entries::Requirement::MUST) => Some(Requirement::Must),
                 Some(query::entries::Requirement::SHOULD) => Some(Requirement::Should),
@@ -301,6 +311,31 @@ impl From<ExportedFeed> for mutation::subscribe_feed::SubscribeFeedInput {
     }
 }
 
+impl From<mutation::subscribe_feed::SubscribeFeedInput>
+    for mutation::update_feeds::UpdateFeedInput
+{
+    fn from(input: mutation::subscribe_feed::SubscribeFeedInput) -> Self {
+        Self {
+            url: input.url,
+            requirement: input.requirement.map(|r| match r {
+                mutation::subscribe_feed::Requirement::MUST => {
+                    mutation::update_feeds::Requirement::MUST
+                }
+                mutation::subscribe_feed::Requirement::SHOULD => {
+                    mutation::update_feeds::Requirement::SHOULD
+                }
+                mutation::subscribe_feed::Requirement::MAY => {
+                    mutation::update_feeds::Requirement::MAY
+                }
+                mutation::subscribe_feed::Requirement::Other(other) => {
+                    mutation::update_feeds::Requirement::Other(other)
+                }
+            }),
+            category: input.category,
+        }
+    }
+}
+
 fn parse_time(t: impl AsRef<str>) -> Time {
     DateTime::parse_from_rfc3339(t.as_ref())
         .expect("invalid rfc3339 time")
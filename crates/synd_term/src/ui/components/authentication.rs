@@ -111,6 +111,9 @@ impl Authentication {
                 .map(|provider| match provider {
                     AuthenticationProvider::Github => Text::from(concat!(icon!(github), " GitHub")),
                     AuthenticationProvider::Google => Text::from(concat!(icon!(google), " Google")),
+                    AuthenticationProvider::Oidc => {
+                        Text::from(concat!(icon!(oidc), " OpenID Connect"))
+                    }
                 })
                 .map(ListItem::new);
 
@@ -1,15 +1,26 @@
-use std::ops::ControlFlow;
+use std::{
+    cmp::Ordering,
+    ops::{ControlFlow, RangeInclusive},
+};
 
 use crate::{
     application::{Direction, IndexOutOfRange, Populate},
     ui::components::filter::{FilterResult, Filterable},
 };
 
+/// Comparison to order the items which pass the filter
+pub(crate) type Order<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+
 pub(crate) struct FilterableVec<T, F> {
     items: Vec<T>,
+    /// Whether the item at the same index is marked for bulk operations
+    marks: Vec<bool>,
     effective_items: Vec<usize>,
     selected_item_index: usize,
+    /// Item index where the visual selection started
+    visual_anchor: Option<usize>,
     filterer: F,
+    order: Option<Order<T>>,
 }
 
 impl<T, F> FilterableVec<T, F>
@@ -23,9 +34,12 @@ where
     pub(crate) fn from_filter(filterer: F) -> Self {
         Self {
             items: Vec::new(),
+            marks: Vec::new(),
             effective_items: Vec::new(),
             selected_item_index: 0,
+            visual_anchor: None,
             filterer,
+            order: None,
         }
     }
 }
@@ -41,6 +55,11 @@ impl<T, F> FilterableVec<T, F> {
         self.selected_item_index
     }
 
+    /// Select the item at the index of the filtered items
+    pub(crate) fn select(&mut self, index: usize) {
+        self.selected_item_index = index.min(self.effective_items.len().saturating_sub(1));
+    }
+
     pub(crate) fn len(&self) -> usize {
         self.effective_items.len()
     }
@@ -81,6 +100,103 @@ impl<T, F> FilterableVec<T, F> {
     }
 }
 
+/// Operation on the marks of the items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Marking {
+    Toggle,
+    Visual,
+    All,
+    Clear,
+}
+
+impl<T, F> FilterableVec<T, F> {
+    pub(crate) fn update_marks(&mut self, marking: Marking) {
+        match marking {
+            Marking::Toggle => self.toggle_mark(),
+            Marking::Visual => self.toggle_visual(),
+            Marking::All => self.mark_all(),
+            Marking::Clear => self.clear_marks(),
+        }
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Some(&idx) = self.effective_items.get(self.selected_item_index) {
+            self.marks[idx] = !self.marks[idx];
+        }
+    }
+
+    /// Start the visual selection, or mark the range selected since it started
+    fn toggle_visual(&mut self) {
+        if let Some(range) = self.visual_range() {
+            for pos in range {
+                self.marks[self.effective_items[pos]] = true;
+            }
+            self.visual_anchor = None;
+        } else {
+            self.visual_anchor = self.effective_items.get(self.selected_item_index).copied();
+        }
+    }
+
+    /// Mark all items which pass the filter
+    fn mark_all(&mut self) {
+        for &idx in &self.effective_items {
+            self.marks[idx] = true;
+        }
+    }
+
+    /// Mark the filtered items at given positions
+    pub(crate) fn mark_positions(&mut self, positions: impl IntoIterator<Item = usize>) {
+        for pos in positions {
+            if let Some(&idx) = self.effective_items.get(pos) {
+                self.marks[idx] = true;
+            }
+        }
+    }
+
+    fn clear_marks(&mut self) {
+        self.marks.fill(false);
+        self.visual_anchor = None;
+    }
+
+    /// Iterate the filtered items with whether they are marked
+    pub(crate) fn iter_with_mark(&self) -> impl Iterator<Item = (&T, bool)> {
+        let visual = self.visual_range();
+        self.effective_items
+            .iter()
+            .enumerate()
+            .map(move |(pos, &idx)| {
+                let in_visual = visual.as_ref().is_some_and(|range| range.contains(&pos));
+                (&self.items[idx], self.marks[idx] || in_visual)
+            })
+    }
+
+    /// Filtered items which are marked, including the visual selection
+    pub(crate) fn marked(&self) -> Vec<&T> {
+        self.iter_with_mark()
+            .filter_map(|(item, marked)| marked.then_some(item))
+            .collect()
+    }
+
+    /// Marked items if any, otherwise the selected item
+    pub(crate) fn marked_or_selected(&self) -> Vec<&T> {
+        let marked = self.marked();
+        if marked.is_empty() {
+            self.selected().into_iter().collect()
+        } else {
+            marked
+        }
+    }
+
+    /// Positions in the filtered items covered by the visual selection
+    fn visual_range(&self) -> Option<RangeInclusive<usize>> {
+        let anchor = self
+            .visual_anchor
+            .and_then(|anchor| self.effective_items.iter().position(|&idx| idx == anchor))?;
+        let selected = self.selected_item_index;
+        Some(anchor.min(selected)..=anchor.max(selected))
+    }
+}
+
 impl<T, F> FilterableVec<T, F>
 where
     F: Filterable<T>,
@@ -88,8 +204,13 @@ where
     pub(crate) fn update(&mut self, populate: Populate, items: Vec<T>) {
         match populate {
             Populate::Append => self.items.extend(items),
-            Populate::Replace => self.items = items,
+            Populate::Replace => {
+                self.items = items;
+                self.marks.clear();
+                self.visual_anchor = None;
+            }
         }
+        self.marks.resize(self.items.len(), false);
         self.refresh();
     }
 
@@ -97,9 +218,12 @@ where
     where
         C: Fn(&T) -> bool,
     {
-        match self.items.iter_mut().find(|item| should_update(item)) {
-            Some(old) => *old = item,
-            None => self.items.insert(0, item),
+        if let Some(old) = self.items.iter_mut().find(|item| should_update(item)) {
+            *old = item;
+        } else {
+            self.items.insert(0, item);
+            self.marks.insert(0, false);
+            self.visual_anchor = self.visual_anchor.map(|anchor| anchor + 1);
         }
         self.refresh();
     }
@@ -127,6 +251,12 @@ where
         }
     }
 
+    /// Order the filtered items, keeping the original order of the equal ones
+    pub(crate) fn order_by(&mut self, order: Option<Order<T>>) {
+        self.order = order;
+        self.refresh();
+    }
+
     pub(crate) fn update_filter(&mut self, filterer: F) {
         self.filterer = filterer;
         self.refresh();
@@ -144,7 +274,16 @@ where
     where
         C: Fn(&T) -> bool,
     {
-        self.items.retain(cond);
+        let keep = self.items.iter().map(&cond).collect::<Vec<_>>();
+        self.visual_anchor = self
+            .visual_anchor
+            .filter(|&anchor| keep[anchor])
+            .map(|anchor| keep[..anchor].iter().filter(|&&keep| keep).count());
+        (self.items, self.marks) = std::mem::take(&mut self.items)
+            .into_iter()
+            .zip(std::mem::take(&mut self.marks))
+            .filter(|(item, _)| cond(item))
+            .unzip();
         self.refresh();
     }
 
@@ -156,9 +295,99 @@ where
             .filter(|(_idx, item)| self.filterer.filter(item) == FilterResult::Use)
             .map(|(idx, _)| idx)
             .collect();
+        if let Some(order) = self.order.as_ref() {
+            self.effective_items
+                .sort_by(|&a, &b| order(&self.items[a], &self.items[b]));
+        }
         // prevent selection from out of index
         self.selected_item_index = self
             .selected_item_index
             .min(self.effective_items.len().saturating_sub(1));
+        // the visual selection can not continue from the item hidden by the filter
+        self.visual_anchor = self
+            .visual_anchor
+            .filter(|anchor| self.effective_items.contains(anchor));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Use only the even numbers
+    #[derive(Default)]
+    struct Even;
+
+    impl Filterable<u32> for Even {
+        fn filter(&self, item: &u32) -> FilterResult {
+            if item % 2 == 0 {
+                FilterResult::Use
+            } else {
+                FilterResult::Discard
+            }
+        }
+    }
+
+    fn marked(v: &FilterableVec<u32, Even>) -> Vec<u32> {
+        v.marked().into_iter().copied().collect()
+    }
+
+    #[test]
+    fn toggle_and_mark_range() {
+        let mut v = FilterableVec::<u32, Even>::new();
+        v.update(Populate::Replace, (0..10).collect());
+        assert_eq!(v.marked_or_selected(), vec![&0]);
+
+        v.toggle_mark();
+        v.move_selection(Direction::Down);
+        v.toggle_visual();
+        v.move_selection(Direction::Down);
+        v.move_selection(Direction::Down);
+        assert_eq!(marked(&v), vec![0, 2, 4, 6]);
+
+        // moving back shrinks the range
+        v.move_selection(Direction::Up);
+        v.toggle_visual();
+        assert!(v.visual_anchor.is_none());
+        assert_eq!(marked(&v), vec![0, 2, 4]);
+
+        v.toggle_mark();
+        assert_eq!(marked(&v), vec![0, 2]);
+
+        v.clear_marks();
+        assert!(v.marked().is_empty());
+        assert_eq!(v.marked_or_selected(), vec![&4]);
+    }
+
+    #[test]
+    fn marks_follow_items() {
+        let mut v = FilterableVec::<u32, Even>::new();
+        v.update(Populate::Replace, (0..6).collect());
+        v.mark_all();
+        assert_eq!(marked(&v), vec![0, 2, 4]);
+
+        v.retain(|&n| n != 2);
+        v.upsert_first(8, |&n| n == 8);
+        v.update(Populate::Append, vec![10]);
+        assert_eq!(marked(&v), vec![0, 4]);
+
+        v.update(Populate::Replace, (0..6).collect());
+        assert!(v.marked().is_empty());
+    }
+
+    #[test]
+    fn order_filtered_items() {
+        let mut v = FilterableVec::<u32, Even>::new();
+        v.update(Populate::Replace, vec![4, 1, 0, 8, 2]);
+        v.order_by(Some(Box::new(|a: &u32, b: &u32| b.cmp(a))));
+        assert_eq!(v.iter().copied().collect::<Vec<_>>(), vec![8, 4, 2, 0]);
+
+        v.select(1);
+        v.toggle_visual();
+        v.move_selection(Direction::Down);
+        assert_eq!(marked(&v), vec![4, 2]);
+
+        v.order_by(None);
+        assert_eq!(v.iter().copied().collect::<Vec<_>>(), vec![4, 0, 8, 2]);
     }
 }
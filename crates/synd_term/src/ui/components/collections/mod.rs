@@ -1,2 +1,3 @@
 mod filterable;
-pub(super) use filterable::FilterableVec;
+pub(crate) use filterable::Marking;
+pub(super) use filterable::{FilterableVec, Order};
@@ -0,0 +1,199 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ratatui::{
+    prelude::{Alignment, Buffer, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{
+    application::{Direction, IndexOutOfRange},
+    command::{Command, label},
+    matcher::Matcher,
+    ui::{
+        Context,
+        extension::RectExt,
+        widgets::prompt::{Prompt, RenderCursor},
+    },
+};
+
+/// Command listed in the palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PaletteItem {
+    name: &'static str,
+    label: String,
+    binding: String,
+}
+
+impl PaletteItem {
+    pub(crate) fn new(name: &'static str, binding: String) -> Self {
+        Self {
+            name,
+            label: label(name),
+            binding,
+        }
+    }
+}
+
+/// Fuzzy searchable list of the commands
+pub(crate) struct CommandPalette {
+    prompt: Rc<RefCell<Prompt>>,
+    matcher: Matcher,
+    items: Vec<PaletteItem>,
+    /// Indices of the items matched with the prompt
+    matched: Vec<usize>,
+    selected: usize,
+    is_open: bool,
+}
+
+impl CommandPalette {
+    pub(crate) fn new() -> Self {
+        Self {
+            prompt: Rc::new(RefCell::new(Prompt::new())),
+            matcher: Matcher::new(),
+            items: Vec::new(),
+            matched: Vec::new(),
+            selected: 0,
+            is_open: false,
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Open the palette with the items, returning the prompt to receive key events
+    pub(crate) fn open(&mut self, items: Vec<PaletteItem>) -> Rc<RefCell<Prompt>> {
+        self.prompt = Rc::new(RefCell::new(Prompt::new()));
+        self.matcher.update_needle("");
+        self.matched = (0..items.len()).collect();
+        self.items = items;
+        self.selected = 0;
+        self.is_open = true;
+        self.prompt.clone()
+    }
+
+    pub(crate) fn close(&mut self) {
+        self.items.clear();
+        self.matched.clear();
+        self.is_open = false;
+    }
+
+    pub(crate) fn update_needle(&mut self) {
+        self.matcher.update_needle(self.prompt.borrow().line());
+        self.matched = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| self.matcher.r#match(&item.label))
+            .map(|(i, _)| i)
+            .collect();
+        self.selected = 0;
+    }
+
+    pub(crate) fn move_selection(&mut self, direction: Direction) {
+        self.selected =
+            direction.apply(self.selected, self.matched.len(), IndexOutOfRange::Wrapping);
+    }
+
+    pub(crate) fn selected_command(&self) -> Option<Command> {
+        let item = &self.items[*self.matched.get(self.selected)?];
+        Command::from_name(item.name)
+    }
+}
+
+impl CommandPalette {
+    pub(crate) fn render(&self, area: Rect, buf: &mut Buffer, cx: &Context<'_>) {
+        let area = {
+            let area = area.centered(60, 60);
+            area.reset(buf);
+            area
+        };
+
+        let block = Block::new()
+            .title_top("Commands")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::new().add_modifier(Modifier::BOLD))
+            .padding(Padding::horizontal(1))
+            .borders(Borders::ALL)
+            .style(cx.theme.base);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [prompt_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        let [marker_area, prompt_area] =
+            Layout::horizontal([Constraint::Length(2), Constraint::Fill(1)]).areas(prompt_area);
+        Line::from(":").render(marker_area, buf);
+        self.prompt
+            .borrow()
+            .render(prompt_area, buf, RenderCursor::Enable);
+
+        let rows = self.matched.iter().map(|&i| {
+            let item = &self.items[i];
+            Row::new([
+                Line::from(item.label.as_str()),
+                Line::from(Span::styled(
+                    item.binding.as_str(),
+                    cx.theme.prompt.key_desc,
+                ))
+                .alignment(Alignment::Right),
+            ])
+        });
+        let table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(12)])
+            .row_highlight_style(cx.theme.selection_popup.highlight);
+        let mut state =
+            TableState::new().with_selected((!self.matched.is_empty()).then_some(self.selected));
+        StatefulWidget::render(table, list_area, buf, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+
+    fn input(palette: &CommandPalette, s: &str) {
+        let prompt = palette.prompt.clone();
+        for c in s.chars() {
+            prompt
+                .borrow_mut()
+                .handle_key_event(&KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    fn selected_name(palette: &CommandPalette) -> Option<&'static str> {
+        let item = &palette.items[*palette.matched.get(palette.selected)?];
+        Some(item.name)
+    }
+
+    #[test]
+    fn filter_then_select() {
+        let mut palette = CommandPalette::new();
+        palette.open(vec![
+            PaletteItem::new("reload_entries", "r".into()),
+            PaletteItem::new("rotate_theme", "S-t".into()),
+            PaletteItem::new("reload_subscription", "r".into()),
+        ]);
+        assert!(palette.is_open());
+        assert_eq!(palette.items[1].label, "Rotate theme");
+
+        input(&palette, "reload");
+        palette.update_needle();
+        assert_eq!(palette.matched, vec![0, 2]);
+
+        palette.move_selection(Direction::Down);
+        assert_eq!(selected_name(&palette), Some("reload_subscription"));
+        palette.move_selection(Direction::Down);
+        assert_eq!(selected_name(&palette), Some("reload_entries"));
+
+        input(&palette, "xyz");
+        palette.update_needle();
+        assert!(palette.selected_command().is_none());
+
+        palette.close();
+        assert!(!palette.is_open());
+    }
+}
@@ -1,36 +1,193 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, cmp::Ordering, collections::HashSet, time::Duration};
 
 use crate::{
-    application::{Direction, Populate},
+    application::{Direction, IndexOutOfRange, Populate},
     client::synd_api::payload,
     types::{self, RequirementExt, TimeExt},
     ui::{
         self, Context,
-        components::{collections::FilterableVec, filter::FeedFilterer},
+        components::{
+            collections::{FilterableVec, Marking, Order},
+            filter::FeedFilterer,
+        },
         icon,
         widgets::{scrollbar::Scrollbar, table::Table},
     },
 };
+use itertools::Itertools;
 use ratatui::{
     prelude::{Alignment, Buffer, Constraint, Layout, Rect},
     style::Stylize,
     text::{Line, Span, Text},
     widgets::{Block, BorderType, Borders, Cell, Padding, Paragraph, Row, Widget, Wrap},
 };
-use synd_feed::types::FeedUrl;
+use serde::{Deserialize, Serialize};
+use synd_feed::types::{FeedUrl, Requirement};
+
+/// Key to sort the entries
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum EntrySort {
+    /// Order of the api, newest published first
+    #[default]
+    Published,
+    /// Newest updated first
+    Updated,
+    Feed,
+    Requirement,
+    Category,
+}
+
+impl EntrySort {
+    fn next(self) -> Self {
+        match self {
+            EntrySort::Published => EntrySort::Updated,
+            EntrySort::Updated => EntrySort::Feed,
+            EntrySort::Feed => EntrySort::Requirement,
+            EntrySort::Requirement => EntrySort::Category,
+            EntrySort::Category => EntrySort::Published,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            EntrySort::Published => "published",
+            EntrySort::Updated => "updated",
+            EntrySort::Feed => "feed",
+            EntrySort::Requirement => "requirement",
+            EntrySort::Category => "category",
+        }
+    }
+
+    fn compare(self, a: &types::Entry, b: &types::Entry) -> Ordering {
+        fn rank(requirement: Requirement) -> u8 {
+            match requirement {
+                Requirement::Must => 0,
+                Requirement::Should => 1,
+                Requirement::May => 2,
+            }
+        }
+        match self {
+            // entries are already ordered by the api
+            EntrySort::Published => Ordering::Equal,
+            EntrySort::Updated => {
+                let updated = |entry: &types::Entry| entry.updated.or(entry.published);
+                updated(b).cmp(&updated(a))
+            }
+            EntrySort::Feed => feed_label(a).cmp(feed_label(b)),
+            EntrySort::Requirement => rank(a.requirement()).cmp(&rank(b.requirement())),
+            EntrySort::Category => a.category().cmp(b.category()),
+        }
+    }
+}
+
+/// Header to group the entries under
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum EntryGrouping {
+    #[default]
+    None,
+    Feed,
+    Category,
+}
+
+impl EntryGrouping {
+    fn next(self) -> Self {
+        match self {
+            EntryGrouping::None => EntryGrouping::Feed,
+            EntryGrouping::Feed => EntryGrouping::Category,
+            EntryGrouping::Category => EntryGrouping::None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            EntryGrouping::None => "none",
+            EntryGrouping::Feed => "feed",
+            EntryGrouping::Category => "category",
+        }
+    }
+
+    fn key(self, entry: &types::Entry) -> &str {
+        match self {
+            EntryGrouping::None => "",
+            EntryGrouping::Feed => feed_label(entry),
+            EntryGrouping::Category => entry.category().as_str(),
+        }
+    }
+}
+
+fn feed_label(entry: &types::Entry) -> &str {
+    entry
+        .feed_title
+        .as_deref()
+        .unwrap_or(entry.feed_url.as_str())
+}
+
+/// How the entries are arranged, persisted across sessions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct EntriesViewOptions {
+    pub(crate) sort: EntrySort,
+    pub(crate) grouping: EntryGrouping,
+}
+
+impl EntriesViewOptions {
+    fn order(self) -> Option<Order<types::Entry>> {
+        if self == Self::default() {
+            return None;
+        }
+        Some(Box::new(move |a: &types::Entry, b: &types::Entry| {
+            // entries in the same group must be adjacent
+            self.grouping
+                .key(a)
+                .cmp(self.grouping.key(b))
+                .then_with(|| self.sort.compare(a, b))
+        }))
+    }
+}
+
+enum EntryRow {
+    Group {
+        key: String,
+        /// Index of the first entry in the group
+        first: usize,
+        count: usize,
+    },
+    Entry {
+        index: usize,
+    },
+}
 
 #[allow(clippy::struct_field_names)]
 pub(crate) struct Entries {
     entries: FilterableVec<types::Entry, FeedFilterer>,
+    options: EntriesViewOptions,
+    /// Keys of the groups whose entries are hidden
+    collapsed: HashSet<String>,
+    /// Entries with the headers of their groups
+    rows: Vec<EntryRow>,
+    selected_row: usize,
 }
 
 impl Entries {
     pub(crate) fn new() -> Self {
+        Self::with_view_options(EntriesViewOptions::default())
+    }
+
+    pub(crate) fn with_view_options(options: EntriesViewOptions) -> Self {
+        let mut entries = FilterableVec::new();
+        entries.order_by(options.order());
         Self {
-            entries: FilterableVec::new(),
+            entries,
+            options,
+            collapsed: HashSet::new(),
+            rows: Vec::new(),
+            selected_row: 0,
         }
     }
 
+    pub(crate) fn view_options(&self) -> EntriesViewOptions {
+        self.options
+    }
+
     /// Return entries count
     pub(crate) fn count(&self) -> usize {
         self.entries.len()
@@ -42,26 +199,124 @@ impl Entries {
         payload: payload::FetchEntriesPayload,
     ) {
         self.entries.update(populate, payload.entries);
+        self.rebuild_rows();
+    }
+
+    /// Show the entries cached in the previous session
+    pub(crate) fn restore_entries(&mut self, entries: Vec<types::Entry>) {
+        self.entries.update(Populate::Replace, entries);
+        self.rebuild_rows();
     }
 
     pub(crate) fn update_filterer(&mut self, filterer: FeedFilterer) {
         self.entries.update_filter(filterer);
+        self.rebuild_rows();
     }
 
     pub(crate) fn remove_unsubscribed_entries(&mut self, url: &FeedUrl) {
         self.entries.retain(|entry| &entry.feed_url != url);
+        self.rebuild_rows();
     }
 
     pub(crate) fn move_selection(&mut self, direction: Direction) {
-        self.entries.move_selection(direction);
+        self.select_row(direction.apply(
+            self.selected_row,
+            self.rows.len(),
+            IndexOutOfRange::Wrapping,
+        ));
     }
 
     pub(crate) fn move_first(&mut self) {
-        self.entries.move_first();
+        self.select_row(0);
     }
 
     pub(crate) fn move_last(&mut self) {
-        self.entries.move_last();
+        self.select_row(self.rows.len().saturating_sub(1));
+    }
+
+    pub(crate) fn update_marks(&mut self, marking: Marking) {
+        match marking {
+            // a group header is not an entry to mark
+            Marking::Toggle if self.selected_entry().is_none() => {}
+            // entries of the collapsed groups are hidden, so they are not marked
+            Marking::All => {
+                self.entries
+                    .mark_positions(self.rows.iter().filter_map(|row| match row {
+                        EntryRow::Entry { index } => Some(*index),
+                        EntryRow::Group { .. } => None,
+                    }));
+            }
+            marking => self.entries.update_marks(marking),
+        }
+    }
+
+    /// Entries the bulk operations act on.
+    /// Marked entries if any, otherwise the selected entry. nothing when a group header is selected
+    pub(crate) fn marked_entries(&self) -> Vec<&types::Entry> {
+        let marked = self.entries.marked();
+        if marked.is_empty() {
+            self.selected_entry().into_iter().collect()
+        } else {
+            marked
+        }
+    }
+
+    pub(crate) fn rotate_sort(&mut self) {
+        self.options.sort = self.options.sort.next();
+        self.entries.order_by(self.options.order());
+        self.rebuild_rows();
+    }
+
+    pub(crate) fn rotate_grouping(&mut self) {
+        self.options.grouping = self.options.grouping.next();
+        self.collapsed.clear();
+        self.entries.order_by(self.options.order());
+        self.rebuild_rows();
+    }
+
+    /// Collapse or expand the group of the selection
+    pub(crate) fn toggle_group(&mut self) {
+        let key = match self.rows.get(self.selected_row) {
+            Some(EntryRow::Group { key, .. }) => key.clone(),
+            Some(EntryRow::Entry { .. }) if self.options.grouping != EntryGrouping::None => {
+                let Some(entry) = self.entries.selected() else {
+                    return;
+                };
+                self.options.grouping.key(entry).to_owned()
+            }
+            _ => return,
+        };
+        if !self.collapsed.remove(&key) {
+            self.collapsed.insert(key.clone());
+        }
+        self.rebuild_rows();
+        self.select_group(&key);
+    }
+
+    /// Collapse all groups, or expand them if any is collapsed
+    pub(crate) fn toggle_all_groups(&mut self) {
+        if self.collapsed.is_empty() {
+            self.collapsed = self
+                .rows
+                .iter()
+                .filter_map(|row| match row {
+                    EntryRow::Group { key, .. } => Some(key.clone()),
+                    EntryRow::Entry { .. } => None,
+                })
+                .collect();
+        } else {
+            self.collapsed.clear();
+        }
+        let key = self
+            .entries
+            .selected()
+            .map(|entry| self.options.grouping.key(entry).to_owned());
+        self.rebuild_rows();
+        if let Some(key) = key {
+            if !self.collapsed.is_empty() {
+                self.select_group(&key);
+            }
+        }
     }
 
     pub(crate) fn entries(&self) -> &[types::Entry] {
@@ -69,13 +324,84 @@ impl Entries {
     }
 
     pub(crate) fn selected_entry_website_url(&self) -> Option<&str> {
-        self.entries
-            .selected()
+        self.selected_entry()
             .and_then(|entry| entry.website_url.as_deref())
     }
 
-    fn selected_entry(&self) -> Option<&types::Entry> {
-        self.entries.selected()
+    pub(crate) fn selected_entry_enclosure_url(&self) -> Option<&str> {
+        self.selected_entry()
+            .and_then(|entry| entry.enclosures.first())
+            .map(|enclosure| enclosure.url.as_str())
+    }
+
+    pub(crate) fn selected_entry(&self) -> Option<&types::Entry> {
+        match self.rows.get(self.selected_row) {
+            Some(EntryRow::Entry { .. }) => self.entries.selected(),
+            _ => None,
+        }
+    }
+
+    /// Arrange the filtered entries under the headers of their groups
+    fn rebuild_rows(&mut self) {
+        let grouping = self.options.grouping;
+        self.rows = if grouping == EntryGrouping::None {
+            (0..self.entries.len())
+                .map(|index| EntryRow::Entry { index })
+                .collect()
+        } else {
+            let mut rows = Vec::new();
+            let groups = self
+                .entries
+                .iter()
+                .enumerate()
+                .chunk_by(|(_, entry)| grouping.key(entry));
+            for (key, group) in &groups {
+                let indices = group.map(|(index, _)| index).collect::<Vec<_>>();
+                rows.push(EntryRow::Group {
+                    key: key.to_owned(),
+                    first: indices[0],
+                    count: indices.len(),
+                });
+                if !self.collapsed.contains(key) {
+                    rows.extend(indices.into_iter().map(|index| EntryRow::Entry { index }));
+                }
+            }
+            rows
+        };
+
+        // keep the selected entry, or the header of its collapsed group
+        let selected = self.entries.selected_index();
+        let row = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, EntryRow::Entry { index } if *index == selected))
+            .or_else(|| {
+                self.rows.iter().rposition(
+                    |row| matches!(row, EntryRow::Group { first, .. } if *first <= selected),
+                )
+            })
+            .unwrap_or(0);
+        self.select_row(row);
+    }
+
+    fn select_group(&mut self, key: &str) {
+        if let Some(row) = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, EntryRow::Group { key: k, .. } if k == key))
+        {
+            self.select_row(row);
+        }
+    }
+
+    fn select_row(&mut self, row: usize) {
+        self.selected_row = row.min(self.rows.len().saturating_sub(1));
+        match self.rows.get(self.selected_row) {
+            Some(EntryRow::Entry { index } | EntryRow::Group { first: index, .. }) => {
+                self.entries.select(*index);
+            }
+            None => {}
+        }
     }
 }
 
@@ -98,7 +424,7 @@ impl Entries {
             .widths(widths)
             .rows(rows)
             .theme(&cx.theme.entries)
-            .selected_idx(self.entries.selected_index())
+            .selected_idx(self.selected_row)
             .highlight_modifier(cx.table_highlight_modifier())
             .build()
             .render(entries_area, buf);
@@ -110,13 +436,13 @@ impl Entries {
             height: area
                 .height
                 .saturating_sub(header_rows)
-                .min(self.entries.len() as u16),
+                .min(self.rows.len() as u16),
             ..area
         };
 
         Scrollbar {
-            content_length: self.entries.len(),
-            position: self.entries.selected_index(),
+            content_length: self.rows.len(),
+            position: self.selected_row,
         }
         .render(scrollbar_area, buf, cx);
     }
@@ -139,9 +465,28 @@ impl Entries {
                 )
             }
         };
+        let marks = match self.entries.marked().len() {
+            0 => String::new(),
+            k => format!(" {} {k}", icon!(check)),
+        };
+        let view = {
+            let EntriesViewOptions { sort, grouping } = self.options;
+            [
+                (sort != EntrySort::default())
+                    .then(|| format!(" {} {}", icon!(sort), sort.label())),
+                (grouping != EntryGrouping::default())
+                    .then(|| format!(" {} {}", icon!(folder), grouping.label())),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<String>()
+        };
         let header = Row::new([
-            Cell::from("Published"),
-            Cell::from(format!("Entry {n}/{m}")),
+            Cell::from(match self.options.sort {
+                EntrySort::Updated => "Updated",
+                _ => "Published",
+            }),
+            Cell::from(format!("Entry {n}/{m}{marks}{view}")),
             Cell::from("Feed"),
             Cell::from("Req"),
         ]);
@@ -153,13 +498,13 @@ impl Entries {
             Constraint::Length(4),
         ];
 
-        let row = |entry: &'a types::Entry| {
+        let row = |(entry, marked): (&'a types::Entry, bool)| {
             let title = entry.title.as_deref().unwrap_or(ui::UNKNOWN_SYMBOL);
-            let published = entry
-                .published
-                .as_ref()
-                .or(entry.updated.as_ref())
-                .map_or_else(|| ui::UNKNOWN_SYMBOL.to_string(), TimeExt::local_ymd);
+            let published = match self.options.sort {
+                EntrySort::Updated => entry.updated.as_ref().or(entry.published.as_ref()),
+                _ => entry.published.as_ref().or(entry.updated.as_ref()),
+            }
+            .map_or_else(|| ui::UNKNOWN_SYMBOL.to_string(), TimeExt::local_ymd);
             let category = entry.category();
             let icon = cx
                 .categories
@@ -169,19 +514,52 @@ impl Entries {
             let feed_title = entry.feed_title.as_deref().unwrap_or(ui::UNKNOWN_SYMBOL);
             let requirement = entry.requirement().label(&cx.theme.requirement);
 
+            let mut title_spans = vec![
+                Span::from(if marked {
+                    concat!(icon!(check), " ")
+                } else {
+                    ""
+                })
+                .bold(),
+                Span::from(icon.symbol()).fg(icon.color().unwrap_or(cx.theme.default_icon_fg)),
+                Span::from(" "),
+                Span::from(title),
+            ];
+            if !entry.enclosures.is_empty() {
+                title_spans.push(Span::from(concat!(" ", icon!(media))));
+            }
+
             Row::new([
                 Cell::from(Span::from(published)),
-                Cell::from(Line::from(vec![
-                    Span::from(icon.symbol()).fg(icon.color().unwrap_or(cx.theme.default_icon_fg)),
-                    Span::from(" "),
-                    Span::from(title),
-                ])),
+                Cell::from(Line::from(title_spans)),
                 Cell::from(Span::from(feed_title)),
                 Cell::from(Line::from(vec![requirement, Span::from(" ")])),
             ])
         };
 
-        (header, constraints, self.entries.iter().map(row))
+        let group = |key: &'a str, count: usize| {
+            let (icon, count) = if self.collapsed.contains(key) {
+                (icon!(folder), format!(" ({count})"))
+            } else {
+                (icon!(folderopen), String::new())
+            };
+            Row::new([
+                Cell::default(),
+                Cell::from(Line::from(vec![
+                    Span::from(format!("{icon} ")),
+                    Span::from(key).bold(),
+                    Span::from(count),
+                ])),
+            ])
+        };
+
+        let entries = self.entries.iter_with_mark().collect::<Vec<_>>();
+        let rows = self.rows.iter().map(move |entry_row| match entry_row {
+            EntryRow::Group { key, count, .. } => group(key, *count),
+            EntryRow::Entry { index } => row(entries[*index]),
+        });
+
+        (header, constraints, rows)
     }
 
     fn render_detail(&self, area: Rect, buf: &mut Buffer, cx: &Context<'_>) {
@@ -209,7 +587,7 @@ impl Entries {
             title_area,
             url_area,
             published_area,
-            _,
+            media_area,
             summary_heading_area,
             summary_area,
         ] = vertical.areas(inner);
@@ -241,7 +619,19 @@ impl Entries {
         ])
         .render(published_area, buf);
 
-        let Some(summary) = entry.summary_text(inner.width.into()) else {
+        if let Some(enclosure) = entry.enclosures.first() {
+            let mut spans = vec![
+                Span::from(concat!(icon!(media), " Media")).bold(),
+                Span::from("     "),
+                Span::from(enclosure.url.as_str()),
+            ];
+            if let Some(duration) = enclosure.duration {
+                spans.push(Span::from(format!(" ({})", format_duration(duration))));
+            }
+            Line::from(spans).render(media_area, buf);
+        }
+
+        let Some(summary) = entry.summary_text.as_deref() else {
             return;
         };
 
@@ -260,3 +650,108 @@ impl Entries {
         Widget::render(paragraph, summary_area, buf);
     }
 }
+
+/// Format the duration as h:mm:ss
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake as _, Faker};
+
+    use super::*;
+
+    fn entry(feed_title: &str) -> types::Entry {
+        let mut entry: types::Entry = Faker.fake();
+        entry.feed_title = Some(feed_title.to_owned());
+        entry
+    }
+
+    fn selected_feed(entries: &Entries) -> Option<&str> {
+        entries
+            .selected_entry()
+            .and_then(|entry| entry.feed_title.as_deref())
+    }
+
+    #[test]
+    fn group_then_collapse() {
+        let mut entries = Entries::new();
+        entries.restore_entries(vec![entry("b"), entry("a"), entry("b")]);
+        assert_eq!(entries.rows.len(), 3);
+
+        entries.rotate_grouping();
+        assert_eq!(entries.view_options().grouping, EntryGrouping::Feed);
+        // a header, a, b header, b, b
+        assert_eq!(entries.rows.len(), 5);
+        assert_eq!(selected_feed(&entries), Some("a"));
+
+        entries.toggle_group();
+        assert_eq!(entries.rows.len(), 4);
+        assert_eq!(entries.selected_row, 0);
+        assert_eq!(selected_feed(&entries), None);
+
+        entries.move_selection(Direction::Down);
+        entries.move_selection(Direction::Down);
+        assert_eq!(selected_feed(&entries), Some("b"));
+
+        entries.toggle_all_groups();
+        assert_eq!(entries.rows.len(), 5);
+        assert_eq!(selected_feed(&entries), Some("b"));
+
+        entries.toggle_all_groups();
+        assert_eq!(entries.rows.len(), 2);
+        assert_eq!(selected_feed(&entries), None);
+    }
+
+    #[test]
+    fn mark_visible_entries() {
+        let mut entries = Entries::new();
+        entries.restore_entries(vec![entry("b"), entry("a"), entry("b")]);
+        entries.rotate_grouping();
+
+        // a header is selected
+        entries.move_first();
+        assert!(entries.marked_entries().is_empty());
+        entries.update_marks(Marking::Toggle);
+        assert!(entries.marked_entries().is_empty());
+
+        entries.move_selection(Direction::Down);
+        assert_eq!(entries.marked_entries().len(), 1);
+
+        // entries of the collapsed group are not marked
+        entries.move_last();
+        entries.toggle_group();
+        entries.update_marks(Marking::All);
+        let feeds = entries
+            .marked_entries()
+            .into_iter()
+            .filter_map(|entry| entry.feed_title.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(feeds, vec!["a"]);
+    }
+
+    #[test]
+    fn sort_by_feed() {
+        let mut entries = Entries::with_view_options(EntriesViewOptions {
+            sort: EntrySort::Feed,
+            grouping: EntryGrouping::None,
+        });
+        entries.restore_entries(vec![entry("b"), entry("c"), entry("a")]);
+        let feeds = entries
+            .entries
+            .iter()
+            .filter_map(|entry| entry.feed_title.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(feeds, vec!["a", "b", "c"]);
+
+        entries.rotate_sort();
+        assert_eq!(entries.view_options().sort, EntrySort::Requirement);
+    }
+}
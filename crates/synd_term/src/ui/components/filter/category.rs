@@ -8,7 +8,7 @@ use crate::{
     types::{self, github::Notification},
     ui::{
         self,
-        components::filter::{Composable, FilterResult, Filterable},
+        components::filter::{Composable, FilterResult, Filterable, SavedFilter},
     },
 };
 
@@ -124,6 +124,39 @@ impl CategoriesState {
         self.categories.clear();
         self.state.clear();
     }
+
+    /// Activate only the categories the saved filter shows
+    pub(super) fn apply_saved_filter(&mut self, filter: &SavedFilter) {
+        for (category, state) in &mut self.state {
+            state.state = if filter.is_category_active(category) {
+                FilterCategoryState::Active
+            } else {
+                FilterCategoryState::Inactive
+            };
+        }
+    }
+
+    pub(super) fn matches_saved_filter(&self, filter: &SavedFilter) -> bool {
+        self.state
+            .iter()
+            .all(|(category, state)| state.state.is_active() == filter.is_category_active(category))
+    }
+
+    /// Active categories, or empty if all categories are active
+    pub(super) fn active_categories(&self) -> Vec<Category<'static>> {
+        if self.state.values().all(|state| state.state.is_active()) {
+            return Vec::new();
+        }
+        self.categories
+            .iter()
+            .filter(|category| {
+                self.state
+                    .get(*category)
+                    .is_some_and(|s| s.state.is_active())
+            })
+            .cloned()
+            .collect()
+    }
 }
 
 #[derive(Default, Clone, Debug)]
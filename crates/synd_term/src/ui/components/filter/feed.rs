@@ -3,7 +3,7 @@ use synd_feed::types::Requirement;
 use crate::{
     types,
     ui::components::filter::{
-        CategoryFilterer, ComposedFilterer, FilterResult, Filterable, MatcherFilterer,
+        CategoryFilterer, ComposedFilterer, FilterResult, Filterable, QueryFilterer,
         category::CategoriesState, composed::Composable,
     },
 };
@@ -64,4 +64,4 @@ impl Filterable<types::Feed> for RequirementFilterer {
 }
 
 pub(crate) type FeedFilterer =
-    ComposedFilterer<ComposedFilterer<RequirementFilterer, CategoryFilterer>, MatcherFilterer>;
+    ComposedFilterer<ComposedFilterer<RequirementFilterer, CategoryFilterer>, QueryFilterer>;
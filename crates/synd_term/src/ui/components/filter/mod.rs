@@ -1,5 +1,6 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
+use chrono::Utc;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use itertools::Itertools;
 use ratatui::{
@@ -12,7 +13,7 @@ use ratatui::{
 use synd_feed::types::{Category, Requirement};
 
 use crate::{
-    application::{Direction, Populate},
+    application::{Direction, InputParser, ParseQueryError, Populate},
     client::github::{FetchNotificationInclude, FetchNotificationParticipating},
     command::Command,
     config::Categories,
@@ -52,6 +53,12 @@ pub(crate) use composed::{Composable, ComposedFilterer};
 mod matcher;
 pub(crate) use matcher::MatcherFilterer;
 
+mod query;
+pub(crate) use query::{Condition, Query, QueryFilterer, Since, Term};
+
+mod saved;
+pub(crate) use saved::{SavedFilter, SavedFilters};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum FilterLane {
     Feed,
@@ -67,12 +74,12 @@ impl From<Tab> for FilterLane {
     }
 }
 
-pub(crate) type CategoryAndMatcherFilterer = ComposedFilterer<CategoryFilterer, MatcherFilterer>;
+pub(crate) type CategoryAndQueryFilterer = ComposedFilterer<CategoryFilterer, QueryFilterer>;
 
 #[derive(Clone, Debug)]
 pub(crate) enum Filterer {
     Feed(FeedFilterer),
-    GhNotification(CategoryAndMatcherFilterer),
+    GhNotification(CategoryAndQueryFilterer),
 }
 
 pub(crate) trait Filterable<T> {
@@ -92,7 +99,10 @@ pub(crate) struct Filter {
     gh_notification: GhNotificationHandler,
 
     prompt: Rc<RefCell<Prompt>>,
+    /// Parsed input of the prompt
+    query: Result<Query, ParseQueryError>,
     matcher: Matcher,
+    saved: SavedFilters,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -107,12 +117,25 @@ impl Filter {
         Self {
             state: State::Normal,
             prompt: Rc::new(RefCell::new(Prompt::new())),
+            query: Ok(Query::default()),
             feed: FeedHandler::new(),
             gh_notification: GhNotificationHandler::new(),
             matcher: Matcher::new(),
+            saved: SavedFilters::default(),
         }
     }
 
+    pub(crate) fn with_saved_filters(saved: SavedFilters) -> Self {
+        Self {
+            saved,
+            ..Self::new()
+        }
+    }
+
+    pub(crate) fn saved_filters(&self) -> &SavedFilters {
+        &self.saved
+    }
+
     #[must_use]
     pub fn activate_search_filtering(&mut self) -> Rc<RefCell<Prompt>> {
         self.state = State::SearchFiltering;
@@ -134,17 +157,17 @@ impl Filter {
                     lane,
                     category: category.clone(),
                 };
-                map.insert(key, KeyTrie::Command(command));
+                map.insert(key, KeyTrie::command(command));
                 map
             },
         );
         map.insert(
             KeyEvent::new(KeyCode::Char('+'), KeyModifiers::NONE),
-            KeyTrie::Command(Command::ActivateAllFilterCategories { lane }),
+            KeyTrie::command(Command::ActivateAllFilterCategories { lane }),
         );
         map.insert(
             KeyEvent::new(KeyCode::Char('-'), KeyModifiers::NONE),
-            KeyTrie::Command(Command::DeactivateAllFilterCategories { lane }),
+            KeyTrie::command(Command::DeactivateAllFilterCategories { lane }),
         );
         Keymap::from_map(crate::keymap::KeymapId::CategoryFiltering, map)
     }
@@ -227,6 +250,57 @@ impl Filter {
         self.filterer(lane)
     }
 
+    /// Switch to the adjacent saved filter and apply it to all the lanes
+    #[must_use]
+    pub(crate) fn rotate_saved_filter(
+        &mut self,
+        direction: Direction,
+        categories: &Categories,
+    ) -> Vec<Filterer> {
+        let Some(filter) = self.saved.rotate(direction).cloned() else {
+            return Vec::new();
+        };
+        self.feed.requirement = filter.requirement;
+        self.feed.categories_state.apply_saved_filter(&filter);
+        self.gh_notification
+            .categories_state
+            .apply_saved_filter(&filter);
+        self.prompt.borrow_mut().set_line(filter.search);
+        self.update_query(categories);
+
+        vec![
+            self.filterer(FilterLane::Feed),
+            self.filterer(FilterLane::GhNotification),
+        ]
+    }
+
+    /// Save the current requirement, categories of the lane and search needle as `name`
+    pub(crate) fn save_current(&mut self, name: String, lane: FilterLane) {
+        let filter = SavedFilter {
+            name,
+            requirement: self.feed.requirement,
+            categories: self.categories_state_from_lane(lane).active_categories(),
+            search: self.prompt.borrow().line().to_owned(),
+        };
+        self.saved.upsert(filter);
+    }
+
+    fn matches_saved_filter(&self, filter: &SavedFilter, lane: FilterLane) -> bool {
+        if self.prompt.borrow().line() != filter.search {
+            return false;
+        }
+        match lane {
+            FilterLane::Feed => {
+                self.feed.requirement == filter.requirement
+                    && self.feed.categories_state.matches_saved_filter(filter)
+            }
+            FilterLane::GhNotification => self
+                .gh_notification
+                .categories_state
+                .matches_saved_filter(filter),
+        }
+    }
+
     #[must_use]
     pub(crate) fn filterer(&self, lane: FilterLane) -> Filterer {
         match lane {
@@ -239,13 +313,13 @@ impl Filter {
     fn feed_filterer(&self) -> FeedFilterer {
         RequirementFilterer::new(self.feed.requirement)
             .and_then(Self::category_filterer(&self.feed.categories_state))
-            .and_then(self.matcher_filterer())
+            .and_then(self.query_filterer())
     }
 
     #[must_use]
-    fn gh_notification_filterer(&self) -> CategoryAndMatcherFilterer {
+    fn gh_notification_filterer(&self) -> CategoryAndQueryFilterer {
         Self::category_filterer(&self.gh_notification.categories_state)
-            .and_then(self.matcher_filterer())
+            .and_then(self.query_filterer())
     }
 
     #[must_use]
@@ -259,11 +333,18 @@ impl Filter {
         )
     }
 
+    /// Parse the input of the prompt, which should be called whenever the input changes
+    pub(crate) fn update_query(&mut self, categories: &Categories) {
+        self.query = InputParser::new(self.prompt.borrow().line()).parse_query(categories);
+    }
+
     #[must_use]
-    fn matcher_filterer(&self) -> MatcherFilterer {
-        let mut matcher = self.matcher.clone();
-        matcher.update_needle(self.prompt.borrow().line());
-        MatcherFilterer::new(matcher)
+    fn query_filterer(&self) -> QueryFilterer {
+        match &self.query {
+            Ok(query) => QueryFilterer::new(query, &self.matcher, Utc::now()),
+            // Search the input as it is until the query becomes valid
+            Err(_) => QueryFilterer::keyword(self.prompt.borrow().line(), &self.matcher),
+        }
     }
 
     pub fn update_categories(
@@ -310,14 +391,43 @@ impl Filter {
                 bottom: 0,
             })
             .inner(area);
-        let vertical = Layout::vertical([Constraint::Length(2), Constraint::Length(1)]);
-        let [filter_area, search_area] = vertical.areas(area);
+        let vertical = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]);
+        let [filter_area, saved_area, search_area] = vertical.areas(area);
 
         let lane = cx.ui.tab.into();
         self.render_filter(filter_area, buf, cx, lane);
+        self.render_saved_filters(saved_area, buf, lane);
         self.render_search(search_area, buf, cx.ui, lane);
     }
 
+    fn render_saved_filters(&self, area: Rect, buf: &mut Buffer, lane: FilterLane) {
+        if self.saved.is_empty() {
+            return;
+        }
+        let padding = match lane {
+            FilterLane::Feed => "    ",
+            FilterLane::GhNotification => "  ",
+        };
+        let mut spans = vec![
+            Span::from(concat!(icon!(bookmark), " Saved")).dim(),
+            Span::from(padding),
+        ];
+        for filter in self.saved.iter() {
+            let mut name = Span::from(filter.name.as_str());
+            if self.matches_saved_filter(filter, lane) {
+                name = name.underlined();
+            } else {
+                name = name.dim();
+            }
+            spans.extend([name, Span::from("  ")]);
+        }
+        Line::from(spans).render(area, buf);
+    }
+
     #[allow(unstable_name_collisions)]
     fn render_filter(
         &self,
@@ -451,7 +561,7 @@ impl Filter {
         Line::from(spans).render(categories_area, buf);
     }
 
-    fn render_search(&self, area: Rect, buf: &mut Buffer, _cx: &Context<'_>, lane: FilterLane) {
+    fn render_search(&self, area: Rect, buf: &mut Buffer, cx: &Context<'_>, lane: FilterLane) {
         let mut spans = vec![];
         let mut label = Span::from(concat!(icon!(search), " Search"));
         if self.state != State::SearchFiltering {
@@ -480,7 +590,20 @@ impl Filter {
         } else {
             RenderCursor::Disable
         };
-        self.prompt.borrow().render(prompt_area, buf, render_cursor);
+        let prompt = self.prompt.borrow();
+        prompt.render(prompt_area, buf, render_cursor);
+
+        if let Err(err) = &self.query {
+            // Leave a space for the cursor
+            #[allow(clippy::cast_possible_truncation)]
+            let margin = Line::from(prompt.line()).width() as u16 + 2;
+            let error_area = Rect {
+                x: prompt_area.x.saturating_add(margin),
+                width: prompt_area.width.saturating_sub(margin),
+                ..prompt_area
+            };
+            Span::styled(err.to_string(), cx.theme.error.message).render(error_area, buf);
+        }
     }
 }
 
@@ -507,4 +630,78 @@ mod tests {
 
         assert_eq!(filter.filter(&feed), FilterResult::Use);
     }
+
+    #[test]
+    fn rotate_saved_filter_apply_all_lanes() {
+        let rust = Category::new("rust").unwrap();
+        let go = Category::new("go").unwrap();
+        let saved = SavedFilter {
+            name: "rust-must".into(),
+            requirement: Requirement::Must,
+            categories: vec![rust.clone()],
+            search: "async".into(),
+        };
+        let mut filter =
+            Filter::with_saved_filters(SavedFilters::new(vec![saved.clone()], Vec::new()));
+        let config = Categories::default_toml();
+        filter
+            .feed
+            .categories_state
+            .update(&config, Populate::Replace, [rust.clone(), go.clone()]);
+        filter.update_gh_notification_categories(
+            &config,
+            Populate::Replace,
+            [rust.clone(), go.clone()],
+        );
+
+        assert_eq!(
+            filter.rotate_saved_filter(Direction::Right, &config).len(),
+            2
+        );
+        assert_eq!(filter.feed.requirement, Requirement::Must);
+        assert_eq!(filter.prompt.borrow().line(), "async");
+        for lane in [FilterLane::Feed, FilterLane::GhNotification] {
+            assert!(filter.matches_saved_filter(&saved, lane));
+            assert!(
+                !filter.categories_state_from_lane(lane).state[&go]
+                    .state
+                    .is_active()
+            );
+        }
+
+        filter.save_current("rust-copy".into(), FilterLane::Feed);
+        assert_eq!(filter.saved_filters().saved()[0].categories, vec![rust]);
+    }
+
+    #[test]
+    fn save_categories_of_the_lane() {
+        let rust = Category::new("rust").unwrap();
+        let go = Category::new("go").unwrap();
+        let mut filter = Filter::new();
+        let config = Categories::default_toml();
+        filter
+            .feed
+            .categories_state
+            .update(&config, Populate::Replace, [rust.clone(), go.clone()]);
+        filter.update_gh_notification_categories(
+            &config,
+            Populate::Replace,
+            [rust.clone(), go.clone()],
+        );
+        filter
+            .categories_state_from_lane_mut(FilterLane::GhNotification)
+            .state
+            .get_mut(&go)
+            .unwrap()
+            .state = FilterCategoryState::Inactive;
+
+        filter.save_current("gh".into(), FilterLane::GhNotification);
+        filter.save_current("feed".into(), FilterLane::Feed);
+
+        let saved = filter.saved_filters().saved();
+        assert_eq!(saved[0].categories, vec![rust]);
+        assert!(saved[1].categories.is_empty());
+        assert!(filter.matches_saved_filter(&saved[0], FilterLane::GhNotification));
+        assert!(!filter.matches_saved_filter(&saved[0], FilterLane::Feed));
+    }
 }
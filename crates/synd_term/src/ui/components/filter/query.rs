@@ -0,0 +1,311 @@
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use synd_feed::types::{Category, Requirement};
+
+use crate::{
+    matcher::Matcher,
+    types::{self, Time, github::Notification},
+    ui::components::filter::{FilterResult, Filterable, MatcherFilterer},
+};
+
+/// Search query such as `feed:"this week" cat:rust req:must after:7d -title:release`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Query {
+    pub(crate) terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Term {
+    pub(crate) negated: bool,
+    pub(crate) condition: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Condition {
+    /// Matched against the title and the feed or repository
+    Keyword(String),
+    Title(String),
+    /// Feed of the entry, or repository of the notification
+    Feed(String),
+    Category(Category<'static>),
+    /// Satisfied by the requirement or higher
+    Requirement(Requirement),
+    After(Since),
+    Before(Since),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Since {
+    Ago(Duration),
+    Date(NaiveDate),
+}
+
+impl Since {
+    /// Whether the time can be resolved, as too long durations overflow the date
+    pub(crate) fn is_in_range(self, now: DateTime<Utc>) -> bool {
+        match self {
+            Since::Ago(duration) => chrono::Duration::from_std(duration)
+                .ok()
+                .and_then(|duration| now.checked_sub_signed(duration))
+                .is_some(),
+            Since::Date(_) => true,
+        }
+    }
+
+    fn resolve(self, now: DateTime<Utc>) -> Time {
+        match self {
+            // Saturate instead of panicking in case the time passed since parsing
+            Since::Ago(duration) => chrono::Duration::from_std(duration)
+                .ok()
+                .and_then(|duration| now.checked_sub_signed(duration))
+                .unwrap_or(DateTime::<Utc>::MIN_UTC),
+            Since::Date(date) => date.and_time(chrono::NaiveTime::MIN).and_utc(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Predicate {
+    Keyword(MatcherFilterer),
+    Title(Matcher),
+    Feed(Matcher),
+    Category(Category<'static>),
+    Requirement(Requirement),
+    After(Time),
+    Before(Time),
+}
+
+/// `Query` compiled against the current time
+#[derive(Default, Clone, Debug)]
+pub(crate) struct QueryFilterer {
+    keywords: MatcherFilterer,
+    predicates: Vec<(bool, Predicate)>,
+}
+
+impl QueryFilterer {
+    pub(crate) fn new(query: &Query, matcher: &Matcher, now: DateTime<Utc>) -> Self {
+        let needle = |needle: &str| {
+            let mut matcher = matcher.clone();
+            matcher.update_needle(needle);
+            matcher
+        };
+        // Keywords are matched as a single pattern like the plain search
+        let keywords = query
+            .terms
+            .iter()
+            .filter_map(|term| match &term.condition {
+                Condition::Keyword(keyword) if !term.negated => Some(keyword.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let predicates = query
+            .terms
+            .iter()
+            .filter_map(|Term { negated, condition }| {
+                let predicate = match condition {
+                    Condition::Keyword(_) if !negated => return None,
+                    Condition::Keyword(keyword) => {
+                        Predicate::Keyword(MatcherFilterer::new(needle(keyword)))
+                    }
+                    Condition::Title(title) => Predicate::Title(needle(title)),
+                    Condition::Feed(feed) => Predicate::Feed(needle(feed)),
+                    Condition::Category(category) => Predicate::Category(category.clone()),
+                    Condition::Requirement(requirement) => Predicate::Requirement(*requirement),
+                    Condition::After(since) => Predicate::After(since.resolve(now)),
+                    Condition::Before(since) => Predicate::Before(since.resolve(now)),
+                };
+                Some((*negated, predicate))
+            })
+            .collect();
+
+        Self {
+            keywords: MatcherFilterer::new(needle(&keywords)),
+            predicates,
+        }
+    }
+
+    /// Match the whole input as keywords
+    pub(crate) fn keyword(input: &str, matcher: &Matcher) -> Self {
+        let mut matcher = matcher.clone();
+        matcher.update_needle(input);
+        Self {
+            keywords: MatcherFilterer::new(matcher),
+            predicates: Vec::new(),
+        }
+    }
+}
+
+/// Fields of the item which the query conditions refer to
+trait QueryFields {
+    fn title(&self) -> &str;
+    fn feeds(&self) -> impl Iterator<Item = &str>;
+    fn has_category(&self, category: &Category<'_>) -> bool;
+    fn requirement(&self) -> Option<Requirement>;
+    fn time(&self) -> Option<Time>;
+}
+
+impl QueryFields for types::Entry {
+    fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or_default()
+    }
+    fn feeds(&self) -> impl Iterator<Item = &str> {
+        self.feed_title.as_deref().into_iter()
+    }
+    fn has_category(&self, category: &Category<'_>) -> bool {
+        self.category() == category
+    }
+    fn requirement(&self) -> Option<Requirement> {
+        Some(types::Entry::requirement(self))
+    }
+    fn time(&self) -> Option<Time> {
+        self.published.or(self.updated)
+    }
+}
+
+impl QueryFields for types::Feed {
+    fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or_default()
+    }
+    fn feeds(&self) -> impl Iterator<Item = &str> {
+        self.title
+            .as_deref()
+            .into_iter()
+            .chain(self.website_url.as_deref())
+    }
+    fn has_category(&self, category: &Category<'_>) -> bool {
+        self.category() == category
+    }
+    fn requirement(&self) -> Option<Requirement> {
+        Some(types::Feed::requirement(self))
+    }
+    fn time(&self) -> Option<Time> {
+        self.updated
+    }
+}
+
+impl QueryFields for Notification {
+    fn title(&self) -> &str {
+        Notification::title(self)
+    }
+    fn feeds(&self) -> impl Iterator<Item = &str> {
+        [
+            self.repository.owner.as_str(),
+            self.repository.name.as_str(),
+        ]
+        .into_iter()
+    }
+    fn has_category(&self, category: &Category<'_>) -> bool {
+        self.categories().any(|c| c == category)
+    }
+    // Notifications have no requirement, so the condition is ignored
+    fn requirement(&self) -> Option<Requirement> {
+        None
+    }
+    fn time(&self) -> Option<Time> {
+        Some(self.updated_at)
+    }
+}
+
+impl Predicate {
+    /// `None` if the condition does not apply to the item
+    fn test<T>(&self, item: &T) -> Option<bool>
+    where
+        T: QueryFields,
+        MatcherFilterer: Filterable<T>,
+    {
+        match self {
+            Predicate::Keyword(keyword) => Some(keyword.filter(item) == FilterResult::Use),
+            Predicate::Title(matcher) => Some(matcher.r#match(item.title())),
+            Predicate::Feed(matcher) => Some(item.feeds().any(|feed| matcher.r#match(feed))),
+            Predicate::Category(category) => Some(item.has_category(category)),
+            Predicate::Requirement(requirement) => {
+                item.requirement().map(|r| r.is_satisfied(*requirement))
+            }
+            Predicate::After(time) => item.time().map(|t| t >= *time),
+            Predicate::Before(time) => item.time().map(|t| t < *time),
+        }
+    }
+}
+
+impl<T> Filterable<T> for QueryFilterer
+where
+    T: QueryFields,
+    MatcherFilterer: Filterable<T>,
+{
+    fn filter(&self, item: &T) -> FilterResult {
+        if self.keywords.filter(item) == FilterResult::Discard {
+            return FilterResult::Discard;
+        }
+        let satisfied = self.predicates.iter().all(|(negated, predicate)| {
+            predicate
+                .test(item)
+                .is_none_or(|matched| matched != *negated)
+        });
+        if satisfied {
+            FilterResult::Use
+        } else {
+            FilterResult::Discard
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake, Faker};
+
+    use super::*;
+
+    fn term(negated: bool, condition: Condition) -> Term {
+        Term { negated, condition }
+    }
+
+    #[test]
+    fn filter_entry_by_query() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut entry: types::Entry = Faker.fake();
+        entry.title = Some("Rust 1.80 release".into());
+        entry.feed_title = Some("This Week in Rust".into());
+        entry.published = Some(now - chrono::Duration::days(3));
+
+        let query =
+            |terms| QueryFilterer::new(&Query { terms }, &Matcher::new(), now).filter(&entry);
+
+        assert_eq!(
+            query(vec![
+                term(false, Condition::Keyword("rust".into())),
+                term(false, Condition::Feed("this week".into())),
+                term(
+                    false,
+                    Condition::After(Since::Ago(Duration::from_secs(7 * 86400)))
+                ),
+            ]),
+            FilterResult::Use
+        );
+        assert_eq!(
+            query(vec![term(true, Condition::Title("release".into()))]),
+            FilterResult::Discard
+        );
+        assert_eq!(
+            query(vec![term(
+                false,
+                Condition::Before(Since::Ago(Duration::from_secs(7 * 86400)))
+            )]),
+            FilterResult::Discard
+        );
+        assert_eq!(
+            query(vec![term(true, Condition::Keyword("go".into()))]),
+            FilterResult::Use
+        );
+    }
+
+    #[test]
+    fn resolve_out_of_range_since() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let since = Since::Ago(Duration::from_secs(u64::MAX));
+
+        assert!(!since.is_in_range(now));
+        assert_eq!(since.resolve(now), DateTime::<Utc>::MIN_UTC);
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use synd_feed::types::{Category, Requirement};
+
+use crate::application::{Direction, IndexOutOfRange};
+
+/// Named combination of the requirement, categories and search needle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub(crate) name: String,
+    pub(crate) requirement: Requirement,
+    /// Categories to show. empty means all categories
+    pub(crate) categories: Vec<Category<'static>>,
+    pub(crate) search: String,
+}
+
+impl SavedFilter {
+    pub(super) fn is_category_active(&self, category: &Category<'_>) -> bool {
+        self.categories.is_empty() || self.categories.iter().any(|c| c == category)
+    }
+}
+
+/// Filters defined in the config file followed by the ones saved from the current state
+#[derive(Debug, Default)]
+pub(crate) struct SavedFilters {
+    configured: Vec<SavedFilter>,
+    saved: Vec<SavedFilter>,
+    current: Option<usize>,
+}
+
+impl SavedFilters {
+    pub(crate) fn new(configured: Vec<SavedFilter>, saved: Vec<SavedFilter>) -> Self {
+        Self {
+            configured,
+            saved,
+            current: None,
+        }
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &SavedFilter> {
+        // Saved filters replace the configured ones with the same name
+        self.configured
+            .iter()
+            .filter(|filter| !self.saved.iter().any(|saved| saved.name == filter.name))
+            .chain(self.saved.iter())
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.configured.is_empty() && self.saved.is_empty()
+    }
+
+    /// Filters saved from the current state to be persisted
+    pub(crate) fn saved(&self) -> &[SavedFilter] {
+        self.saved.as_slice()
+    }
+
+    pub(super) fn rotate(&mut self, direction: Direction) -> Option<&SavedFilter> {
+        let len = self.iter().count();
+        if len == 0 {
+            return None;
+        }
+        let index = match (self.current, direction) {
+            (None, Direction::Up | Direction::Left) => len - 1,
+            (None, Direction::Down | Direction::Right) => 0,
+            (Some(current), direction) => {
+                direction.apply(current.min(len - 1), len, IndexOutOfRange::Wrapping)
+            }
+        };
+        self.current = Some(index);
+        self.iter().nth(index)
+    }
+
+    pub(super) fn upsert(&mut self, filter: SavedFilter) {
+        match self
+            .saved
+            .iter_mut()
+            .find(|saved| saved.name == filter.name)
+        {
+            Some(saved) => *saved = filter,
+            None => self.saved.push(filter),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(name: &str) -> SavedFilter {
+        SavedFilter {
+            name: name.to_owned(),
+            requirement: Requirement::May,
+            categories: Vec::new(),
+            search: String::new(),
+        }
+    }
+
+    #[test]
+    fn rotate_wrapping() {
+        let mut filters = SavedFilters::new(vec![filter("a"), filter("b")], vec![filter("c")]);
+        let mut rotate = |direction| filters.rotate(direction).unwrap().name.clone();
+
+        assert_eq!(rotate(Direction::Right), "a");
+        assert_eq!(rotate(Direction::Right), "b");
+        assert_eq!(rotate(Direction::Right), "c");
+        assert_eq!(rotate(Direction::Right), "a");
+        assert_eq!(rotate(Direction::Left), "c");
+    }
+
+    #[test]
+    fn saved_replace_configured() {
+        let mut filters = SavedFilters::new(vec![filter("a"), filter("b")], Vec::new());
+        let mut b = filter("b");
+        b.requirement = Requirement::Must;
+        filters.upsert(b.clone());
+        filters.upsert(b.clone());
+
+        assert_eq!(
+            filters.iter().cloned().collect::<Vec<_>>(),
+            vec![filter("a"), b.clone()]
+        );
+        assert_eq!(filters.saved(), &[b]);
+        assert!(SavedFilters::default().rotate(Direction::Right).is_none());
+    }
+}
@@ -28,8 +28,8 @@ use crate::{
     ui::{
         Context,
         components::{
-            collections::FilterableVec,
-            filter::{CategoryFilterer, ComposedFilterer, MatcherFilterer},
+            collections::{FilterableVec, Marking},
+            filter::{CategoryAndQueryFilterer, ComposedFilterer},
         },
         extension::RectExt,
         icon,
@@ -105,13 +105,11 @@ pub(crate) struct GhNotificationFilterUpdater {
     pub(crate) toggle_reason: Option<Reason>,
 }
 
-type CategoryAndMatcherFilterer = ComposedFilterer<CategoryFilterer, MatcherFilterer>;
-
 #[allow(clippy::struct_field_names)]
 pub(crate) struct GhNotifications {
     max_repository_name: usize,
     notifications:
-        FilterableVec<Notification, ComposedFilterer<CategoryAndMatcherFilterer, OptionFilterer>>,
+        FilterableVec<Notification, ComposedFilterer<CategoryAndQueryFilterer, OptionFilterer>>,
 
     #[allow(clippy::zero_sized_map_values)]
     status: HashMap<NotificationId, NotificationStatus>,
@@ -127,7 +125,7 @@ impl GhNotifications {
 
     pub(crate) fn with_filter_options(filter_options: GhNotificationFilterOptions) -> Self {
         let filterer =
-            CategoryAndMatcherFilterer::default().and_then(OptionFilterer::new(filter_options));
+            CategoryAndQueryFilterer::default().and_then(OptionFilterer::new(filter_options));
 
         Self {
             notifications: FilterableVec::from_filter(filterer),
@@ -149,7 +147,7 @@ impl GhNotifications {
         self.filter_popup.update_options(updater, &current);
     }
 
-    pub(crate) fn update_filterer(&mut self, filterer: CategoryAndMatcherFilterer) {
+    pub(crate) fn update_filterer(&mut self, filterer: CategoryAndQueryFilterer) {
         self.notifications
             .with_filter(|composed| composed.update_left(filterer));
     }
@@ -260,10 +258,13 @@ impl GhNotifications {
         })
     }
 
-    pub(crate) fn marking_as_done(&mut self) -> Option<NotificationId> {
-        let id = self.selected_notification()?.id;
-        self.status.insert(id, NotificationStatus::MarkingAsDone);
-        Some(id)
+    /// Mark the marked notifications, or the selected one, as done
+    pub(crate) fn marking_as_done(&mut self) -> Vec<NotificationId> {
+        let ids: Vec<NotificationId> = self.marked_notifications().iter().map(|n| n.id).collect();
+        for &id in &ids {
+            self.status.insert(id, NotificationStatus::MarkingAsDone);
+        }
+        ids
     }
 
     pub(crate) fn marking_as_done_all(&mut self) -> Vec<NotificationId> {
@@ -320,6 +321,15 @@ impl GhNotifications {
     pub(crate) fn selected_notification(&self) -> Option<&Notification> {
         self.notifications.selected()
     }
+
+    /// Notifications the bulk operations act on
+    pub(crate) fn marked_notifications(&self) -> Vec<&Notification> {
+        self.notifications.marked_or_selected()
+    }
+
+    pub(crate) fn update_marks(&mut self, marking: Marking) {
+        self.notifications.update_marks(marking);
+    }
 }
 
 impl GhNotifications {
@@ -386,9 +396,13 @@ impl GhNotifications {
                 )
             }
         };
+        let marks = match self.notifications.marked().len() {
+            0 => String::new(),
+            k => format!(" {} {k}", icon!(check)),
+        };
         let header = Row::new([
             Cell::from("Updated"),
-            Cell::from(format!("Title {n}/{m}")),
+            Cell::from(format!("Title {n}/{m}{marks}")),
             Cell::from("Repository"),
             Cell::from("Reason"),
         ]);
@@ -400,7 +414,7 @@ impl GhNotifications {
             Constraint::Length(10),
         ];
 
-        let row = |n: &'a Notification| {
+        let row = |(n, marked): (&'a Notification, bool)| {
             let updated_at = HumanTime::from(n.updated_at.signed_duration_since(cx.now))
                 .to_text_en(Accuracy::Rough, Tense::Past);
             let updated_at = short_human_time(&updated_at);
@@ -421,14 +435,28 @@ impl GhNotifications {
             Row::new([
                 Cell::from(Span::from(updated_at).add_modifier(modifier)),
                 Cell::from(
-                    Line::from(vec![subject_icon, Span::from(" "), Span::from(subject)])
-                        .add_modifier(modifier),
+                    Line::from(vec![
+                        Span::from(if marked {
+                            concat!(icon!(check), " ")
+                        } else {
+                            ""
+                        })
+                        .bold(),
+                        subject_icon,
+                        Span::from(" "),
+                        Span::from(subject),
+                    ])
+                    .add_modifier(modifier),
                 ),
                 Cell::from(Span::from(repo).add_modifier(modifier)),
                 Cell::from(Span::from(reason).add_modifier(modifier)),
             ])
         };
-        (header, constraints, self.notifications.iter().map(row))
+        (
+            header,
+            constraints,
+            self.notifications.iter_with_mark().map(row),
+        )
     }
 
     #[allow(clippy::too_many_lines)]
@@ -0,0 +1,131 @@
+use ratatui::{
+    prelude::{Alignment, Buffer, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    application::Direction,
+    keymap::KeymapHelp,
+    ui::{Context, extension::RectExt, widgets::scrollbar::Scrollbar},
+};
+
+/// Cheat sheet of the bindings available when it was opened
+pub(crate) struct Help {
+    keymaps: Option<Vec<KeymapHelp>>,
+    scroll: usize,
+}
+
+impl Help {
+    pub(crate) fn new() -> Self {
+        Self {
+            keymaps: None,
+            scroll: 0,
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.keymaps.is_some()
+    }
+
+    pub(crate) fn open(&mut self, keymaps: Vec<KeymapHelp>) {
+        self.keymaps = Some(keymaps);
+        self.scroll = 0;
+    }
+
+    pub(crate) fn close(&mut self) {
+        self.keymaps = None;
+    }
+
+    pub(crate) fn scroll(&mut self, direction: Direction) {
+        let max = self.lines().len().saturating_sub(1);
+        self.scroll = match direction {
+            Direction::Up | Direction::Left => self.scroll.saturating_sub(1),
+            Direction::Down | Direction::Right => (self.scroll + 1).min(max),
+        };
+    }
+
+    /// Group title followed by its bindings, separated by an empty line
+    fn lines(&self) -> Vec<HelpLine<'_>> {
+        let Some(keymaps) = self.keymaps.as_ref() else {
+            return Vec::new();
+        };
+        let mut lines = Vec::new();
+        for (i, keymap) in keymaps.iter().enumerate() {
+            if i > 0 {
+                lines.push(HelpLine::Blank);
+            }
+            lines.push(HelpLine::Title(keymap.title));
+            lines.extend(
+                keymap
+                    .bindings
+                    .iter()
+                    .map(|(keys, description)| HelpLine::Binding { keys, description }),
+            );
+        }
+        lines
+    }
+}
+
+enum HelpLine<'a> {
+    Title(&'static str),
+    Binding { keys: &'a str, description: &'a str },
+    Blank,
+}
+
+impl Help {
+    pub(crate) fn render(&self, area: Rect, buf: &mut Buffer, cx: &Context<'_>) {
+        let area = {
+            let area = area.centered(60, 80);
+            area.reset(buf);
+            area
+        };
+
+        let block = Block::new()
+            .title_top("Key bindings")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::new().add_modifier(Modifier::BOLD))
+            .padding(Padding::horizontal(1))
+            .borders(Borders::ALL)
+            .style(cx.theme.base);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [content_area, scrollbar_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+
+        let lines = self.lines();
+        let keys_width = lines
+            .iter()
+            .filter_map(|line| match line {
+                HelpLine::Binding { keys, .. } => Some(keys.chars().count()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+        let content = lines
+            .iter()
+            .skip(self.scroll)
+            .take(usize::from(content_area.height))
+            .map(|line| match line {
+                HelpLine::Title(title) => Line::from(Span::styled(
+                    *title,
+                    Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                )),
+                HelpLine::Binding { keys, description } => Line::from(vec![
+                    Span::styled(format!("  {keys:<keys_width$}  "), cx.theme.prompt.key),
+                    Span::from(*description),
+                ]),
+                HelpLine::Blank => Line::default(),
+            })
+            .collect::<Vec<_>>();
+        Paragraph::new(content).render(content_area, buf);
+
+        Scrollbar {
+            content_length: lines.len(),
+            position: self.scroll,
+        }
+        .render(scrollbar_area, buf, cx);
+    }
+}
@@ -2,22 +2,25 @@ use crate::{
     application::Features,
     auth::AuthenticationProvider,
     ui::components::{
-        authentication::Authentication, entries::Entries, filter::Filter,
-        gh_notifications::GhNotifications, status::StatusLine, subscription::Subscription,
-        tabs::Tabs,
+        authentication::Authentication, command_palette::CommandPalette, entries::Entries,
+        filter::Filter, gh_notifications::GhNotifications, help::Help, reader::Reader,
+        status::StatusLine, subscription::Subscription, tabs::Tabs,
     },
 };
 
 pub(crate) mod authentication;
+pub(crate) mod command_palette;
 pub(crate) mod entries;
 pub(crate) mod filter;
 pub(crate) mod gh_notifications;
+pub(crate) mod help;
+pub(crate) mod reader;
 pub(crate) mod root;
 pub(crate) mod status;
 pub(crate) mod subscription;
 pub(crate) mod tabs;
 
-mod collections;
+pub(crate) mod collections;
 
 pub(crate) struct Components {
     pub tabs: Tabs,
@@ -26,11 +29,14 @@ pub(crate) struct Components {
     pub subscription: Subscription,
     pub entries: Entries,
     pub gh_notifications: GhNotifications,
+    pub reader: Reader,
+    pub command_palette: CommandPalette,
+    pub help: Help,
     pub auth: Authentication,
 }
 
 impl Components {
-    pub fn new(features: &'_ Features) -> Self {
+    pub fn new(features: &'_ Features, providers: Vec<AuthenticationProvider>) -> Self {
         Self {
             tabs: Tabs::new(features),
             filter: Filter::new(),
@@ -38,10 +44,10 @@ impl Components {
             subscription: Subscription::new(),
             entries: Entries::new(),
             gh_notifications: GhNotifications::new(),
-            auth: Authentication::new(vec![
-                AuthenticationProvider::Github,
-                AuthenticationProvider::Google,
-            ]),
+            reader: Reader::new(),
+            command_palette: CommandPalette::new(),
+            help: Help::new(),
+            auth: Authentication::new(providers),
         }
     }
 }
@@ -0,0 +1,473 @@
+use html2text::render::RichAnnotation;
+use ratatui::{
+    prelude::{Buffer, Constraint, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Padding, Paragraph, Widget},
+};
+use url::Url;
+
+use crate::{
+    application::Direction,
+    client::synd_api::FeedUrl,
+    types,
+    ui::{self, Context, icon, widgets::scrollbar::Scrollbar},
+};
+
+/// Horizontal space taken by the border and the padding
+const HORIZONTAL_MARGIN: u16 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FragmentKind {
+    Text,
+    Link,
+    Code,
+}
+
+/// Piece of the rendered content. the style is resolved by the theme when rendering
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Fragment {
+    text: String,
+    kind: FragmentKind,
+    modifier: Modifier,
+}
+
+impl Fragment {
+    fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            kind: FragmentKind::Text,
+            modifier: Modifier::empty(),
+        }
+    }
+}
+
+/// Entry content laid out for the terminal width
+#[derive(Debug)]
+struct Document {
+    feed_url: FeedUrl,
+    id: String,
+    title: String,
+    website_url: Option<String>,
+    /// Sanitized html, or plain text until the content is fetched or if the entry has no content
+    source: Result<String, String>,
+    lines: Vec<Vec<Fragment>>,
+    links: Vec<String>,
+}
+
+impl Document {
+    fn layout(&mut self, width: u16) {
+        let width = usize::from(width.saturating_sub(HORIZONTAL_MARGIN).max(1));
+        (self.lines, self.links) = match &self.source {
+            Ok(html) => render_html(html, width),
+            Err(text) => (
+                text.lines()
+                    .map(|line| vec![Fragment::text(line)])
+                    .collect(),
+                Vec::new(),
+            ),
+        };
+    }
+}
+
+/// Scrollable view of the entry content
+pub(crate) struct Reader {
+    document: Option<Document>,
+    columns: u16,
+    scroll: usize,
+    page: usize,
+    /// Link number being typed
+    link_number: Option<usize>,
+}
+
+impl Reader {
+    pub(crate) fn new() -> Self {
+        Self {
+            document: None,
+            columns: 0,
+            scroll: 0,
+            page: 1,
+            link_number: None,
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.document.is_some()
+    }
+
+    /// Open the entry with the terminal size. the summary is shown until the content is set
+    pub(crate) fn open(&mut self, entry: &types::Entry, (columns, rows): (u16, u16)) {
+        self.document = Some(Document {
+            feed_url: entry.feed_url.clone(),
+            id: entry.id.clone(),
+            title: entry
+                .title
+                .clone()
+                .unwrap_or_else(|| ui::UNKNOWN_SYMBOL.to_owned()),
+            website_url: entry.website_url.clone(),
+            source: Err(entry.summary_text.clone().unwrap_or_default()),
+            lines: Vec::new(),
+            links: Vec::new(),
+        });
+        self.scroll = 0;
+        self.link_number = None;
+        self.resize(columns, rows);
+    }
+
+    /// Replace the summary with the fetched content if the entry is still open
+    pub(crate) fn set_content(&mut self, feed_url: &FeedUrl, id: &str, html: Option<String>) {
+        let Some(document) = self
+            .document
+            .as_mut()
+            .filter(|document| &document.feed_url == feed_url && document.id == id)
+        else {
+            return;
+        };
+        let Some(html) = html.filter(|html| !html.trim().is_empty()) else {
+            return;
+        };
+        document.source = Ok(html);
+        document.layout(self.columns);
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    pub(crate) fn close(&mut self) {
+        self.document = None;
+        self.link_number = None;
+    }
+
+    pub(crate) fn resize(&mut self, columns: u16, rows: u16) {
+        self.columns = columns;
+        self.page = usize::from(rows / 2).max(1);
+        if let Some(document) = self.document.as_mut() {
+            document.layout(columns);
+            self.scroll = self.scroll.min(document.lines.len().saturating_sub(1));
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.document
+            .as_ref()
+            .map_or(0, |document| document.lines.len().saturating_sub(1))
+    }
+
+    pub(crate) fn scroll(&mut self, direction: Direction) {
+        self.scroll_by(direction, 1);
+    }
+
+    pub(crate) fn scroll_page(&mut self, direction: Direction) {
+        self.scroll_by(direction, self.page);
+    }
+
+    fn scroll_by(&mut self, direction: Direction, lines: usize) {
+        self.scroll = match direction {
+            Direction::Up | Direction::Left => self.scroll.saturating_sub(lines),
+            Direction::Down | Direction::Right => (self.scroll + lines).min(self.max_scroll()),
+        };
+    }
+
+    pub(crate) fn scroll_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub(crate) fn scroll_bottom(&mut self) {
+        self.scroll = self.max_scroll();
+    }
+
+    pub(crate) fn input_link_number(&mut self, digit: u8) {
+        self.link_number = self
+            .link_number
+            .unwrap_or_default()
+            .checked_mul(10)
+            .and_then(|number| number.checked_add(usize::from(digit)));
+    }
+
+    /// Take the url of the link whose number was typed.
+    /// Relative links are resolved against the website url of the entry
+    pub(crate) fn take_link(&mut self) -> Option<Result<Url, String>> {
+        let number = self.link_number.take()?;
+        let document = self.document.as_ref()?;
+        let Some(link) = number
+            .checked_sub(1)
+            .and_then(|index| document.links.get(index))
+        else {
+            return Some(Err(format!("link [{number}] not found")));
+        };
+        let url = match document
+            .website_url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok())
+        {
+            Some(base) => base.join(link),
+            None => Url::parse(link),
+        };
+        Some(url.map_err(|err| format!("invalid link {link}: {err}")))
+    }
+}
+
+impl Reader {
+    pub(crate) fn render(&self, area: Rect, buf: &mut Buffer, cx: &Context<'_>) {
+        let Some(document) = self.document.as_ref() else {
+            return;
+        };
+
+        let mut title = vec![
+            Span::from(concat!(icon!(entry), " ")),
+            Span::from(document.title.as_str()),
+        ];
+        if let Some(number) = self.link_number {
+            title.push(Span::from(format!("  {} [{number}]", icon!(open))));
+        }
+        let block = Block::new()
+            .title(Line::from(title).style(cx.theme.reader.title))
+            .title_bottom(
+                Line::from(document.website_url.as_deref().unwrap_or_default())
+                    .style(cx.theme.reader.link),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(2));
+        let inner = block.inner(area);
+        Widget::render(block, area, buf);
+
+        let [content_area, scrollbar_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+
+        let style = |kind: FragmentKind| match kind {
+            FragmentKind::Text => cx.theme.reader.text,
+            FragmentKind::Link => cx.theme.reader.link,
+            FragmentKind::Code => cx.theme.reader.code,
+        };
+        let lines = document
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .take(usize::from(content_area.height))
+            .map(|fragments| {
+                Line::from(
+                    fragments
+                        .iter()
+                        .map(|fragment| {
+                            Span::styled(
+                                fragment.text.as_str(),
+                                style(fragment.kind).add_modifier(fragment.modifier),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Paragraph::new(lines)
+            .style(cx.theme.reader.text)
+            .render(content_area, buf);
+
+        Scrollbar {
+            content_length: document.lines.len(),
+            position: self.scroll,
+        }
+        .render(scrollbar_area, buf, cx);
+    }
+}
+
+/// Lay out the html in the width, numbering the links in the order of appearance.
+/// The links are also listed as footnotes at the end
+fn render_html(html: &str, width: usize) -> (Vec<Vec<Fragment>>, Vec<String>) {
+    let tagged_lines = match html2text::config::rich().lines_from_read(html.as_bytes(), width) {
+        Ok(lines) => lines,
+        Err(err) => {
+            tracing::warn!("Failed to render html: {err}");
+            return (
+                html.lines()
+                    .map(|line| vec![Fragment::text(line)])
+                    .collect(),
+                Vec::new(),
+            );
+        }
+    };
+
+    let mut urls: Vec<String> = Vec::new();
+    let mut lines: Vec<Vec<Fragment>> = tagged_lines
+        .iter()
+        .map(|line| {
+            let strings = line.tagged_strings().collect::<Vec<_>>();
+            let mut fragments = Vec::with_capacity(strings.len());
+            for (i, string) in strings.iter().enumerate() {
+                let mut fragment = Fragment::text(string.s.as_str());
+                let mut link = None;
+                for annotation in &string.tag {
+                    match annotation {
+                        RichAnnotation::Link(url) => {
+                            fragment.kind = FragmentKind::Link;
+                            link = Some(url);
+                        }
+                        RichAnnotation::Code | RichAnnotation::Preformat(_)
+                            if fragment.kind == FragmentKind::Text =>
+                        {
+                            fragment.kind = FragmentKind::Code;
+                        }
+                        RichAnnotation::Emphasis => fragment.modifier |= Modifier::ITALIC,
+                        RichAnnotation::Strong => fragment.modifier |= Modifier::BOLD,
+                        RichAnnotation::Strikeout => fragment.modifier |= Modifier::CROSSED_OUT,
+                        _ => {}
+                    }
+                }
+                fragments.push(fragment);
+
+                // Number the link after its last fragment in the line
+                let Some(url) = link else { continue };
+                let continued = strings.get(i + 1).is_some_and(|next| {
+                    next.tag
+                        .iter()
+                        .any(|annotation| matches!(annotation, RichAnnotation::Link(next) if next == url))
+                });
+                if !continued {
+                    let number = urls.iter().position(|link| link == url).unwrap_or_else(|| {
+                        urls.push(url.clone());
+                        urls.len() - 1
+                    }) + 1;
+                    fragments.push(Fragment {
+                        text: format!("[{number}]"),
+                        kind: FragmentKind::Link,
+                        modifier: Modifier::empty(),
+                    });
+                }
+            }
+            fragments
+        })
+        .collect();
+
+    if !urls.is_empty() {
+        lines.push(Vec::new());
+        lines.extend(urls.iter().enumerate().map(|(i, url)| {
+            vec![
+                Fragment::text(format!("[{}] ", i + 1)),
+                Fragment {
+                    text: url.clone(),
+                    kind: FragmentKind::Link,
+                    modifier: Modifier::empty(),
+                },
+            ]
+        }));
+    }
+
+    (lines, urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake as _, Faker};
+
+    use super::*;
+
+    fn texts(lines: &[Vec<Fragment>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.iter().map(|fragment| fragment.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn number_links() {
+        let html = r#"<p>Read <a href="https://example.com/a">the <strong>docs</strong></a>, <em>then</em> <a href="https://example.com/b">b</a> and <a href="https://example.com/a">a again</a></p><pre>cargo run</pre>"#;
+        let (lines, urls) = render_html(html, 80);
+
+        assert_eq!(urls, vec!["https://example.com/a", "https://example.com/b"]);
+        assert_eq!(
+            texts(&lines),
+            vec![
+                "Read the docs[1], then b[2] and a again[1]",
+                "",
+                "cargo run",
+                "",
+                "[1] https://example.com/a",
+                "[2] https://example.com/b",
+            ]
+        );
+
+        let first = &lines[0];
+        assert!(first.iter().any(|fragment| fragment.text == "docs"
+            && fragment.kind == FragmentKind::Link
+            && fragment.modifier.contains(Modifier::BOLD)));
+        assert!(first.iter().any(
+            |fragment| fragment.text == "then" && fragment.modifier.contains(Modifier::ITALIC)
+        ));
+        assert_eq!(lines[2][0].kind, FragmentKind::Code);
+    }
+
+    #[test]
+    fn set_content_of_open_entry() {
+        let mut entry: types::Entry = Faker.fake();
+        entry.id = "1".into();
+        entry.summary_text = Some("summary".into());
+        let mut reader = Reader::new();
+        reader.open(&entry, (80, 24));
+        assert_eq!(
+            texts(&reader.document.as_ref().unwrap().lines),
+            vec!["summary"]
+        );
+
+        // Content of the other entry is ignored
+        reader.set_content(&entry.feed_url, "2", Some("<p>other</p>".into()));
+        assert_eq!(
+            texts(&reader.document.as_ref().unwrap().lines),
+            vec!["summary"]
+        );
+        // The summary is kept if the entry has no content
+        reader.set_content(&entry.feed_url, "1", None);
+        assert_eq!(
+            texts(&reader.document.as_ref().unwrap().lines),
+            vec!["summary"]
+        );
+
+        reader.set_content(&entry.feed_url, "1", Some("<p>content</p>".into()));
+        assert_eq!(
+            texts(&reader.document.as_ref().unwrap().lines),
+            vec!["content"]
+        );
+    }
+
+    #[test]
+    fn follow_link() {
+        let mut reader = Reader::new();
+        reader.document = Some(Document {
+            feed_url: "https://example.com/feed.xml".try_into().unwrap(),
+            id: String::new(),
+            title: String::new(),
+            website_url: None,
+            source: Ok(String::new()),
+            lines: Vec::new(),
+            links: (1..=11)
+                .map(|i| format!("https://example.com/{i}"))
+                .chain(std::iter::once("/relative".to_owned()))
+                .collect(),
+        });
+        let url = |s: &str| Ok(Url::parse(s).unwrap());
+
+        assert_eq!(reader.take_link(), None);
+        reader.input_link_number(1);
+        reader.input_link_number(1);
+        assert_eq!(reader.take_link(), Some(url("https://example.com/11")));
+        reader.input_link_number(0);
+        assert_eq!(reader.take_link(), Some(Err("link [0] not found".into())));
+        reader.input_link_number(1);
+        reader.input_link_number(3);
+        assert_eq!(reader.take_link(), Some(Err("link [13] not found".into())));
+
+        // Relative links require the website url of the entry
+        reader.input_link_number(1);
+        reader.input_link_number(2);
+        assert!(matches!(reader.take_link(), Some(Err(_))));
+        reader.document.as_mut().unwrap().website_url =
+            Some("https://blog.example.com/posts/1".into());
+        reader.input_link_number(1);
+        reader.input_link_number(2);
+        assert_eq!(
+            reader.take_link(),
+            Some(url("https://blog.example.com/relative"))
+        );
+        // Absolute links are kept
+        reader.input_link_number(1);
+        assert_eq!(reader.take_link(), Some(url("https://example.com/1")));
+    }
+}
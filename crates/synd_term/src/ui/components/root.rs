@@ -30,6 +30,16 @@ impl<'a> Root<'a> {
         let [tabs_area, filter_area, content_area, prompt_area] = layout.areas(area);
 
         self.components.tabs.render(tabs_area, buf, cx);
+
+        // The reader covers the filter and the content while it is open
+        if self.components.reader.is_open() {
+            self.components
+                .reader
+                .render(filter_area.union(content_area), buf, cx);
+            self.components.prompt.render_reader(prompt_area, buf, cx);
+            return;
+        }
+
         self.components.filter.render(
             filter_area,
             buf,
@@ -69,6 +79,12 @@ impl Widget for Root<'_> {
                 .render(prompt_area, buf, &self.cx, None);
         } else {
             self.render_browse(area, buf);
+            if self.components.command_palette.is_open() {
+                self.components.command_palette.render(area, buf, &self.cx);
+            }
+            if self.components.help.is_open() {
+                self.components.help.render(area, buf, &self.cx);
+            }
         }
     }
 }
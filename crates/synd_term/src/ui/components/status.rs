@@ -47,7 +47,22 @@ impl StatusLine {
         }
     }
 
-    #[allow(clippy::cast_possible_truncation)]
+    pub fn render_reader(&self, area: Rect, buf: &mut Buffer, cx: &Context<'_>) {
+        let keys = &[
+            ("j/k", "󰹹"),
+            ("Sp/b", "󰹹"),
+            ("gg", "󱞧"),
+            ("ge", "󱞥"),
+            ("N Ent", icon!(open)),
+            ("o", icon!(browse)),
+            ("q", ""),
+        ][..];
+        match self.error_message.as_ref() {
+            Some(error_message) => Self::render_error(area, buf, cx, error_message),
+            None => Self::render_keys(area, buf, cx, keys.iter()),
+        }
+    }
+
     fn render_prompt(area: Rect, buf: &mut Buffer, cx: &Context<'_>, tab: Option<Tab>) {
         let pre_keys = &[
             ("Tab", "󰹳"),
@@ -75,6 +90,8 @@ impl StatusLine {
                     ("h/l", icon!(requirement)),
                     ("Ent", icon!(open)),
                     ("Sp", icon!(browse)),
+                    ("p", icon!(media)),
+                    ("v", icon!(reader)),
                 ])
                 .chain(suf_keys),
             Some(Tab::GitHub) => pre_keys
@@ -93,7 +110,17 @@ impl StatusLine {
                 .chain(&[("q", "")][..]),
         };
 
-        let spans = per_tab_keys
+        Self::render_keys(area, buf, cx, per_tab_keys);
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn render_keys<'k>(
+        area: Rect,
+        buf: &mut Buffer,
+        cx: &Context<'_>,
+        keys: impl Iterator<Item = &'k (&'k str, &'k str)>,
+    ) {
+        let spans = keys
             .flat_map(|(key, desc)| {
                 let desc = Span::styled(format!("{key}:{desc}  "), cx.theme.prompt.key_desc);
                 [desc]
@@ -108,6 +135,7 @@ impl StatusLine {
                     }
                     RequestId::DeviceFlowPollAccessToken => Cow::Borrowed("Polling..."),
                     RequestId::FetchEntries => Cow::Borrowed("Fetch entries..."),
+                    RequestId::FetchEntryContent => Cow::Borrowed("Fetch entry content..."),
                     RequestId::FetchSubscription => Cow::Borrowed("Fetch subscription..."),
                     RequestId::FetchGithubNotifications { page } => {
                         Cow::Owned(format!("Fetch github notifications(page: {page})..."))
@@ -120,6 +148,7 @@ impl StatusLine {
                     }
                     RequestId::SubscribeFeed => Cow::Borrowed("Subscribe feed..."),
                     RequestId::UnsubscribeFeed => Cow::Borrowed("Unsubscribe feed..."),
+                    RequestId::UpdateFeeds => Cow::Borrowed("Update feeds..."),
                     RequestId::MarkGithubNotificationAsDone { id } => {
                         Cow::Owned(format!("Mark notification({id}) as done..."))
                     }
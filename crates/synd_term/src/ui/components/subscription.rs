@@ -18,8 +18,12 @@ use crate::{
     types::{self, EntryMeta, Feed, RequirementExt, TimeExt},
     ui::{
         self, Context,
-        components::{collections::FilterableVec, filter::FeedFilterer},
+        components::{
+            collections::{FilterableVec, Marking},
+            filter::FeedFilterer,
+        },
         extension::RectExt,
+        icon,
         widgets::{scrollbar::Scrollbar, table::Table},
     },
 };
@@ -47,7 +51,8 @@ impl UnsubscribeSelection {
 
 struct UnsubscribePopup {
     selection: UnsubscribeSelection,
-    selected_feed: Option<types::Feed>,
+    /// Feeds to unsubscribe, empty while the popup is closed
+    selected_feeds: Vec<types::Feed>,
 }
 
 impl Subscription {
@@ -56,7 +61,7 @@ impl Subscription {
             feeds: FilterableVec::new(),
             unsubscribe_popup: UnsubscribePopup {
                 selection: UnsubscribeSelection::Yes,
-                selected_feed: None,
+                selected_feeds: Vec::new(),
             },
         }
     }
@@ -73,20 +78,28 @@ impl Subscription {
         self.feeds.selected()
     }
 
+    /// Feeds the bulk operations act on
+    pub(crate) fn marked_feeds(&self) -> Vec<&types::Feed> {
+        self.feeds.marked_or_selected()
+    }
+
+    pub(crate) fn update_marks(&mut self, marking: Marking) {
+        self.feeds.update_marks(marking);
+    }
+
     pub(crate) fn toggle_unsubscribe_popup(&mut self, show: bool) {
         if show {
-            self.unsubscribe_popup.selected_feed = self.selected_feed().cloned();
+            self.unsubscribe_popup.selected_feeds =
+                self.marked_feeds().into_iter().cloned().collect();
         } else {
-            self.unsubscribe_popup.selected_feed = None;
+            self.unsubscribe_popup.selected_feeds.clear();
         }
     }
 
-    pub(crate) fn unsubscribe_popup_selection(
-        &self,
-    ) -> (UnsubscribeSelection, Option<&types::Feed>) {
+    pub(crate) fn unsubscribe_popup_selection(&self) -> (UnsubscribeSelection, &[types::Feed]) {
         (
             self.unsubscribe_popup.selection,
-            self.unsubscribe_popup.selected_feed.as_ref(),
+            self.unsubscribe_popup.selected_feeds.as_slice(),
         )
     }
 
@@ -104,6 +117,15 @@ impl Subscription {
         FilterableVec::update(&mut self.feeds, populate, feeds);
     }
 
+    /// Show the feeds cached in the previous session
+    pub(crate) fn restore_feeds(&mut self, feeds: Vec<types::Feed>) {
+        FilterableVec::update(&mut self.feeds, Populate::Replace, feeds);
+    }
+
+    pub(crate) fn feeds(&self) -> &[types::Feed] {
+        self.feeds.as_unfiltered_slice()
+    }
+
     pub(crate) fn update_filterer(&mut self, filterer: FeedFilterer) {
         self.feeds.update_filter(filterer);
     }
@@ -144,8 +166,8 @@ impl Subscription {
         self.render_feeds(feeds_area, buf, cx);
         self.render_feed_detail(feed_detail_area, buf, cx);
 
-        if let Some(feed) = self.unsubscribe_popup.selected_feed.as_ref() {
-            self.render_unsubscribe_popup(area, buf, cx, feed);
+        if !self.unsubscribe_popup.selected_feeds.is_empty() {
+            self.render_unsubscribe_popup(area, buf, cx, &self.unsubscribe_popup.selected_feeds);
         }
     }
 
@@ -200,9 +222,13 @@ impl Subscription {
                 )
             }
         };
+        let marks = match self.feeds.marked().len() {
+            0 => String::new(),
+            k => format!(" {} {k}", icon!(check)),
+        };
         let header = Row::new([
             Cell::from("Updated"),
-            Cell::from(format!("Feed {n}/{m}")),
+            Cell::from(format!("Feed {n}/{m}{marks}")),
             Cell::from("URL"),
             Cell::from("Description"),
             Cell::from("Req"),
@@ -216,7 +242,7 @@ impl Subscription {
             Constraint::Length(4),
         ];
 
-        let row = |feed_meta: &'a Feed| {
+        let row = |(feed_meta, marked): (&'a Feed, bool)| {
             let title = feed_meta.title.as_deref().unwrap_or(ui::UNKNOWN_SYMBOL);
             let updated = feed_meta
                 .updated
@@ -241,6 +267,12 @@ impl Subscription {
             Row::new([
                 Cell::from(Span::from(updated)),
                 Cell::from(Line::from(vec![
+                    Span::from(if marked {
+                        concat!(icon!(check), " ")
+                    } else {
+                        ""
+                    })
+                    .bold(),
                     Span::from(icon.symbol()).fg(icon.color().unwrap_or(cx.theme.default_icon_fg)),
                     Span::from(" "),
                     Span::from(title),
@@ -256,7 +288,7 @@ impl Subscription {
             ])
         };
 
-        (header, constraints, self.feeds.iter().map(row))
+        (header, constraints, self.feeds.iter_with_mark().map(row))
     }
 
     #[allow(clippy::too_many_lines)]
@@ -349,7 +381,11 @@ impl Subscription {
                 .as_ref()
                 .or(entry.updated.as_ref())
                 .map_or_else(|| ui::UNKNOWN_SYMBOL.to_string(), TimeExt::local_ymd);
-            let summary = entry.summary_text(100).unwrap_or(ui::UNKNOWN_SYMBOL.into());
+            let summary = entry
+                .summary_text
+                .as_deref()
+                .unwrap_or(ui::UNKNOWN_SYMBOL)
+                .to_owned();
 
             Row::new([
                 Cell::new(Span::from(published)),
@@ -378,7 +414,7 @@ impl Subscription {
         area: Rect,
         buf: &mut Buffer,
         cx: &Context<'_>,
-        feed: &types::Feed,
+        feeds: &[types::Feed],
     ) {
         let area = {
             let area = area.centered(60, 60);
@@ -411,32 +447,54 @@ impl Subscription {
 
         block.render(area, buf);
 
-        // for align line
-        let feed_n = "Feed: ".len() + feed.title.as_deref().unwrap_or("-").len();
-        let url_n = "URL : ".len() + feed.url.as_str().len();
-
-        Paragraph::new(vec![
-            Line::from("Do you unsubscribe from this feed?"),
-            Line::from(""),
-            Line::from(vec![
-                Span::from("Feed: "),
-                Span::from(feed.title.as_deref().unwrap_or("-")).bold(),
-                Span::from(" ".repeat(url_n.saturating_sub(feed_n))),
-            ]),
-            Line::from(vec![
-                Span::from("URL : "),
-                Span::from(feed.url.to_string()).bold(),
-                Span::from(" ".repeat(feed_n.saturating_sub(url_n))),
-            ]),
-        ])
-        .alignment(Alignment::Center)
-        .block(
-            Block::new()
-                .borders(Borders::BOTTOM)
-                .border_type(BorderType::Plain)
-                .border_style(Style::new().add_modifier(Modifier::DIM)),
-        )
-        .render(info_area, buf);
+        let info = if let [feed] = feeds {
+            // for align line
+            let feed_n = "Feed: ".len() + feed.title.as_deref().unwrap_or("-").len();
+            let url_n = "URL : ".len() + feed.url.as_str().len();
+
+            vec![
+                Line::from("Do you unsubscribe from this feed?"),
+                Line::from(""),
+                Line::from(vec![
+                    Span::from("Feed: "),
+                    Span::from(feed.title.as_deref().unwrap_or("-")).bold(),
+                    Span::from(" ".repeat(url_n.saturating_sub(feed_n))),
+                ]),
+                Line::from(vec![
+                    Span::from("URL : "),
+                    Span::from(feed.url.to_string()).bold(),
+                    Span::from(" ".repeat(feed_n.saturating_sub(url_n))),
+                ]),
+            ]
+        } else {
+            let mut info = vec![
+                Line::from(format!(
+                    "Do you unsubscribe from these {} feeds?",
+                    feeds.len()
+                )),
+                Line::from(""),
+            ];
+            info.extend(feeds.iter().map(|feed| {
+                Line::from(
+                    feed.title
+                        .as_deref()
+                        .unwrap_or(feed.url.as_str())
+                        .to_owned(),
+                )
+                .bold()
+            }));
+            info
+        };
+
+        Paragraph::new(info)
+            .alignment(Alignment::Center)
+            .block(
+                Block::new()
+                    .borders(Borders::BOTTOM)
+                    .border_type(BorderType::Plain)
+                    .border_style(Style::new().add_modifier(Modifier::DIM)),
+            )
+            .render(info_area, buf);
 
         // align center
         let horizontal =
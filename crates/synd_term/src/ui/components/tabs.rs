@@ -1,11 +1,12 @@
 use ratatui::{
     prelude::{Buffer, Constraint, Layout, Rect},
-    text::Span,
+    text::{Line, Span},
     widgets::{Paragraph, Tabs as TuiTabs, Widget},
 };
 
 use crate::{
     application::{Direction, Features, IndexOutOfRange},
+    types::{Time, TimeExt},
     ui::{Context, icon},
 };
 
@@ -26,9 +27,21 @@ impl Tab {
     }
 }
 
+/// Whether the shown data is kept up to date with the api
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub(crate) enum Connection {
+    Online,
+    /// The api is unreachable, so the data fetched at `stale_since` is shown
+    Offline {
+        stale_since: Option<Time>,
+    },
+}
+
+#[allow(clippy::struct_field_names)]
 pub struct Tabs {
     pub selected: usize,
     pub tabs: Vec<Tab>,
+    connection: Connection,
 }
 
 impl Tabs {
@@ -39,7 +52,15 @@ impl Tabs {
         if features.enable_github_notification {
             tabs.insert(0, Tab::GitHub);
         }
-        Self { selected: 0, tabs }
+        Self {
+            selected: 0,
+            tabs,
+            connection: Connection::Online,
+        }
+    }
+
+    pub(crate) fn set_connection(&mut self, connection: Connection) {
+        self.connection = connection;
     }
 
     pub fn current(&self) -> Tab {
@@ -70,7 +91,19 @@ impl Tabs {
         let horizontal = Layout::horizontal([Constraint::Min(0), Constraint::Length(self.width())]);
         let [title, tabs] = horizontal.areas(area);
 
-        Paragraph::new(Span::styled("Syndicationd", cx.theme.application_title)).render(title, buf);
+        let mut spans = vec![Span::styled("Syndicationd", cx.theme.application_title)];
+        if let Connection::Offline { stale_since } = self.connection {
+            let status = match stale_since {
+                Some(fetched_at) => format!(
+                    "  {} offline / stale since {}",
+                    icon!(offline),
+                    fetched_at.local_ymd_hm()
+                ),
+                None => format!("  {} offline", icon!(offline)),
+            };
+            spans.push(Span::styled(status, cx.theme.error.message));
+        }
+        Paragraph::new(Line::from(spans)).render(title, buf);
 
         TuiTabs::new(self.tabs.iter().map(|tab| match tab {
             Tab::Entries => concat!(icon!(entries), " Entries"),
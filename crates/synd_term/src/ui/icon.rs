@@ -1,5 +1,6 @@
 #[rustfmt::skip]
 macro_rules! icon {
+    (bookmark)          => { "" };
     (browse)            => { "󰏋" };
     (feeds)             => { "󰑫" };
     (feedsoff)          => { "󰑫" };
@@ -13,6 +14,8 @@ macro_rules! icon {
     (discussion)        => { "" };
     (entry)             => { "󰯂" };
     (filter)            => { "󰈶" };
+    (folder)            => { "" };
+    (folderopen)        => { "" };
     (github)            => { "󰊤" };
     (google)            => { "󰊭" };
     (issueopen)         => { "" };
@@ -20,14 +23,19 @@ macro_rules! icon {
     (issuenotplanned)   => { "" };
     (issueclosed)       => { "" };
     (label)             => { "󱍵" };
+    (media)             => { "󰝚" };
     (requirement)       => { "" };
+    (offline)           => { "󰖪" };
+    (oidc)              => { "󰌆" };
     (open)              => { "󰏌" };
     (pullrequest)       => { "" };
     (pullrequestmerged) => { "" };
     (pullrequestclosed) => { "" };
     (pullrequestdraft)  => { "" };
+    (reader)            => { "󰂺" };
     (repository)        => { "" };
     (search)            => { "" };
+    (sort)              => { "󰒺" };
     (summary)           => { "󱙓" };
     (tag)               => { "󰓹" };
     (unread)            => { "󰮒" };
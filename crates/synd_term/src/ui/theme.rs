@@ -1,8 +1,8 @@
 use ratatui::style::{Color, Modifier, Style, Stylize};
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Theme {
-    pub name: &'static str,
+    pub name: String,
     pub base: Style,
     pub application_title: Style,
     pub login: LoginTheme,
@@ -15,41 +15,42 @@ pub struct Theme {
     pub default_icon_fg: Color,
     pub requirement: RequirementLabelTheme,
     pub selection_popup: SelectionPopup,
+    pub reader: ReaderTheme,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct LoginTheme {
     pub title: Style,
     pub selected_auth_provider_item: Style,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct ErrorTheme {
     pub message: Style,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct PromptTheme {
     pub key: Style,
     pub key_desc: Style,
     pub background: Style,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct SubscriptionTheme {
     pub background: Style,
     pub header: Style,
     pub selected_feed: Style,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct EntriesTheme {
     pub header: Style,
     pub selected_entry: Style,
     pub summary: Style,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct RequirementLabelTheme {
     pub must: Color,
     pub should: Color,
@@ -57,25 +58,33 @@ pub struct RequirementLabelTheme {
     pub fg: Color,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
+pub struct ReaderTheme {
+    pub title: Style,
+    pub text: Style,
+    pub link: Style,
+    pub code: Style,
+}
+
+#[derive(Clone, Debug)]
 pub struct SelectionPopup {
     pub highlight: Style,
 }
 
 #[derive(Clone, Debug)]
 pub struct Palette {
-    name: &'static str,
-    bg: Color,
-    fg: Color,
-    fg_inactive: Color,
-    fg_focus: Color,
-    error: Color,
+    pub(crate) name: String,
+    pub(crate) bg: Color,
+    pub(crate) fg: Color,
+    pub(crate) fg_inactive: Color,
+    pub(crate) fg_focus: Color,
+    pub(crate) error: Color,
 }
 
 impl Palette {
     pub fn dracula() -> Self {
         Self {
-            name: "dracula",
+            name: "dracula".into(),
             bg: Color::Rgb(0x28, 0x2a, 0x36),
             fg: Color::Rgb(0xf8, 0xf8, 0xf2),
             fg_inactive: Color::Rgb(0x62, 0x72, 0xa4),
@@ -86,7 +95,7 @@ impl Palette {
 
     pub fn eldritch() -> Self {
         Self {
-            name: "eldritch",
+            name: "eldritch".into(),
             bg: Color::Rgb(0x21, 0x23, 0x37),
             fg: Color::Rgb(0xeb, 0xfa, 0xfa),
             fg_inactive: Color::Rgb(0x70, 0x81, 0xd0),
@@ -97,7 +106,7 @@ impl Palette {
 
     pub fn helix() -> Self {
         Self {
-            name: "helix",
+            name: "helix".into(),
             bg: Color::Rgb(0x3b, 0x22, 0x4c),
             fg: Color::Rgb(0xa4, 0xa0, 0xe8),
             fg_inactive: Color::Rgb(0x69, 0x7c, 0x81),
@@ -108,7 +117,7 @@ impl Palette {
 
     pub fn ferra() -> Self {
         Self {
-            name: "ferra",
+            name: "ferra".into(),
             bg: Color::Rgb(0x2b, 0x29, 0x2d),
             fg: Color::Rgb(0xfe, 0xcd, 0xb2),
             fg_inactive: Color::Rgb(0x6F, 0x5D, 0x63),
@@ -119,7 +128,7 @@ impl Palette {
 
     pub fn solarized_dark() -> Self {
         Self {
-            name: "solarized_dark",
+            name: "solarized_dark".into(),
             bg: Color::Rgb(0x00, 0x2b, 0x36),
             fg: Color::Rgb(0x93, 0xa1, 0xa1),
             fg_inactive: Color::Rgb(0x58, 0x6e, 0x75),
@@ -130,7 +139,6 @@ impl Palette {
 }
 
 impl Theme {
-    #[allow(clippy::needless_pass_by_value)]
     pub fn with_palette(p: Palette) -> Self {
         let Palette {
             name,
@@ -179,10 +187,74 @@ impl Theme {
             selection_popup: SelectionPopup {
                 highlight: Style::new().bg(Color::Yellow).fg(bg),
             },
+            reader: ReaderTheme {
+                title: Style::new().fg(fg_focus).add_modifier(Modifier::BOLD),
+                text: Style::new().fg(fg),
+                link: Style::new().fg(fg_focus).add_modifier(Modifier::UNDERLINED),
+                code: Style::new().fg(fg_inactive),
+            },
         }
     }
 }
 
+impl Theme {
+    /// Builtin themes in the order of rotation
+    pub fn builtins() -> Vec<Theme> {
+        [
+            Palette::ferra(),
+            Palette::solarized_dark(),
+            Palette::helix(),
+            Palette::dracula(),
+            Palette::eldritch(),
+        ]
+        .into_iter()
+        .map(Theme::with_palette)
+        .collect()
+    }
+
+    /// Lookup the style by the key used in the theme configuration such as `entries.header`
+    pub(crate) fn style_mut(&mut self, key: &str) -> Option<&mut Style> {
+        let style = match key {
+            "base" => &mut self.base,
+            "application_title" => &mut self.application_title,
+            "login.title" => &mut self.login.title,
+            "login.selected_auth_provider_item" => &mut self.login.selected_auth_provider_item,
+            "tabs" => &mut self.tabs,
+            "tabs_selected" => &mut self.tabs_selected,
+            "prompt.key" => &mut self.prompt.key,
+            "prompt.key_desc" => &mut self.prompt.key_desc,
+            "prompt.background" => &mut self.prompt.background,
+            "subscription.background" => &mut self.subscription.background,
+            "subscription.header" => &mut self.subscription.header,
+            "subscription.selected_feed" => &mut self.subscription.selected_feed,
+            "entries.header" => &mut self.entries.header,
+            "entries.selected_entry" => &mut self.entries.selected_entry,
+            "entries.summary" => &mut self.entries.summary,
+            "error.message" => &mut self.error.message,
+            "selection_popup.highlight" => &mut self.selection_popup.highlight,
+            "reader.title" => &mut self.reader.title,
+            "reader.text" => &mut self.reader.text,
+            "reader.link" => &mut self.reader.link,
+            "reader.code" => &mut self.reader.code,
+            _ => return None,
+        };
+        Some(style)
+    }
+
+    /// Lookup the color by the key used in the theme configuration such as `requirement.must`
+    pub(crate) fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        let color = match key {
+            "default_icon_fg" => &mut self.default_icon_fg,
+            "requirement.must" => &mut self.requirement.must,
+            "requirement.should" => &mut self.requirement.should,
+            "requirement.may" => &mut self.requirement.may,
+            "requirement.fg" => &mut self.requirement.fg,
+            _ => return None,
+        };
+        Some(color)
+    }
+}
+
 impl Default for Theme {
     fn default() -> Self {
         Theme::with_palette(Palette::ferra())
@@ -33,6 +33,11 @@ impl Prompt {
         self.line.as_str()
     }
 
+    pub fn set_line(&mut self, line: impl Into<String>) {
+        self.line = line.into();
+        self.cursor = self.line.len();
+    }
+
     fn insert_char(&mut self, c: char) {
         self.line.insert(self.cursor, c);
         let mut cursor = GraphemeCursor::new(self.cursor, self.line.len(), true);
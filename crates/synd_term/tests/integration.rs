@@ -2,6 +2,8 @@
 mod test {
     use std::path::{Path, PathBuf};
 
+    use ratatui::buffer::Cell;
+
     use synd_term::{
         application::{Config, Features},
         auth::Credential,
@@ -308,8 +310,10 @@ mod test {
             mock_port: 6030,
             synd_api_port: 6031,
             kvsd_port: 47409,
-            kvsd_root_dir: PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                .join("tests/fixtures/kvsd/20240609"),
+            kvsd_root_dir: synd_test::temp_dir_from(
+                PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/kvsd/20240609"),
+            )
+            .into_path(),
             terminal_col_row: (120, 30),
             config: Config {
                 // To test pagination
@@ -430,6 +434,29 @@ mod test {
                 key!(esc),
             ]);
         }
+
+        // Restore the entries persisted after the fetch while the api is unreachable
+        {
+            let offline_case = TestCase {
+                synd_api_port: 6032,
+                ..test_case.clone()
+            };
+            let mut application = offline_case.init_app_without_api().await?;
+            let (_tx, mut event_stream) = helper::event_stream();
+            application
+                .wait_until_jobs_completed(&mut event_stream)
+                .await;
+
+            let buffer = application.buffer();
+            let rows = buffer
+                .content
+                .chunks(usize::from(buffer.area.width))
+                .map(|row| row.iter().map(Cell::symbol).collect::<String>())
+                .collect::<Vec<_>>();
+            assert!(rows[0].contains("offline / stale since"), "{rows:#?}");
+            assert!(rows[4].contains("Entry 1/34"), "{rows:#?}");
+            assert!(rows.iter().any(|row| row.contains("This Week in Rust 549")));
+        }
         Ok(())
     }
 
@@ -5,7 +5,10 @@ use futures_util::future;
 use octocrab::Octocrab;
 use ratatui::backend::TestBackend;
 use synd_api::{
-    cli::{CacheOptions, KvsdOptions, ServeOptions, TlsOptions},
+    cli::{
+        AuthorizationOptions, CacheOptions, FetchOptions, KvsdOptions, OidcOptions, ServeOptions,
+        TlsOptions,
+    },
     client::github::GithubClient,
     dependency::Dependency,
     repository::kvsd::KvsdClient,
@@ -121,6 +124,12 @@ impl TestCase {
     }
 
     pub async fn init_app(&self) -> anyhow::Result<Application> {
+        self.run_api().await?;
+        self.init_app_without_api().await
+    }
+
+    /// Init the application whose api is unreachable unless the api of the other case is running
+    pub async fn init_app_without_api(&self) -> anyhow::Result<Application> {
         let TestCase {
             mock_port,
             synd_api_port,
@@ -134,8 +143,6 @@ impl TestCase {
             ..
         } = self.clone();
 
-        self.run_api().await?;
-
         // Configure application
         let application = {
             let endpoint = format!("https://localhost:{synd_api_port}/graphql")
@@ -156,6 +163,7 @@ impl TestCase {
                     .with_device_authorization_endpoint(Url::parse(&format!("http://localhost:{mock_port}/{device_flow_case}/google/login/device/code")).unwrap())
                     .with_token_endpoint(Url::parse(&format!("http://localhost:{mock_port}/{device_flow_case}/google/login/oauth/access_token")).unwrap())
                 ),
+                oidc: None,
             };
             let jwt_service = {
                 // client_id is used for verify jwt
@@ -268,13 +276,20 @@ pub async fn serve_api(
         kvsd_password: "test".into(),
     };
     let tls_options = TlsOptions {
-        certificate: synd_test::certificate(),
-        private_key: synd_test::private_key(),
+        no_tls: false,
+        certificate: Some(synd_test::certificate()),
+        private_key: Some(synd_test::private_key()),
+        reload_interval: Duration::from_secs(60),
     };
     let serve_options = ServeOptions {
         timeout: Duration::from_secs(10),
         body_limit_bytes: 1024 * 2,
         concurrency_limit: 100,
+        rate_limit_per_minute: 6000,
+        expensive_rate_limit_per_minute: 600,
+        graphql_depth_limit: 10,
+        graphql_complexity_limit: 20_000,
+        persisted_query_cache_size: 16,
     };
     let cache_options = CacheOptions {
         feed_cache_size_mb: 1,
@@ -297,6 +312,24 @@ pub async fn serve_api(
         tls_options,
         serve_options,
         cache_options,
+        FetchOptions {
+            fetch_timeout: Duration::from_secs(10),
+            fetch_connect_timeout: Duration::from_secs(10),
+            fetch_proxy: None,
+            fetch_root_ca: Vec::new(),
+            fetch_host_headers: None,
+            fetch_credential_key: None,
+            // Feeds are served by the mock server
+            fetch_allowed_hosts: vec!["localhost".into()],
+        },
+        OidcOptions {
+            oidc_issuer: None,
+            oidc_client_id: None,
+        },
+        AuthorizationOptions {
+            admin_accounts: Vec::new(),
+            read_only_accounts: Vec::new(),
+        },
         CancellationToken::new(),
     )
     .await
@@ -1,11 +1,13 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub mod jwt;
 pub mod kvsd;
 pub mod mock;
 
 pub const TEST_EMAIL: &str = "ymgyt@ymgyt.io";
+/// Email derived id of the user whose subscriptions are persisted in the kvsd fixture
 pub const TEST_USER_ID: &str = "899cf3fa5afc0aa1";
+pub const TEST_GITHUB_NODE_ID: &str = "MDQ6VXNlcjE=";
 pub const GITHUB_INVALID_TOKEN: &str = "github_invalid_token";
 
 pub fn certificate() -> PathBuf {
@@ -39,3 +41,24 @@ pub fn private_key_buff() -> Vec<u8> {
 pub fn temp_dir() -> tempfile::TempDir {
     tempfile::TempDir::new().unwrap()
 }
+
+/// Copy given fixture directory into a temp dir so that tests do not modify the fixture
+pub fn temp_dir_from(fixture: impl AsRef<Path>) -> tempfile::TempDir {
+    fn copy(src: &Path, dst: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(dst)?;
+        for entry in std::fs::read_dir(src)? {
+            let entry = entry?;
+            let dst = dst.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                copy(&entry.path(), &dst)?;
+            } else {
+                std::fs::copy(entry.path(), dst)?;
+            }
+        }
+        Ok(())
+    }
+
+    let dir = temp_dir();
+    copy(fixture.as_ref(), dir.path()).unwrap();
+    dir
+}
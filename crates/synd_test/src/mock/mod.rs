@@ -15,7 +15,10 @@ use synd_auth::device_flow::{
 };
 use tokio::net::TcpListener;
 
-use crate::{GITHUB_INVALID_TOKEN, TEST_EMAIL, certificate_buff, jwt::DUMMY_GOOGLE_JWT_KEY_ID};
+use crate::{
+    GITHUB_INVALID_TOKEN, TEST_EMAIL, TEST_GITHUB_NODE_ID, certificate_buff,
+    jwt::DUMMY_GOOGLE_JWT_KEY_ID,
+};
 
 mod feed;
 pub mod github;
@@ -151,6 +154,7 @@ async fn github_graphql_viewer(
         let response = serde_json::json!({
             "data": {
                 "viewer": {
+                    "id": TEST_GITHUB_NODE_ID,
                     "email": TEST_EMAIL,
                 }
             }